@@ -28,10 +28,95 @@ together in the same file. An example file would be -
 string_name, default_locale         , spanish, french
 string_1   , string_1 default locale,
 string_3   , string_3 default locale,
+
+If any of the strings have context for translators (comments right above the
+string, its `description` attribute or the `example`s of its `xliff:g`
+placeholders), a `context` column is written after `default_locale`. Similarly,
+a `max_length` column is written if any of the strings declare one through
+`tools:maxLength`
+
+With `--translation-memory`, texts whose default text has already been
+translated (in this or the other modules passed in through `--memory-res-dir`)
+are pre-filled with that translation & a leverage report is printed out
+
+With `--include-localized`, every text is written out for every locale along
+with its current localized text (if any) so that they can be reviewed.
+`localized` only applies the texts that come back changed
+
+`--layout` can be used to write out a CSV per locale or a single CSV with
+all the locales instead. Files are named `to_localize_<index>.csv` (or
+`to_localize_<locale>.csv` when written per locale) unless
+`--file-name-template` says otherwise. Locales are always written out in the
+same order, so running this twice on the same project gives the same files
 "#;
 
         pub mod args {
-            pub static OUTPUT_DIR: &str = "Specifies output dir to write CSV files to";
+            pub static OUTPUT_DIR: &str =
+                "Specifies output dir to write files to; Pass in `-` to write a single CSV to stdout";
+            pub static TRANSLATION_MEMORY: &str =
+                "Set this to pre-fill texts that have already been translated elsewhere";
+            pub static MEMORY_RES_DIR: &str =
+                "Points to the `res` dir of another module whose translations should be reused";
+            pub static APPLY_MEMORY: &str =
+                "Set this to write reused translations into `strings.xml` instead of pre-filling";
+            pub mod file_name_template {
+                pub static SHORT: &str = "Template for the names of the written out files";
+                pub static LONG: &str = r#"
+Template for the names (without the extension) of the written out files. These
+placeholders are filled in -
+
+    - {locales}: Locales in the file joined by `_`
+    - {date}: Current date in UTC (YYYY-MM-DD)
+    - {module}: Name of the module the res dir is in
+    - {index}: Index of the file (starting at 1)
+
+Eg., `{module}_{locales}_{date}` could give `app_french_spanish_2019-03-25.csv`
+            "#;
+            }
+
+            pub static INCLUDE_LOCALIZED: &str =
+                "Set this to also write out already localized texts (along with their localized text) for review";
+            pub mod layout {
+                pub static SHORT: &str = "How locales are split across CSVs (defaults to grouped)";
+                pub static LONG: &str = r#"
+How locales are split across CSVs. Defaults to grouped. JSON & ARB documents
+are always written out per locale
+
+    - grouped: Locales missing the same texts are grouped into the same file
+    - per-locale: Every locale is written into a file of its own
+    - wide: All locales are written into a single file. Cells of texts that
+      are already localized are left empty
+            "#;
+            }
+
+            pub mod if_exists {
+                pub static SHORT: &str =
+                    "What to do if an output file already exists (defaults to fail)";
+                pub static LONG: &str = r#"
+What to do if a file with the same name already exists in the output dir.
+Defaults to fail
+
+    - fail: Nothing is written out
+    - overwrite: Existing file is replaced
+    - append: Rows are added to the existing CSV if it has the same columns
+    - timestamp: Current UTC time is added to the new file's name
+            "#;
+            }
+
+            pub mod format {
+                pub static SHORT: &str = "Format of the files to be written out (defaults to csv)";
+                pub static LONG: &str = r#"
+Format of the files to be written out. Defaults to csv
+
+    - csv: Locales missing the same texts are grouped into the same file
+    - json: A flat object of string name to text is written for each locale
+    - arb: Application Resource Bundle (used by Flutter) is written for each
+      locale. Along with the texts, the default text is carried in each
+      string's metadata (`@string_name`) so that it can be checked against
+      when the localized texts come back
+            "#;
+            }
+
             pub mod mapping {
                 pub static SHORT: &str = "Locale ID (fr) to CSV file name (french); Eg., fr=french";
                 pub static LONG: &str = r#"
@@ -65,11 +150,38 @@ string_3   , string_3 default locale, spanish value 2, french value 2
 
 When populating the `strings.xml` file, translated texts will be written
 only if that particular string's value has stayed the same in the default
-locale
+locale. With `--fuzzy-match`, translated texts are also written if the value
+has changed only a little. Such texts are listed out so that they can be
+reviewed
+
+Files ending with `.json` or `.arb` are read as JSON documents of string name
+to localized text (the format written by `localize --format json/arb`). The
+locale is read from `@@locale` if present & from the file name otherwise
 "#;
 
         pub mod args {
-            pub static INPUT_FILE: &str = "Specifies input CSV file to read localized texts from";
+            pub static INPUT_FILE: &str =
+                "Specifies input CSV file to read localized texts from; Pass in `-` to read from stdin";
+            pub static ESCAPE_MALFORMED_TEXTS: &str =
+                "Escapes localized texts that aren't well formed XML (like with a stray `<` or `&` or a tag) instead of failing";
+            pub mod fuzzy_match {
+                pub static SHORT: &str =
+                    "Uses texts localized from a slightly different default text; Eg., 0.9";
+                pub static LONG: &str = r#"
+By default, localized texts are only used if the default text they were
+localized from is the same as the current default text. This relaxes that
+
+    - whitespace-punctuation: Differences in whitespace & punctuation are
+      ignored (`Save  changes` matches `Save changes.`). Format specifiers
+      (`%1$d`) are kept as they are
+    - A number between 0 & 1: Texts that are at least this similar (going
+      by the number of chars to be edited) are matched
+
+Texts with different format specifiers (`%1$d` & `%2$d`) or numbers (`5 items`
+& `6 items`) are never matched. Localized texts used this way are listed out as needing review
+            "#;
+            }
+
             pub mod mapping {
                 pub static SHORT: &str = "CSV file name (french) to locale ID (fr); Eg., french=fr";
                 pub static LONG: &str = r#"
@@ -98,6 +210,21 @@ The following validations are run on the `strings.xml` files
     - Unescaped apostrophe (`'` without a preceeding `\`)
     - Format string mismatch with default locale (this could be either the
       number of format strings or the type of data they refer to)
+    - Foreign strings longer than the max length declared on the default
+      string (`tools:maxLength`) or, if a ratio is passed in, longer than
+      the default text times that ratio
+    - Names defined more than once across the XML files of a values dir
+    - Foreign strings & plurals whose default is marked
+      `translatable="false"` (these can be removed with
+      `--remove-untranslatable`)
+    - References (`@string/name`) to strings that aren't defined & foreign
+      texts replacing a reference of the default locale
+    - Plurals missing quantities required (as per CLDR) by the language of
+      their locale & plurals with quantities the language doesn't use
+    - With `--check-punctuation`, foreign texts whose whitespace or punctuation
+      isn't consistent with their default text
+    - With `--check-identical`, foreign texts that are the same as their
+      default text (most likely pasted in instead of being localized)
 
 Note: There are known corner cases whether these validations would be failing
 incorrectly. As of now, this validation is not aware of the allowed grammar
@@ -107,10 +234,115 @@ of the `strings.xml` files. This uses some naive regex to validate
         pub mod args {
             pub static SKIP_UNLOCALIZED: &str =
                 "Set this to not fail validation in case there are unlocalized default strings";
+            pub static FAIL_ON_UNUSED_QUANTITIES: &str =
+                "Set this to fail validation in case plurals have quantities their language doesn't use";
+            pub static MAX_LENGTH_RATIO: &str =
+                "Foreign strings longer than the default text times this ratio fail validation; Eg., 1.5";
+            pub static CHECK_IDENTICAL: &str =
+                "Set this to fail validation for foreign texts that are the same as their default text";
+            pub static ALLOW_IDENTICAL: &str =
+                "Text (like a brand name) that is allowed to be the same in all the locales; Eg., OK";
+            pub static ALLOW_IDENTICAL_IN: &str =
+                "Locale ID to a text that is allowed to be the same only in that locale; Eg., fr=Chat";
+            pub static SKIP_IDENTICAL_IN: &str =
+                "Locale ID whose texts aren't checked against the default texts; Eg., en";
+            pub static CHECK_PUNCTUATION: &str =
+                "Set this to fail validation for foreign texts whose whitespace or punctuation isn't consistent with their default text";
+            pub static SKIP_PUNCTUATION_RULE_IN: &str =
+                "Locale ID to a punctuation rule that isn't run for that locale; Eg., th=terminal-punctuation";
+
+            pub mod punctuation_rule {
+                pub static SHORT: &str = "Punctuation rule to run (defaults to all of them)";
+                pub static LONG: &str = r#"
+Punctuation rule to run. Defaults to all of them
+
+    - whitespace: Leading & trailing whitespace of the default text has to be
+      kept & no doubled spaces can be introduced
+    - terminal-punctuation: A default text ending with `.`, `:`, `?` or `!`
+      needs a foreign text ending with the same (or its full width form, like
+      the CJK `。`) & vice versa
+    - newlines: Both the texts need the same number of newlines
+    - ellipsis: Both the texts need to use the same ellipsis (`…` or `...`)
+                "#;
+            }
+
+            pub static REMOVE_UNTRANSLATABLE: &str =
+                "Set this to remove foreign strings & plurals whose default is marked `translatable=\"false\"` before validating";
+        }
+    }
+
+    pub mod ios {
+        pub static SHORT: &str = "Converts between strings XML files & iOS strings files";
+        pub static LONG: &str = r#"
+Exports the localizable strings & plurals of every locale into iOS's
+`Localizable.strings` & `Localizable.stringsdict` files. Default texts are
+written into `Base.lproj` & foreign texts into `<locale>.lproj`. Existing iOS
+files are overwritten
+
+With `--import`, the conversion goes the other way: texts from iOS files are
+merged into the `strings.xml` files. Only strings that are localizable in the
+default locale are imported & imported texts win over the existing ones
+
+Format specifiers are converted as required (Android's `%1$s` is iOS's `%1$@`)
+& so is escaping. Only `.stringsdict` plurals with a single variable are
+supported
+"#;
+
+        pub mod args {
+            pub static IOS_DIR: &str =
+                "Points to the dir that has (or would have) the `.lproj` dirs";
+            pub static IMPORT: &str =
+                "Set this to import texts from iOS files instead of exporting to them";
+            pub mod mapping {
+                pub static SHORT: &str =
+                    "Android locale ID (zh-rCN) to iOS locale ID (zh-Hans); Eg., zh-rCN=zh-Hans";
+                pub static LONG: &str = r#"
+Android & iOS don't always refer to a locale by the same ID. For eg., when
+a mapping like `zh-rCN=zh-Hans` is given, texts in `values-zh-rCN` would be
+exported to (or imported from) `zh-Hans.lproj`
+
+Multiple mappings can be passed in - each for a locale. These mappings also
+act as a filter. Only the mapped locales are exported or imported
+
+Note: When no mappings are given, all foreign locales are considered with
+the same IDs on both the platforms
+            "#;
+            }
+        }
+    }
+
+    pub mod csv {
+        pub static DELIMITER: &str =
+            "Char that separates the columns of CSVs (defaults to ,); Pass in `tab` for TSVs";
+        pub static QUOTE: &str = "Char that quotes the fields of CSVs (defaults to \")";
+        pub static KEY_HEADER: &str =
+            "Header of the column with the string names (defaults to string_name)";
+        pub static SOURCE_HEADER: &str =
+            "Header of the column with the default texts (defaults to default_locale)";
+        pub static IGNORE_COLUMN: &str =
+            "Header of a column that should be skipped when reading the CSV";
+        pub static WRITTEN_METADATA_COLUMN: &str =
+            "Header of a metadata column (like a vendor's Status) written out for translators to fill in";
+        pub static READ_METADATA_COLUMN: &str =
+            "Header of a metadata column (like a vendor's Status) that is read along with the localized texts & not as a locale";
+        pub mod encoding {
+            pub static SHORT: &str = "Encoding of the CSVs to be written out (defaults to utf-8)";
+            pub static LONG: &str = r#"
+Encoding of the CSVs to be written out. Defaults to utf-8
+
+    - utf-8: No BOM is written
+    - utf-8-bom: A BOM is written so that Excel figures out the encoding
+    - utf-16le: UTF-16 (little endian) with a BOM, as written by Excel
+
+When reading CSVs, the encoding is figured out from the BOM (UTF-8 is assumed
+when there isn't one)
+            "#;
         }
     }
 
     pub mod common {
+        pub static ALLOW_DUPLICATE_NAMES: &str =
+            "Uses the first definition of names that are defined more than once in a values dir instead of failing";
         pub static RES_DIR_SHORT: &str = "Points to the `res` dir of an Android module";
         pub static RES_DIR_LONG: &str = r#"
 This utility expects the Android module to follow the standard structure.
@@ -132,6 +364,7 @@ pub fn build() -> App<'static, 'static> {
         .subcommand(build_localize_sub_command())
         .subcommand(build_localized_sub_command())
         .subcommand(build_validate_sub_command())
+        .subcommand(build_ios_sub_command())
 }
 
 fn build_localize_sub_command() -> App<'static, 'static> {
@@ -150,6 +383,96 @@ fn build_localize_sub_command() -> App<'static, 'static> {
                 .takes_value(true)
                 .required(true),
         )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_FORMAT)
+                .help(doc::localize::args::format::SHORT)
+                .long_help(doc::localize::args::format::LONG.trim_start())
+                .long(constants::args::LOCALIZE_FORMAT)
+                .takes_value(true)
+                .possible_values(&[
+                    constants::formats::CSV,
+                    constants::formats::JSON,
+                    constants::formats::ARB,
+                ])
+                .default_value(constants::formats::CSV),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_TRANSLATION_MEMORY)
+                .help(doc::localize::args::TRANSLATION_MEMORY)
+                .long(constants::args::LOCALIZE_TRANSLATION_MEMORY)
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_MEMORY_RES_DIR)
+                .help(doc::localize::args::MEMORY_RES_DIR)
+                .long(constants::args::LOCALIZE_MEMORY_RES_DIR)
+                .takes_value(true)
+                .multiple(true)
+                .requires(constants::args::LOCALIZE_TRANSLATION_MEMORY),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_APPLY_MEMORY)
+                .help(doc::localize::args::APPLY_MEMORY)
+                .long(constants::args::LOCALIZE_APPLY_MEMORY)
+                .takes_value(false)
+                .requires(constants::args::LOCALIZE_TRANSLATION_MEMORY),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_INCLUDE_LOCALIZED)
+                .help(doc::localize::args::INCLUDE_LOCALIZED)
+                .long(constants::args::LOCALIZE_INCLUDE_LOCALIZED)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_LAYOUT)
+                .help(doc::localize::args::layout::SHORT)
+                .long_help(doc::localize::args::layout::LONG.trim_start())
+                .long(constants::args::LOCALIZE_LAYOUT)
+                .takes_value(true)
+                .possible_values(&[
+                    constants::layouts::GROUPED,
+                    constants::layouts::PER_LOCALE,
+                    constants::layouts::WIDE,
+                ])
+                .default_value(constants::layouts::GROUPED),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_FILE_NAME_TEMPLATE)
+                .help(doc::localize::args::file_name_template::SHORT)
+                .long_help(doc::localize::args::file_name_template::LONG.trim_start())
+                .long(constants::args::LOCALIZE_FILE_NAME_TEMPLATE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZE_IF_EXISTS)
+                .help(doc::localize::args::if_exists::SHORT)
+                .long_help(doc::localize::args::if_exists::LONG.trim_start())
+                .long(constants::args::LOCALIZE_IF_EXISTS)
+                .takes_value(true)
+                .possible_values(&[
+                    constants::if_exists::FAIL,
+                    constants::if_exists::OVERWRITE,
+                    constants::if_exists::APPEND,
+                    constants::if_exists::TIMESTAMP,
+                ])
+                .default_value(constants::if_exists::FAIL),
+        )
+        .arg(build_allow_duplicate_names_arg())
+        .args(&build_csv_dialect_args(doc::csv::WRITTEN_METADATA_COLUMN))
+        .arg(
+            Arg::with_name(constants::args::CSV_ENCODING)
+                .help(doc::csv::encoding::SHORT)
+                .long_help(doc::csv::encoding::LONG.trim_start())
+                .long(constants::args::CSV_ENCODING)
+                .takes_value(true)
+                .possible_values(&[
+                    constants::csv_encodings::UTF8,
+                    constants::csv_encodings::UTF8_BOM,
+                    constants::csv_encodings::UTF16_LE,
+                ])
+                .default_value(constants::csv_encodings::UTF8),
+        )
 }
 
 fn build_localized_sub_command() -> App<'static, 'static> {
@@ -168,6 +491,30 @@ fn build_localized_sub_command() -> App<'static, 'static> {
                 .takes_value(true)
                 .required(true),
         )
+        .arg(
+            Arg::with_name(constants::args::LOCALIZED_FUZZY_MATCH)
+                .help(doc::localized::args::fuzzy_match::SHORT)
+                .long_help(doc::localized::args::fuzzy_match::LONG.trim_start())
+                .long(constants::args::LOCALIZED_FUZZY_MATCH)
+                .takes_value(true)
+                .validator(fuzzy_match_validator)
+                .required(false),
+        )
+        .arg(build_allow_duplicate_names_arg())
+        .arg(
+            Arg::with_name(constants::args::ESCAPE_MALFORMED_TEXTS)
+                .help(doc::localized::args::ESCAPE_MALFORMED_TEXTS)
+                .long(constants::args::ESCAPE_MALFORMED_TEXTS)
+                .takes_value(false),
+        )
+        .args(&build_csv_dialect_args(doc::csv::READ_METADATA_COLUMN))
+        .arg(
+            Arg::with_name(constants::args::CSV_IGNORE_COLUMN)
+                .help(doc::csv::IGNORE_COLUMN)
+                .long(constants::args::CSV_IGNORE_COLUMN)
+                .takes_value(true)
+                .multiple(true),
+        )
 }
 
 fn build_validate_sub_command() -> App<'static, 'static> {
@@ -182,6 +529,113 @@ fn build_validate_sub_command() -> App<'static, 'static> {
                 .takes_value(false)
                 .required(false),
         )
+        .arg(
+            Arg::with_name(constants::args::FAIL_ON_UNUSED_QUANTITIES)
+                .help(doc::validate::args::FAIL_ON_UNUSED_QUANTITIES)
+                .long(constants::args::FAIL_ON_UNUSED_QUANTITIES)
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(constants::args::MAX_LENGTH_RATIO)
+                .help(doc::validate::args::MAX_LENGTH_RATIO)
+                .long(constants::args::MAX_LENGTH_RATIO)
+                .takes_value(true)
+                .validator(max_length_ratio_validator)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(constants::args::CHECK_IDENTICAL)
+                .help(doc::validate::args::CHECK_IDENTICAL)
+                .long(constants::args::CHECK_IDENTICAL)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name(constants::args::ALLOW_IDENTICAL)
+                .help(doc::validate::args::ALLOW_IDENTICAL)
+                .long(constants::args::ALLOW_IDENTICAL)
+                .takes_value(true)
+                .multiple(true)
+                .requires(constants::args::CHECK_IDENTICAL),
+        )
+        .arg(
+            Arg::with_name(constants::args::ALLOW_IDENTICAL_IN)
+                .help(doc::validate::args::ALLOW_IDENTICAL_IN)
+                .long(constants::args::ALLOW_IDENTICAL_IN)
+                .takes_value(true)
+                .multiple(true)
+                .validator(locale_text_validator)
+                .requires(constants::args::CHECK_IDENTICAL),
+        )
+        .arg(
+            Arg::with_name(constants::args::SKIP_IDENTICAL_IN)
+                .help(doc::validate::args::SKIP_IDENTICAL_IN)
+                .long(constants::args::SKIP_IDENTICAL_IN)
+                .takes_value(true)
+                .multiple(true)
+                .requires(constants::args::CHECK_IDENTICAL),
+        )
+        .arg(
+            Arg::with_name(constants::args::CHECK_PUNCTUATION)
+                .help(doc::validate::args::CHECK_PUNCTUATION)
+                .long(constants::args::CHECK_PUNCTUATION)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name(constants::args::PUNCTUATION_RULE)
+                .help(doc::validate::args::punctuation_rule::SHORT)
+                .long_help(doc::validate::args::punctuation_rule::LONG.trim_start())
+                .long(constants::args::PUNCTUATION_RULE)
+                .takes_value(true)
+                .multiple(true)
+                .possible_values(&[
+                    constants::punctuation_rules::WHITESPACE,
+                    constants::punctuation_rules::TERMINAL_PUNCTUATION,
+                    constants::punctuation_rules::NEWLINES,
+                    constants::punctuation_rules::ELLIPSIS,
+                ])
+                .requires(constants::args::CHECK_PUNCTUATION),
+        )
+        .arg(
+            Arg::with_name(constants::args::SKIP_PUNCTUATION_RULE_IN)
+                .help(doc::validate::args::SKIP_PUNCTUATION_RULE_IN)
+                .long(constants::args::SKIP_PUNCTUATION_RULE_IN)
+                .takes_value(true)
+                .multiple(true)
+                .validator(locale_punctuation_rule_validator)
+                .requires(constants::args::CHECK_PUNCTUATION),
+        )
+        .arg(
+            Arg::with_name(constants::args::REMOVE_UNTRANSLATABLE)
+                .help(doc::validate::args::REMOVE_UNTRANSLATABLE)
+                .long(constants::args::REMOVE_UNTRANSLATABLE)
+                .takes_value(false),
+        )
+}
+
+fn build_ios_sub_command() -> App<'static, 'static> {
+    SubCommand::with_name(constants::commands::IOS)
+        .about(doc::ios::SHORT)
+        .long_about(doc::ios::LONG)
+        .arg(build_res_dir_arg())
+        .arg(build_mapping_arg(
+            doc::ios::args::mapping::SHORT,
+            doc::ios::args::mapping::LONG.trim_start(),
+        ))
+        .arg(
+            Arg::with_name(constants::args::IOS_DIR)
+                .help(doc::ios::args::IOS_DIR)
+                .long(constants::args::IOS_DIR)
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(constants::args::IOS_IMPORT)
+                .help(doc::ios::args::IMPORT)
+                .long(constants::args::IOS_IMPORT)
+                .takes_value(false)
+                .required(false),
+        )
 }
 
 fn build_res_dir_arg() -> Arg<'static, 'static> {
@@ -203,7 +657,46 @@ fn build_mapping_arg(short_help: &'static str, long_help: &'static str) -> Arg<'
         .multiple(true)
 }
 
-#[cfg_attr(feature = "cargo-clippy", allow(clippy::needless_pass_by_value))]
+fn build_allow_duplicate_names_arg() -> Arg<'static, 'static> {
+    Arg::with_name(constants::args::ALLOW_DUPLICATE_NAMES)
+        .help(doc::common::ALLOW_DUPLICATE_NAMES)
+        .long(constants::args::ALLOW_DUPLICATE_NAMES)
+        .takes_value(false)
+}
+
+/// Metadata columns are written out by `localize` but read by `localized`, hence
+/// the help for them is passed in
+fn build_csv_dialect_args(metadata_column_help: &'static str) -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name(constants::args::CSV_DELIMITER)
+            .help(doc::csv::DELIMITER)
+            .long(constants::args::CSV_DELIMITER)
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .validator(csv_char_validator),
+        Arg::with_name(constants::args::CSV_QUOTE)
+            .help(doc::csv::QUOTE)
+            .long(constants::args::CSV_QUOTE)
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .validator(csv_char_validator),
+        Arg::with_name(constants::args::CSV_KEY_HEADER)
+            .help(doc::csv::KEY_HEADER)
+            .long(constants::args::CSV_KEY_HEADER)
+            .takes_value(true),
+        Arg::with_name(constants::args::CSV_SOURCE_HEADER)
+            .help(doc::csv::SOURCE_HEADER)
+            .long(constants::args::CSV_SOURCE_HEADER)
+            .takes_value(true),
+        Arg::with_name(constants::args::CSV_METADATA_COLUMN)
+            .help(metadata_column_help)
+            .long(constants::args::CSV_METADATA_COLUMN)
+            .takes_value(true)
+            .multiple(true),
+    ]
+}
+
+#[allow(clippy::needless_pass_by_value)]
 fn mapping_validator(mapping: String) -> Result<(), String> {
     let valid_mapping = match constants::TEXT_TO_TEXT_REGEX.captures(&mapping) {
         None => false,
@@ -224,3 +717,73 @@ fn mapping_validator(mapping: String) -> Result<(), String> {
         ))
     }
 }
+
+#[allow(clippy::needless_pass_by_value)]
+fn csv_char_validator(value: String) -> Result<(), String> {
+    if value == constants::csv_delimiters::TAB || (value.len() == 1 && value.is_ascii()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Should either be a single ASCII char or {}; Found: {}",
+            constants::csv_delimiters::TAB,
+            value
+        ))
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn locale_text_validator(locale_text: String) -> Result<(), String> {
+    match locale_text.split_once('=') {
+        Some((locale_id, text)) if !locale_id.is_empty() && !text.is_empty() => Ok(()),
+        _ => Err(format!(
+            "Should be of the format locale_id=text; Found: {}",
+            locale_text
+        )),
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn locale_punctuation_rule_validator(locale_rule: String) -> Result<(), String> {
+    let rules = [
+        constants::punctuation_rules::WHITESPACE,
+        constants::punctuation_rules::TERMINAL_PUNCTUATION,
+        constants::punctuation_rules::NEWLINES,
+        constants::punctuation_rules::ELLIPSIS,
+    ];
+
+    match locale_rule.split_once('=') {
+        Some((locale_id, rule)) if !locale_id.is_empty() && rules.contains(&rule) => Ok(()),
+        _ => Err(format!(
+            "Should be of the format locale_id=rule (one of {}); Found: {}",
+            rules.join(", "),
+            locale_rule
+        )),
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn max_length_ratio_validator(ratio: String) -> Result<(), String> {
+    match ratio.parse::<f32>() {
+        Ok(parsed_ratio) if parsed_ratio > 0.0 => Ok(()),
+        _ => Err(format!(
+            "Max length ratio should be a positive number; Found: {}",
+            ratio
+        )),
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn fuzzy_match_validator(fuzzy_match: String) -> Result<(), String> {
+    if fuzzy_match == constants::fuzzy_match::WHITESPACE_PUNCTUATION {
+        return Ok(());
+    }
+
+    match fuzzy_match.parse::<f32>() {
+        Ok(threshold) if threshold > 0.0 && threshold <= 1.0 => Ok(()),
+        _ => Err(format!(
+            "Fuzzy match should either be {} or a number between 0 & 1; Found: {}",
+            constants::fuzzy_match::WHITESPACE_PUNCTUATION,
+            fuzzy_match
+        )),
+    }
+}
@@ -1,10 +1,17 @@
 use std::collections::HashMap;
-use std::fmt;
+use std::path::PathBuf;
 
 use clap::ArgMatches;
 use console::style;
 
-use android_localization_core;
+use android_localization_core::error::{Error, ErrorKind};
+use android_localization_core::format::{CsvDialect, CsvEncoding, Format, JsonFlavor};
+use android_localization_core::localize::{CsvLayout, ExistingFilePolicy, OutputOptions};
+use android_localization_core::localized::MatchPolicy;
+use android_localization_core::translation_memory;
+use android_localization_core::validator::{
+    IdenticalTextsConfig, PunctuationConfig, PunctuationRule, ValidateOptions,
+};
 use android_localization_utilities::DevExpt;
 
 use crate::constants;
@@ -12,7 +19,7 @@ use crate::constants;
 /// In this file, you most probably would see wide spread usages of `Option#unwrap`.
 /// Please don't let that bother you as the requirements are correctly setup in
 /// `args_parser.rs` & unwrapped values are guaranteed to be present there
-pub fn execute_for_matches(matches: ArgMatches) -> Result<(), ()> {
+pub fn execute_for_matches(matches: ArgMatches) -> Result<(), i32> {
     if let Some(localized_command) = matches.subcommand_matches(constants::commands::LOCALIZED) {
         return localized(localized_command);
     }
@@ -25,37 +32,85 @@ pub fn execute_for_matches(matches: ArgMatches) -> Result<(), ()> {
         return validate(validations_command);
     }
 
+    if let Some(ios_command) = matches.subcommand_matches(constants::commands::IOS) {
+        return ios(ios_command);
+    }
+
     err_with_failure(String::from("Command couldn't be recognized"))
 }
 
-fn localize(matches: &ArgMatches) -> Result<(), ()> {
+fn localize(matches: &ArgMatches) -> Result<(), i32> {
+    let output_dir_path = matches
+        .value_of(constants::args::LOCALIZE_OUTPUT_DIR)
+        .expt(arg_missing_msg(constants::args::LOCALIZE_OUTPUT_DIR));
+
     let result = android_localization_core::localize::localize(
         matches
             .value_of(constants::args::RES_DIR)
             .expt(arg_missing_msg(constants::args::RES_DIR)),
-        matches
-            .value_of(constants::args::LOCALIZE_OUTPUT_DIR)
-            .expt(arg_missing_msg(constants::args::LOCALIZE_OUTPUT_DIR)),
+        output_dir_path,
         build_mappings(matches),
+        build_format(matches),
+        build_translation_memory_options(matches),
+        build_output_options(matches),
+        matches.is_present(constants::args::ALLOW_DUPLICATE_NAMES),
     );
 
     match result {
-        Err(error) => exit_based_on_result("", Err(error)),
-        Ok(file_names) => {
-            if file_names.is_empty() {
-                err_with_warning(String::from("Nothing found to localize"))
-            } else {
-                ok_with_success(format!(
+        Err(error) => err_with_error(error),
+        Ok(localization) => {
+            if localization.created_files.is_empty() && localization.applied_files.is_empty() {
+                return err_with_warning(String::from("Nothing found to localize"));
+            }
+
+            let mut sections = vec![];
+            if !localization.created_files.is_empty() {
+                sections.push(format!(
                     "{} - \n\n{}",
                     "Texts to be localized written to",
-                    file_names.join("\n")
-                ))
+                    join_paths(&localization.created_files)
+                ));
+            }
+
+            if !localization.applied_files.is_empty() {
+                sections.push(format!(
+                    "{} - \n\n{}",
+                    "Texts from translation memory written to",
+                    join_paths(&localization.applied_files)
+                ));
+            }
+
+            if !localization.leverage.is_empty() {
+                sections.push(format!(
+                    "{} - \n\n{}",
+                    "Translation memory leverage",
+                    localization
+                        .leverage
+                        .iter()
+                        .map(|leverage| format!(
+                            "{}: {} of {} strings ({} of {} words)",
+                            leverage.locale,
+                            leverage.matched_strings_count,
+                            leverage.strings_count,
+                            leverage.matched_words_count,
+                            leverage.words_count
+                        ))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                ));
+            }
+
+            // Stdout is taken up by the texts to be localized
+            if output_dir_path == constants::STD_STREAM_PATH {
+                ok_with_success_on_stderr(sections.join("\n\n"))
+            } else {
+                ok_with_success(sections.join("\n\n"))
             }
         }
     }
 }
 
-fn localized(matches: &ArgMatches) -> Result<(), ()> {
+fn localized(matches: &ArgMatches) -> Result<(), i32> {
     let result = android_localization_core::localized::localized(
         matches
             .value_of(constants::args::RES_DIR)
@@ -64,39 +119,154 @@ fn localized(matches: &ArgMatches) -> Result<(), ()> {
             .value_of(constants::args::LOCALIZED_INPUT_FILE)
             .expt(arg_missing_msg(constants::args::LOCALIZED_INPUT_FILE)),
         build_mappings(matches),
+        build_match_policy(matches),
+        build_csv_dialect(matches),
+        matches.is_present(constants::args::ALLOW_DUPLICATE_NAMES),
+        matches.is_present(constants::args::ESCAPE_MALFORMED_TEXTS),
     );
 
     match result {
-        Err(error) => exit_based_on_result("", Err(error)),
+        Err(error) => err_with_error(error),
+        Ok(localized_texts) => {
+            if !localized_texts.warnings.is_empty() {
+                warn(format!(
+                    "{} - \n\n{}",
+                    "Skipped columns of the localized texts",
+                    localized_texts
+                        .warnings
+                        .iter()
+                        .map(|warning| warning.to_string())
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                ));
+            }
+
+            if !localized_texts.skipped.is_empty() {
+                warn(format!(
+                    "{} - \n\n{}",
+                    "Localized texts skipped as they are still the default text",
+                    localized_texts
+                        .skipped
+                        .iter()
+                        .map(|skipped| format!("{}: {}", skipped.locale_id, skipped.name))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                ));
+            }
+
+            if localized_texts.updated_files.is_empty() {
+                return err_with_warning(String::from("No updated localized texts found"));
+            }
+
+            let mut output = format!(
+                "{} - \n\n{}",
+                "Localized texts written to",
+                join_paths(&localized_texts.updated_files)
+            );
+
+            if !localized_texts.needing_review.is_empty() {
+                output.push_str(&format!(
+                    "\n\n{} - \n\n{}",
+                    "Localized texts needing review (default text has changed since)",
+                    localized_texts
+                        .needing_review
+                        .iter()
+                        .map(|review| format!(
+                            "{}: {} (localized from \"{}\"; now \"{}\")",
+                            review.locale_id,
+                            review.fuzzy_match.name,
+                            review.fuzzy_match.localized_default,
+                            review.fuzzy_match.current_default
+                        ))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                ));
+            }
+
+            ok_with_success(output)
+        }
+    }
+}
+
+fn ios(matches: &ArgMatches) -> Result<(), i32> {
+    let res_dir_path = matches
+        .value_of(constants::args::RES_DIR)
+        .expt(arg_missing_msg(constants::args::RES_DIR));
+    let ios_dir_path = matches
+        .value_of(constants::args::IOS_DIR)
+        .expt(arg_missing_msg(constants::args::IOS_DIR));
+
+    let is_import = matches.is_present(constants::args::IOS_IMPORT);
+    let result = if is_import {
+        android_localization_core::ios::import(res_dir_path, ios_dir_path, build_mappings(matches))
+    } else {
+        android_localization_core::ios::export(res_dir_path, ios_dir_path, build_mappings(matches))
+    };
+
+    match result {
+        Err(error) => err_with_error(error),
         Ok(file_names) => {
             if file_names.is_empty() {
-                err_with_warning(String::from("No updated localized texts found"))
+                err_with_warning(String::from("No updated texts found to import"))
             } else {
-                ok_with_success(format!(
-                    "{} - \n\n{}",
-                    "Localized texts written to",
-                    file_names.join("\n")
-                ))
+                let prefix = if is_import {
+                    "Imported texts written to"
+                } else {
+                    "Exported texts written to"
+                };
+
+                ok_with_success(format!("{} - \n\n{}", prefix, join_paths(&file_names)))
             }
         }
     }
 }
 
-fn validate(matches: &ArgMatches) -> Result<(), ()> {
+fn validate(matches: &ArgMatches) -> Result<(), i32> {
+    let res_dir = matches
+        .value_of(constants::args::RES_DIR)
+        .expt(arg_missing_msg(constants::args::RES_DIR));
+
+    if matches.is_present(constants::args::REMOVE_UNTRANSLATABLE) {
+        match android_localization_core::validator::remove_untranslatable_strings(res_dir) {
+            Err(error) => return err_with_error(error),
+            Ok(file_names) => {
+                if !file_names.is_empty() {
+                    println!(
+                        "{}\n",
+                        style(format!(
+                            "{} - \n\n{}",
+                            "Removed untranslatable strings from the following files",
+                            join_paths(&file_names)
+                        ))
+                        .green()
+                    );
+                }
+            }
+        }
+    }
+
     let result = android_localization_core::validator::validate(
-        matches
-            .value_of(constants::args::RES_DIR)
-            .expt(arg_missing_msg(constants::args::RES_DIR)),
-        !matches.is_present(constants::args::SKIP_UNLOCALIZED),
+        res_dir,
+        &ValidateOptions {
+            skip_unlocalized: matches.is_present(constants::args::SKIP_UNLOCALIZED),
+            fail_on_unused_quantities: matches
+                .is_present(constants::args::FAIL_ON_UNUSED_QUANTITIES),
+            max_length_ratio: matches
+                .value_of(constants::args::MAX_LENGTH_RATIO)
+                .map(|ratio| ratio.parse().expt("Max length ratio should be valid")),
+            identical_texts: build_identical_texts_config(matches),
+            punctuation: build_punctuation_config(matches),
+        },
     );
 
     match result {
-        Err(error) => exit_based_on_result("", Err(error)),
+        Err(error) => err_with_error(error),
         Ok(validation_result) => match validation_result {
-            Ok(file_names) => {
-                let result: Result<Vec<String>, String> = Ok(file_names);
-                exit_based_on_result("No issues found. Validated the following files", result)
-            }
+            Ok(file_names) => ok_with_success(format!(
+                "{} - \n\n{}",
+                "No issues found. Validated the following files",
+                join_paths(&file_names)
+            )),
 
             Err(invalid_strings_files) => err_with_failure(
                 android_localization_core::formatter::format_to_string(invalid_strings_files).unwrap_or_else(|_| String::from("Looks like this utility is experiencing issues while displaying some invalid strings! Please contact the dev (jayrave) about this error")),
@@ -132,6 +302,167 @@ fn build_mappings(matches: &ArgMatches) -> HashMap<String, String> {
     }
 }
 
+fn build_translation_memory_options(matches: &ArgMatches) -> Option<translation_memory::Options> {
+    if !matches.is_present(constants::args::LOCALIZE_TRANSLATION_MEMORY) {
+        return None;
+    }
+
+    Some(translation_memory::Options {
+        extra_res_dir_paths: matches
+            .values_of(constants::args::LOCALIZE_MEMORY_RES_DIR)
+            .map(|values| values.map(PathBuf::from).collect())
+            .unwrap_or_default(),
+        auto_apply: matches.is_present(constants::args::LOCALIZE_APPLY_MEMORY),
+    })
+}
+
+fn build_output_options(matches: &ArgMatches) -> OutputOptions {
+    OutputOptions {
+        file_name_template: matches
+            .value_of(constants::args::LOCALIZE_FILE_NAME_TEMPLATE)
+            .map(String::from),
+        existing_file_policy: match matches.value_of(constants::args::LOCALIZE_IF_EXISTS) {
+            Some(constants::if_exists::OVERWRITE) => ExistingFilePolicy::Overwrite,
+            Some(constants::if_exists::APPEND) => ExistingFilePolicy::Append,
+            Some(constants::if_exists::TIMESTAMP) => ExistingFilePolicy::Timestamp,
+            _ => ExistingFilePolicy::Fail,
+        },
+        csv_layout: match matches.value_of(constants::args::LOCALIZE_LAYOUT) {
+            Some(constants::layouts::PER_LOCALE) => CsvLayout::PerLocale,
+            Some(constants::layouts::WIDE) => CsvLayout::Wide,
+            _ => CsvLayout::Grouped,
+        },
+        include_localized: matches.is_present(constants::args::LOCALIZE_INCLUDE_LOCALIZED),
+        csv_dialect: build_csv_dialect(matches),
+    }
+}
+
+fn build_csv_dialect(matches: &ArgMatches) -> CsvDialect {
+    let mut dialect = CsvDialect::default();
+    if let Some(delimiter) = matches.value_of(constants::args::CSV_DELIMITER) {
+        dialect.delimiter = csv_char_to_byte(delimiter);
+    }
+
+    if let Some(quote) = matches.value_of(constants::args::CSV_QUOTE) {
+        dialect.quote = csv_char_to_byte(quote);
+    }
+
+    dialect.encoding = match matches.value_of(constants::args::CSV_ENCODING) {
+        Some(constants::csv_encodings::UTF8_BOM) => CsvEncoding::Utf8Bom,
+        Some(constants::csv_encodings::UTF16_LE) => CsvEncoding::Utf16Le,
+        _ => CsvEncoding::Utf8,
+    };
+
+    if let Some(key_header) = matches.value_of(constants::args::CSV_KEY_HEADER) {
+        dialect.key_header = String::from(key_header);
+    }
+
+    if let Some(source_header) = matches.value_of(constants::args::CSV_SOURCE_HEADER) {
+        dialect.source_header = String::from(source_header);
+    }
+
+    if let Some(ignored_headers) = matches.values_of(constants::args::CSV_IGNORE_COLUMN) {
+        dialect.ignored_headers = ignored_headers.map(String::from).collect();
+    }
+
+    if let Some(metadata_headers) = matches.values_of(constants::args::CSV_METADATA_COLUMN) {
+        dialect.metadata_headers = metadata_headers.map(String::from).collect();
+    }
+
+    dialect
+}
+
+/// Values are expected to have been validated by the args parser
+fn csv_char_to_byte(value: &str) -> u8 {
+    if value == constants::csv_delimiters::TAB {
+        b'\t'
+    } else {
+        value.as_bytes()[0]
+    }
+}
+
+fn build_identical_texts_config(matches: &ArgMatches) -> Option<IdenticalTextsConfig> {
+    if !matches.is_present(constants::args::CHECK_IDENTICAL) {
+        return None;
+    }
+
+    let mut config = IdenticalTextsConfig::default();
+    if let Some(texts) = matches.values_of(constants::args::ALLOW_IDENTICAL) {
+        config.allowed_texts = texts.map(String::from).collect();
+    }
+
+    if let Some(locale_texts) = matches.values_of(constants::args::ALLOW_IDENTICAL_IN) {
+        for locale_text in locale_texts {
+            let (locale_id, text) = locale_text
+                .split_once('=')
+                .expt("Looks like locale text validator doesn't work!");
+            config
+                .allowed_texts_per_locale
+                .entry(String::from(locale_id))
+                .or_default()
+                .push(String::from(text));
+        }
+    }
+
+    if let Some(locale_ids) = matches.values_of(constants::args::SKIP_IDENTICAL_IN) {
+        config.skipped_locales = locale_ids.map(String::from).collect();
+    }
+
+    Some(config)
+}
+
+fn build_punctuation_config(matches: &ArgMatches) -> Option<PunctuationConfig> {
+    if !matches.is_present(constants::args::CHECK_PUNCTUATION) {
+        return None;
+    }
+
+    let mut config = PunctuationConfig::default();
+    if let Some(rules) = matches.values_of(constants::args::PUNCTUATION_RULE) {
+        config.rules = rules.map(build_punctuation_rule).collect();
+    }
+
+    if let Some(locale_rules) = matches.values_of(constants::args::SKIP_PUNCTUATION_RULE_IN) {
+        for locale_rule in locale_rules {
+            let (locale_id, rule) = locale_rule
+                .split_once('=')
+                .expt("Looks like locale punctuation rule validator doesn't work!");
+            config
+                .exempted_rules_per_locale
+                .entry(String::from(locale_id))
+                .or_default()
+                .push(build_punctuation_rule(rule));
+        }
+    }
+
+    Some(config)
+}
+
+fn build_punctuation_rule(name: &str) -> PunctuationRule {
+    PunctuationRule::from_name(name).expt("Punctuation rule should be valid")
+}
+
+fn build_match_policy(matches: &ArgMatches) -> MatchPolicy {
+    match matches.value_of(constants::args::LOCALIZED_FUZZY_MATCH) {
+        None => MatchPolicy::Exact,
+        Some(constants::fuzzy_match::WHITESPACE_PUNCTUATION) => {
+            MatchPolicy::IgnoreWhitespaceAndPunctuation
+        }
+        Some(threshold) => MatchPolicy::Similarity(
+            threshold
+                .parse()
+                .expt("Looks like fuzzy match validator doesn't work!"),
+        ),
+    }
+}
+
+fn build_format(matches: &ArgMatches) -> Format {
+    match matches.value_of(constants::args::LOCALIZE_FORMAT) {
+        Some(constants::formats::JSON) => Format::Json(JsonFlavor::Plain),
+        Some(constants::formats::ARB) => Format::Json(JsonFlavor::Arb),
+        _ => Format::Csv,
+    }
+}
+
 fn arg_missing_msg(arg_name: &str) -> String {
     format!("{} arg is missing", arg_name)
 }
@@ -140,32 +471,70 @@ fn invalid_mapping_validator_msg() -> &'static str {
     "Looks like mapping validator doesn't work!"
 }
 
-fn exit_based_on_result<E: fmt::Display>(
-    success_prefix: &str,
-    result: Result<Vec<String>, E>,
-) -> Result<(), ()> {
-    match result {
-        Ok(file_names) => ok_with_success(format!(
-            "{} - \n\n{}",
-            success_prefix,
-            file_names.join("\n")
-        )),
-
-        Err(error) => err_with_failure(error.to_string()),
+fn exit_code_for(error_kind: &ErrorKind) -> i32 {
+    match error_kind {
+        ErrorKind::Io(_) => constants::exit_codes::IO,
+        ErrorKind::MissingResDir => constants::exit_codes::MISSING_RES_DIR,
+        ErrorKind::NoForeignStringsFiles => constants::exit_codes::NO_FOREIGN_STRINGS_FILES,
+        ErrorKind::UnknownLocale(_) => constants::exit_codes::UNKNOWN_LOCALE,
+        ErrorKind::InvalidOutputDir => constants::exit_codes::INVALID_OUTPUT_DIR,
+        ErrorKind::OutputFileExists => constants::exit_codes::OUTPUT_FILE_EXISTS,
+        ErrorKind::DuplicateOutputFile => constants::exit_codes::DUPLICATE_OUTPUT_FILE,
+        ErrorKind::UnsupportedFormat(_) => constants::exit_codes::UNSUPPORTED_FORMAT,
+        ErrorKind::Csv(_) => constants::exit_codes::CSV,
+        ErrorKind::InvalidCsv(_) => constants::exit_codes::INVALID_CSV,
+        ErrorKind::MismatchedCsvColumns => constants::exit_codes::MISMATCHED_CSV_COLUMNS,
+        ErrorKind::DuplicateKey(_) => constants::exit_codes::DUPLICATE_KEY,
+        ErrorKind::Json(_) => constants::exit_codes::JSON,
+        ErrorKind::XmlRead(_) => constants::exit_codes::XML_READ,
+        ErrorKind::XmlWrite(_) => constants::exit_codes::XML_WRITE,
+        ErrorKind::MalformedTexts(_) => constants::exit_codes::MALFORMED_TEXTS,
+        ErrorKind::InvalidJson(_) => constants::exit_codes::INVALID_JSON,
+        ErrorKind::InvalidEncoding(_) => constants::exit_codes::INVALID_ENCODING,
+        ErrorKind::InvalidStringsXml(_) => constants::exit_codes::INVALID_STRINGS_XML,
+        ErrorKind::InvalidAppleStrings(_) => constants::exit_codes::INVALID_APPLE_STRINGS,
+        ErrorKind::InvalidStringsdict(_) => constants::exit_codes::INVALID_STRINGSDICT,
+        ErrorKind::Message(_) => constants::exit_codes::FAILURE,
     }
 }
 
-fn ok_with_success(output: String) -> Result<(), ()> {
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn ok_with_success(output: String) -> Result<(), i32> {
     println!("{}", style(output).green());
     Ok(())
 }
 
-fn err_with_warning(output: String) -> Result<(), ()> {
+fn ok_with_success_on_stderr(output: String) -> Result<(), i32> {
+    eprintln!("{}", style(output).green());
+    Ok(())
+}
+
+fn warn(output: String) {
+    eprintln!("{}\n", style(output).yellow());
+}
+
+fn err_with_warning(output: String) -> Result<(), i32> {
     eprintln!("{}", style(output).yellow());
-    Err(())
+    Err(constants::exit_codes::FAILURE)
 }
 
-fn err_with_failure(error: String) -> Result<(), ()> {
+fn err_with_failure(error: String) -> Result<(), i32> {
     eprintln!("{}", style(error).red());
-    Err(())
+    Err(constants::exit_codes::FAILURE)
+}
+
+/// The stable code of the error is printed out along with it
+fn err_with_error(error: Error) -> Result<(), i32> {
+    eprintln!(
+        "{}",
+        style(format!("[{}] {}", error.kind().code(), error)).red()
+    );
+    Err(exit_code_for(error.kind()))
 }
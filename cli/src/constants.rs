@@ -3,6 +3,7 @@ use regex::Regex;
 use android_localization_utilities::DevExpt;
 
 pub mod commands {
+    pub const IOS: &str = "ios";
     pub const LOCALIZE: &str = "localize";
     pub const LOCALIZED: &str = "localized";
     pub const VALIDATE: &str = "validate";
@@ -10,12 +11,113 @@ pub mod commands {
 
 pub mod args {
     pub const RES_DIR: &str = "res-dir";
+    pub const IOS_DIR: &str = "ios-dir";
+    pub const IOS_IMPORT: &str = "import";
     pub const LOCALIZE_OUTPUT_DIR: &str = "output-dir";
+    pub const LOCALIZE_FORMAT: &str = "format";
+    pub const LOCALIZE_TRANSLATION_MEMORY: &str = "translation-memory";
+    pub const LOCALIZE_MEMORY_RES_DIR: &str = "memory-res-dir";
+    pub const LOCALIZE_APPLY_MEMORY: &str = "apply-memory";
+    pub const LOCALIZE_FILE_NAME_TEMPLATE: &str = "file-name-template";
+    pub const LOCALIZE_IF_EXISTS: &str = "if-exists";
+    pub const LOCALIZE_LAYOUT: &str = "layout";
+    pub const LOCALIZE_INCLUDE_LOCALIZED: &str = "include-localized";
     pub const LOCALIZED_INPUT_FILE: &str = "input-file";
+    pub const LOCALIZED_FUZZY_MATCH: &str = "fuzzy-match";
     pub const MAPPING: &str = "mapping";
+    pub const ALLOW_DUPLICATE_NAMES: &str = "allow-duplicate-names";
+    pub const ESCAPE_MALFORMED_TEXTS: &str = "escape-malformed-texts";
+    pub const MAX_LENGTH_RATIO: &str = "max-length-ratio";
     pub const SKIP_UNLOCALIZED: &str = "skip-unlocalized";
+    pub const FAIL_ON_UNUSED_QUANTITIES: &str = "fail-on-unused-quantities";
+    pub const CHECK_IDENTICAL: &str = "check-identical";
+    pub const ALLOW_IDENTICAL: &str = "allow-identical";
+    pub const ALLOW_IDENTICAL_IN: &str = "allow-identical-in";
+    pub const SKIP_IDENTICAL_IN: &str = "skip-identical-in";
+    pub const REMOVE_UNTRANSLATABLE: &str = "remove-untranslatable";
+    pub const CHECK_PUNCTUATION: &str = "check-punctuation";
+    pub const PUNCTUATION_RULE: &str = "punctuation-rule";
+    pub const SKIP_PUNCTUATION_RULE_IN: &str = "skip-punctuation-rule-in";
+    pub const CSV_DELIMITER: &str = "csv-delimiter";
+    pub const CSV_QUOTE: &str = "csv-quote";
+    pub const CSV_ENCODING: &str = "csv-encoding";
+    pub const CSV_KEY_HEADER: &str = "csv-key-header";
+    pub const CSV_SOURCE_HEADER: &str = "csv-source-header";
+    pub const CSV_IGNORE_COLUMN: &str = "csv-ignore-column";
+    pub const CSV_METADATA_COLUMN: &str = "csv-metadata-column";
 }
 
+pub mod fuzzy_match {
+    pub const WHITESPACE_PUNCTUATION: &str = "whitespace-punctuation";
+}
+
+pub mod if_exists {
+    pub const FAIL: &str = "fail";
+    pub const OVERWRITE: &str = "overwrite";
+    pub const APPEND: &str = "append";
+    pub const TIMESTAMP: &str = "timestamp";
+}
+
+pub mod layouts {
+    pub const GROUPED: &str = "grouped";
+    pub const PER_LOCALE: &str = "per-locale";
+    pub const WIDE: &str = "wide";
+}
+
+pub mod punctuation_rules {
+    pub const WHITESPACE: &str = "whitespace";
+    pub const TERMINAL_PUNCTUATION: &str = "terminal-punctuation";
+    pub const NEWLINES: &str = "newlines";
+    pub const ELLIPSIS: &str = "ellipsis";
+}
+
+pub mod csv_delimiters {
+    pub const TAB: &str = "tab";
+}
+
+pub mod csv_encodings {
+    pub const UTF8: &str = "utf-8";
+    pub const UTF8_BOM: &str = "utf-8-bom";
+    pub const UTF16_LE: &str = "utf-16le";
+}
+
+pub mod formats {
+    pub const CSV: &str = "csv";
+    pub const JSON: &str = "json";
+    pub const ARB: &str = "arb";
+}
+
+/// Exit codes are stable so that scripts can react to specific failures. `1`
+/// is for everything that doesn't have a code of its own (warnings, invalid
+/// strings found by validate, etc.)
+pub mod exit_codes {
+    pub const FAILURE: i32 = 1;
+    pub const IO: i32 = 10;
+    pub const MISSING_RES_DIR: i32 = 11;
+    pub const NO_FOREIGN_STRINGS_FILES: i32 = 12;
+    pub const UNKNOWN_LOCALE: i32 = 13;
+    pub const INVALID_OUTPUT_DIR: i32 = 14;
+    pub const OUTPUT_FILE_EXISTS: i32 = 15;
+    pub const DUPLICATE_OUTPUT_FILE: i32 = 16;
+    pub const UNSUPPORTED_FORMAT: i32 = 17;
+    pub const CSV: i32 = 20;
+    pub const INVALID_CSV: i32 = 21;
+    pub const MISMATCHED_CSV_COLUMNS: i32 = 22;
+    pub const DUPLICATE_KEY: i32 = 23;
+    pub const JSON: i32 = 30;
+    pub const XML_READ: i32 = 31;
+    pub const XML_WRITE: i32 = 32;
+    pub const MALFORMED_TEXTS: i32 = 33;
+    pub const INVALID_JSON: i32 = 34;
+    pub const INVALID_ENCODING: i32 = 35;
+    pub const INVALID_STRINGS_XML: i32 = 36;
+    pub const INVALID_APPLE_STRINGS: i32 = 37;
+    pub const INVALID_STRINGSDICT: i32 = 38;
+}
+
+/// Stands for stdout (when written to) & stdin (when read from)
+pub const STD_STREAM_PATH: &str = "-";
+
 lazy_static::lazy_static! {
-    pub static ref TEXT_TO_TEXT_REGEX: Regex = Regex::new("^([a-zA-Z]+)=([a-zA-Z]+)$").expt("Invalid regex!");
+    pub static ref TEXT_TO_TEXT_REGEX: Regex = Regex::new("^([a-zA-Z_-]+)=([a-zA-Z_-]+)$").expt("Invalid regex!");
 }
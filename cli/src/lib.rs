@@ -4,7 +4,8 @@ mod args_parser;
 mod args_user;
 mod constants;
 
-pub fn execute_for_commands<I, T>(itr: I) -> Result<(), ()>
+/// Errors with the exit code the process should end with
+pub fn execute_for_commands<I, T>(itr: I) -> Result<(), i32>
 where
     I: IntoIterator<Item = T>,
     T: Into<OsString> + Clone,
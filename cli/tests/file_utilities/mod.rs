@@ -1,4 +1,5 @@
-use test_utilities;
+// Not every integration test uses all of these helpers
+#![allow(dead_code)]
 
 pub fn assert_eq_of_file_contents(actual_file_path: &str, expected_file_path: &str) {
     let actual_file_lines = read_file_contents_as_lines(actual_file_path);
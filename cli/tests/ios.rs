@@ -0,0 +1,116 @@
+use std::io::Write;
+use std::process::Command;
+
+mod file_utilities;
+
+#[test]
+fn exports_strings_and_plurals() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let ios_path = format!("{}/ios", temp_dir.path().to_str().unwrap());
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "ios",
+            "--res-dir",
+            "./tests_data/ios/export/input",
+            "--ios-dir",
+            &ios_path,
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Exported texts written to - \n\n"));
+
+    for file_path in &[
+        "Base.lproj/Localizable.strings",
+        "Base.lproj/Localizable.stringsdict",
+        "fr.lproj/Localizable.strings",
+    ] {
+        file_utilities::assert_eq_of_file_contents(
+            &format!("{}/{}", ios_path, file_path),
+            &format!("./tests_data/ios/export/output/{}", file_path),
+        );
+    }
+}
+
+#[test]
+fn imports_strings_and_plurals() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut res_path = temp_dir.path().to_path_buf();
+    res_path.push("res");
+
+    let mut default_strings =
+        test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+    let mut fr_strings =
+        test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+    default_strings
+        .file
+        .write_all(
+            test_utilities::file::read_content(
+                "./tests_data/ios/import/input/sample_res/values/strings.xml",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+
+    fr_strings
+        .file
+        .write_all(
+            test_utilities::file::read_content(
+                "./tests_data/ios/import/input/sample_res/values-fr/strings.xml",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "ios",
+            "--import",
+            "--res-dir",
+            res_path.to_str().unwrap(),
+            "--ios-dir",
+            "./tests_data/ios/import/input/ios",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Imported texts written to - \n\n"));
+
+    file_utilities::assert_eq_of_file_contents(
+        fr_strings.path.to_str().unwrap(),
+        "./tests_data/ios/import/output/french_strings.xml",
+    );
+}
+
+#[test]
+fn errors_are_printed_out() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "ios",
+            "--res-dir",
+            &format!(
+                "{}/non_existent",
+                temp_dir.path().to_path_buf().to_str().unwrap()
+            ),
+            "--ios-dir",
+            temp_dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Res dir path doesn't exist or it is not a directory\n"));
+}
@@ -1,3 +1,4 @@
+use std::fs;
 use std::process::{Command, Output};
 
 use tempfile::TempDir;
@@ -24,9 +25,9 @@ fn succeeds_with_mapping() {
         .unwrap();
 
     assert_status_and_stdout(output);
-    assert_output_files(
+    assert_output_file(
         temp_dir,
-        "./tests_data/localize/success/output_with_mapping/",
+        "./tests_data/localize/success/output_with_mapping/fr_es.csv",
     );
 }
 
@@ -46,12 +47,208 @@ fn succeeds_without_mapping() {
         .unwrap();
 
     assert_status_and_stdout(output);
-    assert_output_files(
+    assert_output_file(
         temp_dir,
-        "./tests_data/localize/success/output_without_mapping/",
+        "./tests_data/localize/success/output_without_mapping/es_fr.csv",
     );
 }
 
+#[test]
+fn writes_a_csv_per_locale() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "localize",
+            "--res-dir",
+            "./tests_data/localize/success/input",
+            "--output-dir",
+            temp_dir.path().to_str().unwrap(),
+            "--mapping",
+            "fr=french",
+            "--mapping",
+            "es=spanish",
+            "--layout",
+            "per-locale",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    file_utilities::assert_eq_of_file_contents(
+        &format!(
+            "{}/to_localize_french.csv",
+            temp_dir.path().to_str().unwrap()
+        ),
+        "./tests_data/localize/success/output_with_mapping/fr.csv",
+    );
+    file_utilities::assert_eq_of_file_contents(
+        &format!(
+            "{}/to_localize_spanish.csv",
+            temp_dir.path().to_str().unwrap()
+        ),
+        "./tests_data/localize/success/output_with_mapping/es.csv",
+    );
+}
+
+#[test]
+fn includes_localized_texts() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "localize",
+            "--res-dir",
+            "./tests_data/localize/success/input",
+            "--output-dir",
+            "-",
+            "--mapping",
+            "fr=french",
+            "--include-localized",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        test_utilities::file::read_content(
+            "./tests_data/localize/success/output_with_localized/fr.csv"
+        )
+    );
+}
+
+#[test]
+fn writes_a_single_csv_to_stdout() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "localize",
+            "--res-dir",
+            "./tests_data/localize/success/input",
+            "--output-dir",
+            "-",
+            "--mapping",
+            "fr=french",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        test_utilities::file::read_content(
+            "./tests_data/localize/success/output_with_mapping/fr.csv"
+        )
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Texts to be localized written to - \n\n-\n"));
+}
+
+#[test]
+fn writes_csv_as_per_dialect_with_metadata_columns() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "localize",
+            "--res-dir",
+            "./tests_data/localize/success/input",
+            "--output-dir",
+            "-",
+            "--mapping",
+            "fr=french",
+            "--csv-delimiter",
+            ";",
+            "--csv-key-header",
+            "key",
+            "--csv-metadata-column",
+            "status",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "key;default_locale;status;french\n\
+         string_1;string_1 default locale;;\n\
+         string_3;string_3 default locale;;\n"
+    );
+}
+
+#[test]
+fn refuses_duplicate_names_unless_allowed() {
+    let args = vec![
+        "run",
+        "localize",
+        "--res-dir",
+        "./tests_data/localize/duplicate/input",
+        "--output-dir",
+        "-",
+    ];
+
+    let output = Command::new("cargo").args(&args).output().unwrap();
+    assert_eq!(output.status.code(), Some(23));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("[duplicate_key] ./tests_data/localize/duplicate/input/values: Found more than once: string_2"));
+
+    let output = Command::new("cargo")
+        .args(args)
+        .arg("--allow-duplicate-names")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "string_name,default_locale,fr\nstring_2,Cancel,\n"
+    );
+}
+
+#[test]
+fn names_files_by_template_and_handles_existing_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let run_localize = |if_exists: &str| {
+        Command::new("cargo")
+            .args(vec![
+                "run",
+                "localize",
+                "--res-dir",
+                "./tests_data/localize/success/input",
+                "--output-dir",
+                temp_dir.path().to_str().unwrap(),
+                "--mapping",
+                "fr=french",
+                "--mapping",
+                "es=spanish",
+                "--file-name-template",
+                "{module}_{locales}",
+                "--if-exists",
+                if_exists,
+            ])
+            .output()
+            .unwrap()
+    };
+
+    assert!(run_localize("fail").status.success());
+    file_utilities::assert_eq_of_file_contents(
+        &format!(
+            "{}/success_french_spanish.csv",
+            temp_dir.path().to_str().unwrap()
+        ),
+        "./tests_data/localize/success/output_with_mapping/fr_es.csv",
+    );
+
+    let output = run_localize("fail");
+    assert_eq!(output.status.code(), Some(15));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Output file already exists!\n"));
+
+    assert!(run_localize("timestamp").status.success());
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+}
+
 #[test]
 fn warns_if_nothing_to_localize() {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -73,6 +270,35 @@ fn warns_if_nothing_to_localize() {
         .contains("Nothing found to localize\n"));
 }
 
+#[test]
+fn prefills_from_translation_memory() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "localize",
+            "--res-dir",
+            "./tests_data/localize/memory/app",
+            "--output-dir",
+            temp_dir.path().to_str().unwrap(),
+            "--translation-memory",
+            "--memory-res-dir",
+            "./tests_data/localize/memory/lib",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("Translation memory leverage - \n\nfr: 1 of 2 strings (1 of 2 words)"));
+
+    file_utilities::assert_eq_of_file_contents(
+        &format!("{}/to_localize_1.csv", temp_dir.path().to_str().unwrap()),
+        "./tests_data/localize/memory/output/to_localize_1.csv",
+    );
+}
+
 #[test]
 fn errors_are_printed_out() {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -88,10 +314,10 @@ fn errors_are_printed_out() {
         .output()
         .unwrap();
 
-    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(11));
     assert!(String::from_utf8(output.stderr)
         .unwrap()
-        .contains("Res dir path doesn't exist or it is not a directory\n"));
+        .contains("[missing_res_dir] "));
 }
 
 fn assert_status_and_stdout(output: Output) {
@@ -110,10 +336,9 @@ fn assert_status_and_stdout(output: Output) {
     assert_eq!(output_lines.next(), None);
 }
 
-fn assert_output_files(temp_dir: TempDir, expected_output_dir_path: &str) {
-    file_utilities::assert_eq_of_file_contents_to_either_or(
+fn assert_output_file(temp_dir: TempDir, expected_output_file_path: &str) {
+    file_utilities::assert_eq_of_file_contents(
         &format!("{}/to_localize_1.csv", temp_dir.path().to_str().unwrap()),
-        &format!("{}/es_fr.csv", expected_output_dir_path),
-        &format!("{}/fr_es.csv", expected_output_dir_path),
+        expected_output_file_path,
     );
 }
@@ -1,10 +1,8 @@
 use std::io::Write;
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 
 use tempfile::TempDir;
 
-use test_utilities;
-
 mod file_utilities;
 
 #[test]
@@ -59,6 +57,111 @@ fn succeeds_without_mapping() {
     )
 }
 
+#[test]
+fn succeeds_with_csv_dialect() {
+    execute_with_copied_sample_res(
+        tempfile::tempdir().unwrap(),
+        "success",
+        |output_res_path: String| {
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    "./tests_data/localized/success/input/localized_with_dialect.csv",
+                    "--csv-delimiter",
+                    ";",
+                    "--csv-key-header",
+                    "key",
+                    "--csv-source-header",
+                    "source",
+                    "--csv-ignore-column",
+                    "notes",
+                ])
+                .output()
+                .unwrap();
+
+            assert_status_and_stdout(output);
+            assert_output_files(output_res_path);
+        },
+    )
+}
+
+#[test]
+fn succeeds_with_fuzzy_match() {
+    execute_with_copied_sample_res(
+        tempfile::tempdir().unwrap(),
+        "success",
+        |output_res_path: String| {
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    "./tests_data/localized/success/input/localized_fuzzy.csv",
+                    "--fuzzy-match",
+                    "whitespace-punctuation",
+                ])
+                .output()
+                .unwrap();
+
+            assert!(output.status.success());
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            assert!(stdout.contains(
+                "fr: string_1 (localized from \"string_1  default locale.\"; now \"string_1 default locale\")\n"
+            ));
+            assert!(stdout.contains(
+                "es: string_1 (localized from \"string_1  default locale.\"; now \"string_1 default locale\")\n"
+            ));
+
+            assert_output_files(output_res_path);
+        },
+    )
+}
+
+#[test]
+fn reads_from_stdin() {
+    execute_with_copied_sample_res(
+        tempfile::tempdir().unwrap(),
+        "success",
+        |output_res_path: String| {
+            let mut child = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    "-",
+                ])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .unwrap();
+
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(
+                    test_utilities::file::read_content(
+                        "./tests_data/localized/success/input/localized_without_mapping.csv",
+                    )
+                    .as_bytes(),
+                )
+                .unwrap();
+
+            assert_status_and_stdout(child.wait_with_output().unwrap());
+            assert_output_files(output_res_path);
+        },
+    )
+}
+
 #[test]
 fn warns_if_nothing_new_localized() {
     execute_with_copied_sample_res(
@@ -85,6 +188,140 @@ fn warns_if_nothing_new_localized() {
     )
 }
 
+#[test]
+fn lists_out_all_issues_in_csv() {
+    execute_with_copied_sample_res(
+        tempfile::tempdir().unwrap(),
+        "warn",
+        |output_res_path: String| {
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    "./tests_data/localized/error/input/localized.csv",
+                ])
+                .output()
+                .unwrap();
+
+            assert_eq!(output.status.code(), Some(21));
+            let stderr = String::from_utf8(output.stderr).unwrap();
+            assert!(stderr.contains("Found 2 issue(s) in the CSV\n"));
+            assert!(stderr.contains("Row 2: Row has 3 values but the header has 4 columns"));
+            assert!(stderr.contains("Row 3, column string_name: string_name can't be empty"));
+        },
+    )
+}
+
+#[test]
+fn round_trips_plain_json() {
+    let output_dir = tempfile::tempdir().unwrap();
+    execute_with_copied_sample_res(
+        tempfile::tempdir().unwrap(),
+        "success",
+        |output_res_path: String| {
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localize",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--output-dir",
+                    output_dir.path().to_str().unwrap(),
+                    "--format",
+                    "json",
+                ])
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+
+            // Only one of the texts is localized. The other is left as it was written out
+            let json_file_path = format!("{}/to_localize_fr.json", output_dir.path().display());
+            test_utilities::file::write_content(
+                &json_file_path,
+                test_utilities::file::read_content(&json_file_path).replacen(
+                    "\"string_1 default locale\"",
+                    "\"string_1 french\"",
+                    1,
+                ),
+            );
+
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    &json_file_path,
+                ])
+                .output()
+                .unwrap();
+
+            assert!(output.status.success());
+            let fr_content = test_utilities::file::read_content(format!(
+                "{}/values-fr/strings.xml",
+                output_res_path
+            ));
+            assert!(fr_content.contains(r#"<string name="string_1">string_1 french</string>"#));
+            assert!(fr_content.contains(r#"<string name="string_2">string_2 french</string>"#));
+            assert!(!fr_content.contains("string_3"));
+        },
+    )
+}
+
+#[test]
+fn errors_for_malformed_texts_unless_escaped() {
+    execute_with_copied_sample_res(
+        tempfile::tempdir().unwrap(),
+        "warn",
+        |output_res_path: String| {
+            let fr_strings_path = format!("{}/values-fr/strings.xml", output_res_path);
+            let fr_content = test_utilities::file::read_content(&fr_strings_path);
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    "./tests_data/localized/error/input/malformed.csv",
+                ])
+                .output()
+                .unwrap();
+
+            assert_eq!(output.status.code(), Some(33));
+            let stderr = String::from_utf8(output.stderr).unwrap();
+            assert!(stderr.contains("Found 2 localized text(s) that aren't well formed XML"));
+            assert!(stderr.contains("Locale es, string string_2: "));
+            assert!(stderr.contains("Locale fr, string string_1: "));
+            assert_eq!(
+                test_utilities::file::read_content(&fr_strings_path),
+                fr_content
+            );
+
+            let output = Command::new("cargo")
+                .args(vec![
+                    "run",
+                    "localized",
+                    "--res-dir",
+                    &output_res_path.clone(),
+                    "--input-file",
+                    "./tests_data/localized/error/input/malformed.csv",
+                    "--escape-malformed-texts",
+                ])
+                .output()
+                .unwrap();
+
+            assert!(output.status.success());
+            assert!(test_utilities::file::read_content(&fr_strings_path)
+                .contains("string_1 french &amp; co"));
+        },
+    )
+}
+
 #[test]
 fn errors_are_printed_out() {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -111,7 +348,7 @@ fn errors_are_printed_out() {
 
 fn execute_with_copied_sample_res<F>(temp_dir: TempDir, input_type: &str, test: F)
 where
-    F: FnOnce(String) -> (),
+    F: FnOnce(String),
 {
     // Build paths
     let mut res_path = temp_dir.path().to_path_buf();
@@ -179,14 +416,14 @@ fn assert_status_and_stdout(output: Output) {
     assert_eq!(output_lines.next().unwrap(), "");
     test_utilities::eq::assert_eq_to_either_or_by(
         output_lines.next().unwrap(),
-        &fr_values,
-        &es_values,
+        fr_values,
+        es_values,
         |actual, expected| actual.contains("strings.xml") && actual.contains(expected),
     );
     test_utilities::eq::assert_eq_to_either_or_by(
         output_lines.next().unwrap(),
-        &fr_values,
-        &es_values,
+        fr_values,
+        es_values,
         |actual, expected| actual.contains("strings.xml") && actual.contains(expected),
     );
     assert_eq!(output_lines.next().unwrap(), "");
@@ -1,7 +1,6 @@
+use std::io::Write;
 use std::process::Command;
 
-use test_utilities;
-
 #[test]
 fn success_is_printed_out() {
     let output = Command::new("cargo")
@@ -31,14 +30,14 @@ fn success_is_printed_out() {
     assert_eq!(output_lines.next().unwrap(), "");
     test_utilities::eq::assert_eq_to_either_or_by(
         output_lines.next().unwrap(),
-        &default_values,
-        &fr_values,
+        default_values,
+        fr_values,
         |actual, expected| actual.contains("strings.xml") && actual.contains(expected),
     );
     test_utilities::eq::assert_eq_to_either_or_by(
         output_lines.next().unwrap(),
-        &default_values,
-        &fr_values,
+        default_values,
+        fr_values,
         |actual, expected| actual.contains("strings.xml") && actual.contains(expected),
     );
     assert_eq!(output_lines.next().unwrap(), "");
@@ -81,3 +80,270 @@ fn errors_can_skip_unlocalized() {
         .unwrap()
         .contains("Found 3 issues across 2 files!\n"));
 }
+
+#[test]
+fn errors_for_too_long_strings() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/too_long_input",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Found 1 issue across 1 file!\n"));
+}
+
+#[test]
+fn errors_for_strings_exceeding_max_length_ratio() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/too_long_input",
+            "--max-length-ratio",
+            "2",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output
+        .contains("Error 2 (too long string): Found 15 chars (allowed 12) in Annuler annuler\n"));
+    assert!(output.contains("Found 2 issues across 1 file!\n"));
+}
+
+#[test]
+fn errors_for_duplicate_names() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/duplicate_input",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output.contains(
+        "Error 1 (duplicate name): string_2 is defined at ./tests_data/validate/duplicate_input/values/donottranslate.xml:2 & ./tests_data/validate/duplicate_input/values/strings.xml:3\n"
+    ));
+    assert!(output.contains("Found 1 issue across 1 file!\n"));
+}
+
+#[test]
+fn errors_for_identical_texts_only_when_checked() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/identical_input",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/identical_input",
+            "--check-identical",
+            "--allow-identical",
+            "OK",
+            "--allow-identical-in",
+            "fr=Chat",
+            "--skip-identical-in",
+            "en",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output.contains("Error 1 (same as default text): Cancel\n"));
+    assert!(output.contains("Found 1 issue across 1 file!\n"));
+}
+
+#[test]
+fn errors_for_untranslatable_strings_unless_removed() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut res_path = temp_dir.path().to_path_buf();
+    res_path.push("res");
+
+    let mut default_strings =
+        test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+    let mut fr_strings =
+        test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+    default_strings
+        .file
+        .write_all(
+            test_utilities::file::read_content(
+                "./tests_data/validate/untranslatable_input/values/strings.xml",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+
+    fr_strings
+        .file
+        .write_all(
+            test_utilities::file::read_content(
+                "./tests_data/validate/untranslatable_input/values-fr/strings.xml",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+
+    let res_path = res_path.to_str().unwrap();
+    let output = Command::new("cargo")
+        .args(vec!["run", "validate", "--res-dir", res_path])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output.contains("Error 1 (translated untranslatable string(s)): Acmé\n"));
+    assert!(output.contains("Found 1 issue across 1 file!\n"));
+
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            res_path,
+            "--remove-untranslatable",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Removed untranslatable strings from the following files - \n\n"));
+    assert_eq!(
+        test_utilities::file::read_content(fr_strings.path),
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <string name="string_1">Enregistrer</string>
+</resources>"#
+    );
+}
+
+#[test]
+fn errors_for_inconsistent_punctuation() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/punctuation_input",
+            "--check-punctuation",
+            "--skip-punctuation-rule-in",
+            "th=terminal-punctuation",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        output.contains("Error 1 (inconsistent punctuation): Found [ellipsis] in Chargement...\n")
+    );
+    assert!(output.contains(
+        "Error 2 (inconsistent punctuation): Found [whitespace, terminal-punctuation] in Nom \n"
+    ));
+    assert!(output.contains("Found 2 issues across 1 file!\n"));
+
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/punctuation_input",
+            "--check-punctuation",
+            "--punctuation-rule",
+            "ellipsis",
+            "--skip-punctuation-rule-in",
+            "fr=ellipsis",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+}
+
+#[test]
+fn errors_for_invalid_references() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/references_input",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output.contains("Error 1 (undefined reference(s)): @string/string_4\n"));
+    assert!(output.contains("Error 1 (reference(s) replaced by text): Aide\n"));
+    assert!(output.contains("Found 2 issues across 2 files!\n"));
+}
+
+#[test]
+fn errors_for_missing_and_unused_quantities() {
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/plurals_input",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output.contains("Error 1 (missing quantities): [few, many] in plurals_1\n"));
+    assert!(!output.contains("unused quantities"));
+    assert!(output.contains("Found 1 issue across 1 file!\n"));
+
+    let output = Command::new("cargo")
+        .args(vec![
+            "run",
+            "validate",
+            "--res-dir",
+            "./tests_data/validate/plurals_input",
+            "--fail-on-unused-quantities",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let output = String::from_utf8(output.stderr).unwrap();
+    assert!(output.contains("Error 1 (unused quantities): [one] in plurals_1\n"));
+    assert!(output.contains("Found 2 issues across 2 files!\n"));
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct AndroidPlurals {
+    name: String,
+    items: BTreeMap<Quantity, String>,
+    is_localizable: bool,
+}
+
+/// Ordered the way they are usually listed out in `strings.xml`
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Quantity {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl AndroidPlurals {
+    pub fn new(
+        name: String,
+        items: BTreeMap<Quantity, String>,
+        is_localizable: bool,
+    ) -> AndroidPlurals {
+        AndroidPlurals {
+            name,
+            items,
+            is_localizable,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn items(&self) -> &BTreeMap<Quantity, String> {
+        &self.items
+    }
+
+    pub fn is_localizable(&self) -> bool {
+        self.is_localizable
+    }
+}
+
+impl Quantity {
+    pub fn from_name(name: &str) -> Option<Quantity> {
+        match name {
+            "zero" => Some(Quantity::Zero),
+            "one" => Some(Quantity::One),
+            "two" => Some(Quantity::Two),
+            "few" => Some(Quantity::Few),
+            "many" => Some(Quantity::Many),
+            "other" => Some(Quantity::Other),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Quantity::Zero => "zero",
+            Quantity::One => "one",
+            Quantity::Two => "two",
+            Quantity::Few => "few",
+            Quantity::Many => "many",
+            Quantity::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{AndroidPlurals, Quantity};
+
+    /// To expose a convenient way to build for tests
+    impl AndroidPlurals {
+        pub fn localizable<N: Into<String>>(
+            name: N,
+            items: Vec<(Quantity, &str)>,
+        ) -> AndroidPlurals {
+            AndroidPlurals::new(name.into(), build_items(items), true)
+        }
+
+        pub fn unlocalizable<N: Into<String>>(
+            name: N,
+            items: Vec<(Quantity, &str)>,
+        ) -> AndroidPlurals {
+            AndroidPlurals::new(name.into(), build_items(items), false)
+        }
+    }
+
+    fn build_items(items: Vec<(Quantity, &str)>) -> BTreeMap<Quantity, String> {
+        items
+            .into_iter()
+            .map(|(quantity, value)| (quantity, String::from(value)))
+            .collect()
+    }
+
+    #[test]
+    fn quantity_round_trips_through_name() {
+        for quantity in &[
+            Quantity::Zero,
+            Quantity::One,
+            Quantity::Two,
+            Quantity::Few,
+            Quantity::Many,
+            Quantity::Other,
+        ] {
+            assert_eq!(Quantity::from_name(quantity.name()), Some(*quantity))
+        }
+
+        assert_eq!(Quantity::from_name("several"), None)
+    }
+}
@@ -1,21 +1,42 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Debug, Hash, PartialEq)]
+use crate::constants;
+
+/// Strings are identified by their name, value & whether they are localizable.
+/// Metadata (context & max length) doesn't count towards equality, so compare it
+/// explicitly where it matters
+#[derive(Clone, Debug)]
 pub struct AndroidString {
     name: String,
     value: String,
     is_localizable: bool,
+    context: Option<String>,
+    max_length: Option<usize>,
 }
 
 impl AndroidString {
+    /// Use `with_context` & `with_max_length` to fill in the rest
     pub fn new(name: String, value: String, is_localizable: bool) -> AndroidString {
         AndroidString {
             name,
             value,
             is_localizable,
+            context: None,
+            max_length: None,
         }
     }
 
+    pub fn with_context(mut self, context: Option<String>) -> AndroidString {
+        self.context = context;
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: Option<usize>) -> AndroidString {
+        self.max_length = max_length;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -27,6 +48,60 @@ impl AndroidString {
     pub fn is_localizable(&self) -> bool {
         self.is_localizable
     }
+
+    /// Notes for translators about what this string is for. This is gathered from
+    /// the comments right above the string, its `description` attribute & the
+    /// examples of its `xliff:g` placeholders
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+
+    /// Maximum number of chars the value of this string (in any locale) could
+    /// have. This is declared through the `tools:maxLength` attribute
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Whether this string is an alias of another string (`@string/name` or
+    /// `@android:string/name`) instead of having a text of its own
+    pub fn is_reference(&self) -> bool {
+        self.referenced_name().is_some()
+            || parse_reference(&self.value, constants::references::ANDROID_STRING).is_some()
+    }
+
+    /// Name of the string in the same app this string is an alias of (`@string/name`)
+    pub fn referenced_name(&self) -> Option<&str> {
+        parse_reference(&self.value, constants::references::STRING)
+    }
+}
+
+fn parse_reference<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    let name = value.trim().strip_prefix(prefix)?;
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+    {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+impl PartialEq for AndroidString {
+    fn eq(&self, other: &AndroidString) -> bool {
+        self.name == other.name
+            && self.value == other.value
+            && self.is_localizable == other.is_localizable
+    }
+}
+
+impl Hash for AndroidString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.value.hash(state);
+        self.is_localizable.hash(state);
+    }
 }
 
 impl fmt::Display for AndroidString {
@@ -51,8 +126,46 @@ mod tests {
             AndroidString::new(name.into(), value.into(), true)
         }
 
+        pub fn localizable_with_context<N: Into<String>, V: Into<String>, C: Into<String>>(
+            name: N,
+            value: V,
+            context: C,
+        ) -> AndroidString {
+            AndroidString::new(name.into(), value.into(), true).with_context(Some(context.into()))
+        }
+
+        pub fn localizable_with_max_length<N: Into<String>, V: Into<String>>(
+            name: N,
+            value: V,
+            max_length: usize,
+        ) -> AndroidString {
+            AndroidString::new(name.into(), value.into(), true).with_max_length(Some(max_length))
+        }
+
         pub fn unlocalizable<N: Into<String>, V: Into<String>>(name: N, value: V) -> AndroidString {
             AndroidString::new(name.into(), value.into(), false)
         }
     }
+
+    #[test]
+    fn detects_references() {
+        let string = AndroidString::localizable("s1", " @string/s2 ");
+        assert!(string.is_reference());
+        assert_eq!(string.referenced_name(), Some("s2"));
+
+        let string = AndroidString::localizable("s1", "@android:string/ok");
+        assert!(string.is_reference());
+        assert_eq!(string.referenced_name(), None);
+
+        for value in &[
+            "@string/",
+            "See @string/s2",
+            "@string/s2 & more",
+            "email@string/s2",
+        ] {
+            let string = AndroidString::localizable("s1", *value);
+            assert!(!string.is_reference());
+            assert_eq!(string.referenced_name(), None);
+        }
+    }
 }
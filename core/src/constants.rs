@@ -1,4 +1,8 @@
 pub mod elements {
+    /// `xliff:g` which is used to mark placeholders
+    pub const XLIFF_G: &str = "g";
+    pub const ITEM: &str = "item";
+    pub const PLURALS: &str = "plurals";
     pub const RESOURCES: &str = "resources";
     pub const STRING: &str = "string";
 }
@@ -6,6 +10,19 @@ pub mod elements {
 pub mod attributes {
     pub const NAME: &str = "name";
     pub const LOCALIZABLE: &str = "translatable";
+    pub const QUANTITY: &str = "quantity";
+    pub const DESCRIPTION: &str = "description";
+    pub const ID: &str = "id";
+    pub const EXAMPLE: &str = "example";
+
+    /// Expected to be in the `tools` namespace (`tools:maxLength`)
+    pub const MAX_LENGTH: &str = "maxLength";
+}
+
+/// Prefixes of values that make a string an alias of another string
+pub mod references {
+    pub const STRING: &str = "@string/";
+    pub const ANDROID_STRING: &str = "@android:string/";
 }
 
 pub mod flags {
@@ -15,8 +32,65 @@ pub mod flags {
 pub mod fs {
     pub const BASE_VALUES_DIR_NAME: &str = "values";
     pub const STRING_FILE_NAME: &str = "strings.xml";
+    pub const SRC_DIR_NAME: &str = "src";
+
+    /// Stands for stdout (when written to) & stdin (when read from)
+    pub const STD_STREAM_PATH: &str = "-";
+}
+
+/// Placeholders in the names of files written out by `localize`
+pub mod templates {
+    pub const LOCALES: &str = "{locales}";
+    pub const DATE: &str = "{date}";
+    pub const MODULE: &str = "{module}";
+    pub const INDEX: &str = "{index}";
 }
 
 pub mod extn {
+    pub const ARB: &str = "arb";
     pub const CSV: &str = "csv";
+    pub const JSON: &str = "json";
+    pub const XML: &str = "xml";
+}
+
+pub mod csv {
+    pub const KEY_HEADER: &str = "string_name";
+    pub const SOURCE_HEADER: &str = "default_locale";
+    pub const CONTEXT_HEADER: &str = "context";
+    pub const MAX_LENGTH_HEADER: &str = "max_length";
+    pub const NOTES_HEADER: &str = "notes";
+    pub const STATUS_HEADER: &str = "status";
+    pub const REVIEWER_HEADER: &str = "reviewer";
+    pub const DELIMITER: u8 = b',';
+    pub const QUOTE: u8 = b'"';
+    pub const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+    pub const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+}
+
+pub mod json {
+    pub const LOCALE_KEY: &str = "@@locale";
+    pub const METADATA_KEY_PREFIX: &str = "@";
+    pub const SOURCE_METADATA_KEY: &str = "x-source";
+    pub const DESCRIPTION_METADATA_KEY: &str = "description";
+    pub const ARB_MAX_LENGTH_METADATA_KEY: &str = "x-max-length";
+}
+
+pub mod apple {
+    pub const BASE_LPROJ_DIR_NAME: &str = "Base";
+    pub const LPROJ_EXTN: &str = "lproj";
+    pub const STRINGS_FILE_NAME: &str = "Localizable.strings";
+    pub const STRINGSDICT_FILE_NAME: &str = "Localizable.stringsdict";
+}
+
+pub mod stringsdict {
+    pub const DICT: &str = "dict";
+    pub const KEY: &str = "key";
+    pub const PLIST: &str = "plist";
+    pub const STRING: &str = "string";
+
+    pub const LOCALIZED_FORMAT_KEY: &str = "NSStringLocalizedFormatKey";
+    pub const SPEC_TYPE_KEY: &str = "NSStringFormatSpecTypeKey";
+    pub const PLURAL_RULE_TYPE: &str = "NSStringPluralRuleType";
+    pub const VALUE_TYPE_KEY: &str = "NSStringFormatValueTypeKey";
+    pub const VARIABLE_NAME: &str = "value";
 }
@@ -1,22 +1,124 @@
 use std::error;
 use std::fmt;
 use std::io;
+use std::path::{Path, PathBuf};
 
 /// Do not implement any `From` for `Error` since we want all `Error`s to carry
-/// some context (which most usually is the path of the file with issues)
+/// some context (which most usually is the path of the file with issues). The
+/// path is kept as is, so that even paths with non UTF-8 chars are carried along
 #[derive(Debug)]
 pub struct Error {
-    pub(crate) context: String,
+    pub(crate) context: PathBuf,
     pub(crate) kind: ErrorKind,
 }
 
+/// Every kind has a stable code (look @ `ErrorKind::code`) that can be relied
+/// upon by scripts. Codes are never reused or changed once released
 #[derive(Debug)]
 pub enum ErrorKind {
     Csv(csv::Error),
     Io(io::Error),
+    Json(serde_json::Error),
     Message(String),
     XmlRead(xml::reader::Error),
     XmlWrite(xml::writer::Error),
+
+    /// Res dir doesn't exist or isn't a directory
+    MissingResDir,
+
+    /// Res dir doesn't have any `values-*` dir with a strings file
+    NoForeignStringsFiles,
+
+    /// There is no strings file for the locale ID
+    UnknownLocale(String),
+
+    /// Output directory path points to a file
+    InvalidOutputDir,
+
+    /// Output file exists & the policy doesn't allow touching it
+    OutputFileExists,
+
+    /// More than one output file would be written to the same path
+    DuplicateOutputFile,
+
+    /// The requested operation isn't supported for the format
+    UnsupportedFormat(String),
+
+    /// Localized CSV can't be used. All the problems found are listed out
+    InvalidCsv(Vec<CsvDiagnostic>),
+
+    /// CSVs with different columns can't be appended to one another
+    MismatchedCsvColumns,
+
+    /// The same key (string name, locale, etc.) is found more than once
+    DuplicateKey(String),
+
+    /// Localized texts that can't be written into `strings.xml` files as they
+    /// aren't well formed XML. All of them are listed out
+    MalformedTexts(Vec<MalformedText>),
+
+    /// JSON (or ARB) document isn't shaped like a localized strings document
+    InvalidJson(String),
+
+    /// Text isn't encoded as it claims to be (invalid UTF-8, odd UTF-16, etc.)
+    InvalidEncoding(String),
+
+    /// `strings.xml` has elements that aren't valid strings or plurals (like
+    /// ones without a name) or a value that can't be written out
+    InvalidStringsXml(String),
+
+    /// Apple `.strings` file can't be parsed
+    InvalidAppleStrings(String),
+
+    /// Apple `.stringsdict` file can't be parsed or has unsupported plurals
+    InvalidStringsdict(String),
+}
+
+/// A localized text that isn't well formed XML
+#[derive(Clone, Debug, PartialEq)]
+pub struct MalformedText {
+    pub locale_id: String,
+    pub name: String,
+    pub problem: String,
+}
+
+impl fmt::Display for MalformedText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Locale {}, string {}: {}",
+            self.locale_id, self.name, self.problem
+        )
+    }
+}
+
+/// A problem found at a specific place in a CSV
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvDiagnostic {
+    /// 1-based line on which the row starts (the header is on line 1)
+    pub row: Option<u64>,
+
+    /// Header of the column (or its 1-based position if it doesn't have a header)
+    pub column: Option<String>,
+    pub problem: String,
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for CsvDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.row, &self.column) {
+            (Some(row), Some(column)) => write!(f, "Row {}, column {}: ", row, column)?,
+            (Some(row), None) => write!(f, "Row {}: ", row)?,
+            (None, Some(column)) => write!(f, "Column {}: ", column)?,
+            (None, None) => {}
+        }
+
+        f.write_str(&self.problem)?;
+        match &self.suggestion {
+            None => Ok(()),
+            Some(suggestion) => write!(f, " ({})", suggestion),
+        }
+    }
 }
 
 /// Components that don't know the path, should return this which could be
@@ -27,13 +129,17 @@ pub struct InnerError {
 }
 
 impl Error {
-    pub fn new<S: Into<String>, E: Into<InnerError>>(context: S, error: E) -> Error {
+    pub fn new<P: Into<PathBuf>, E: Into<InnerError>>(context: P, error: E) -> Error {
         error.into().into_error(context)
     }
 
-    pub fn context(&self) -> &str {
+    pub fn context(&self) -> &Path {
         &self.context
     }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
 }
 
 impl error::Error for Error {
@@ -41,16 +147,17 @@ impl error::Error for Error {
         match &self.kind {
             ErrorKind::Csv(error) => Some(error),
             ErrorKind::Io(error) => Some(error),
-            ErrorKind::Message(_message) => None,
+            ErrorKind::Json(error) => Some(error),
             ErrorKind::XmlRead(error) => Some(error),
             ErrorKind::XmlWrite(error) => Some(error),
+            _ => None,
         }
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}: ", self.context)?;
+        write!(f, "{}: ", self.context.display())?;
         fmt::Display::fmt(&self.kind, f)
     }
 }
@@ -60,9 +167,84 @@ impl fmt::Display for ErrorKind {
         match &self {
             ErrorKind::Csv(error) => fmt::Display::fmt(error, f),
             ErrorKind::Io(error) => fmt::Display::fmt(error, f),
+            ErrorKind::Json(error) => fmt::Display::fmt(error, f),
             ErrorKind::Message(message) => fmt::Display::fmt(message, f),
             ErrorKind::XmlRead(error) => fmt::Display::fmt(error, f),
             ErrorKind::XmlWrite(error) => fmt::Display::fmt(error, f),
+            ErrorKind::MissingResDir => {
+                f.write_str("Res dir path doesn't exist or it is not a directory")
+            }
+            ErrorKind::NoForeignStringsFiles => {
+                f.write_str("Res dir doesn't have any non-default values dir with strings file!")
+            }
+            ErrorKind::UnknownLocale(locale_id) => {
+                write!(f, "No strings file found for locale: {}", locale_id)
+            }
+            ErrorKind::InvalidOutputDir => f.write_str("Output directory path points to a file!"),
+            ErrorKind::OutputFileExists => f.write_str("Output file already exists!"),
+            ErrorKind::DuplicateOutputFile => {
+                f.write_str("File name template doesn't give a unique name for every file!")
+            }
+            ErrorKind::UnsupportedFormat(message) => fmt::Display::fmt(message, f),
+            ErrorKind::InvalidCsv(diagnostics) => {
+                write!(f, "Found {} issue(s) in the CSV", diagnostics.len())?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n{}", diagnostic)?;
+                }
+
+                Ok(())
+            }
+            ErrorKind::MismatchedCsvColumns => {
+                f.write_str("Can't append to a file with different columns")
+            }
+            ErrorKind::DuplicateKey(key) => write!(f, "Found more than once: {}", key),
+            ErrorKind::MalformedTexts(malformed_texts) => {
+                write!(
+                    f,
+                    "Found {} localized text(s) that aren't well formed XML",
+                    malformed_texts.len()
+                )?;
+                for malformed_text in malformed_texts {
+                    write!(f, "\n{}", malformed_text)?;
+                }
+
+                Ok(())
+            }
+            ErrorKind::InvalidJson(message) => fmt::Display::fmt(message, f),
+            ErrorKind::InvalidEncoding(message) => fmt::Display::fmt(message, f),
+            ErrorKind::InvalidStringsXml(message) => fmt::Display::fmt(message, f),
+            ErrorKind::InvalidAppleStrings(message) => fmt::Display::fmt(message, f),
+            ErrorKind::InvalidStringsdict(message) => fmt::Display::fmt(message, f),
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Stable code identifying the kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Csv(_) => "csv",
+            ErrorKind::Io(_) => "io",
+            ErrorKind::Json(_) => "json",
+            ErrorKind::Message(_) => "other",
+            ErrorKind::XmlRead(_) => "xml_read",
+            ErrorKind::XmlWrite(_) => "xml_write",
+            ErrorKind::MissingResDir => "missing_res_dir",
+            ErrorKind::NoForeignStringsFiles => "no_foreign_strings_files",
+            ErrorKind::UnknownLocale(_) => "unknown_locale",
+            ErrorKind::InvalidOutputDir => "invalid_output_dir",
+            ErrorKind::OutputFileExists => "output_file_exists",
+            ErrorKind::DuplicateOutputFile => "duplicate_output_file",
+            ErrorKind::UnsupportedFormat(_) => "unsupported_format",
+            ErrorKind::InvalidCsv(_) => "invalid_csv",
+            ErrorKind::MismatchedCsvColumns => "mismatched_csv_columns",
+            ErrorKind::DuplicateKey(_) => "duplicate_key",
+            ErrorKind::MalformedTexts(_) => "malformed_texts",
+            ErrorKind::InvalidJson(_) => "invalid_json",
+            ErrorKind::InvalidEncoding(_) => "invalid_encoding",
+            ErrorKind::InvalidStringsXml(_) => "invalid_strings_xml",
+            ErrorKind::InvalidAppleStrings(_) => "invalid_apple_strings",
+            ErrorKind::InvalidStringsdict(_) => "invalid_stringsdict",
         }
     }
 }
@@ -74,7 +256,11 @@ impl fmt::Display for InnerError {
 }
 
 impl InnerError {
-    pub fn into_error<S: Into<String>>(self, context: S) -> Error {
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn into_error<P: Into<PathBuf>>(self, context: P) -> Error {
         Error {
             context: context.into(),
             kind: self.kind,
@@ -82,6 +268,12 @@ impl InnerError {
     }
 }
 
+impl From<ErrorKind> for InnerError {
+    fn from(kind: ErrorKind) -> Self {
+        InnerError { kind }
+    }
+}
+
 impl From<csv::Error> for InnerError {
     fn from(error: csv::Error) -> Self {
         InnerError {
@@ -98,6 +290,14 @@ impl From<io::Error> for InnerError {
     }
 }
 
+impl From<serde_json::Error> for InnerError {
+    fn from(error: serde_json::Error) -> Self {
+        InnerError {
+            kind: ErrorKind::Json(error),
+        }
+    }
+}
+
 impl From<String> for InnerError {
     fn from(message: String) -> Self {
         InnerError {
@@ -130,11 +330,11 @@ impl From<xml::writer::Error> for InnerError {
 
 /// To easily add context to errors
 pub trait ResultExt<T> {
-    fn with_context<S: Into<String>>(self, context: S) -> Result<T, Error>;
+    fn with_context<P: Into<PathBuf>>(self, context: P) -> Result<T, Error>;
 }
 
 impl<T, E: Into<InnerError>> ResultExt<T> for Result<T, E> {
-    fn with_context<S: Into<String>>(self, context: S) -> Result<T, Error> {
+    fn with_context<P: Into<PathBuf>>(self, context: P) -> Result<T, Error> {
         self.map_err(|error| error.into().into_error(context))
     }
 }
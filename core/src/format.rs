@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use crate::constants;
+
+/// Formats in which texts can be shipped off to be localized & read back from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    Json(JsonFlavor),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JsonFlavor {
+    /// A flat object of string name to text
+    Plain,
+
+    /// Application Resource Bundle (used by Flutter). Along with string name to text,
+    /// this also carries the locale (`@@locale`) & per string metadata (`@string_name`)
+    Arb,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => constants::extn::CSV,
+            Format::Json(JsonFlavor::Plain) => constants::extn::JSON,
+            Format::Json(JsonFlavor::Arb) => constants::extn::ARB,
+        }
+    }
+
+    /// Figures out the format from the file's extension. Returns `None` if
+    /// the extension isn't one of the known ones
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            constants::extn::CSV => Some(Format::Csv),
+            constants::extn::JSON => Some(Format::Json(JsonFlavor::Plain)),
+            constants::extn::ARB => Some(Format::Json(JsonFlavor::Arb)),
+            _ => None,
+        }
+    }
+}
+
+/// How CSVs are laid out & encoded. Spreadsheet apps & vendors don't always
+/// agree on these
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    pub quote: u8,
+
+    /// Encoding of the written out CSVs. CSVs that are read in are decoded as
+    /// per their BOM (UTF-8 if they don't have one)
+    pub encoding: CsvEncoding,
+
+    /// Header of the column with the string names
+    pub key_header: String,
+
+    /// Header of the column with the default texts
+    pub source_header: String,
+
+    /// Headers of extra columns that are skipped when reading CSVs
+    pub ignored_headers: Vec<String>,
+
+    /// Headers of extra metadata columns (like a vendor's `Status`). These are
+    /// written out empty for translators to fill in & their values are carried
+    /// along when reading CSVs, just like the reserved metadata columns
+    pub metadata_headers: Vec<String>,
+}
+
+/// Values of the metadata columns (notes, status, etc.) of a localized CSV row.
+/// Empty values are left out
+#[derive(Clone, Debug, PartialEq)]
+pub struct StringMetadata {
+    pub name: String,
+
+    /// Header to value in the order of the columns
+    pub values: Vec<(String, String)>,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        CsvDialect {
+            delimiter: constants::csv::DELIMITER,
+            quote: constants::csv::QUOTE,
+            encoding: CsvEncoding::default(),
+            key_header: String::from(constants::csv::KEY_HEADER),
+            source_header: String::from(constants::csv::SOURCE_HEADER),
+            ignored_headers: vec![],
+            metadata_headers: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CsvEncoding {
+    #[default]
+    Utf8,
+
+    /// UTF-8 with a BOM so that Excel figures out the encoding
+    Utf8Bom,
+
+    /// UTF-16 (little endian) with a BOM. This is what Excel writes out
+    Utf16Le,
+}
+
+impl CsvEncoding {
+    pub(crate) fn encode(self, text: &str, with_bom: bool) -> Vec<u8> {
+        let mut bytes = vec![];
+        match self {
+            CsvEncoding::Utf8 => bytes.extend_from_slice(text.as_bytes()),
+            CsvEncoding::Utf8Bom => {
+                if with_bom {
+                    bytes.extend_from_slice(constants::csv::UTF8_BOM);
+                }
+
+                bytes.extend_from_slice(text.as_bytes())
+            }
+            CsvEncoding::Utf16Le => {
+                if with_bom {
+                    bytes.extend_from_slice(constants::csv::UTF16_LE_BOM);
+                }
+
+                for unit in text.encode_utf16() {
+                    bytes.extend_from_slice(&unit.to_le_bytes())
+                }
+            }
+        }
+
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CsvEncoding, Format, JsonFlavor};
+
+    #[test]
+    fn encodes_csv_text() {
+        assert_eq!(CsvEncoding::Utf8.encode("é", true), vec![0xC3, 0xA9]);
+        assert_eq!(
+            CsvEncoding::Utf8Bom.encode("é", true),
+            vec![0xEF, 0xBB, 0xBF, 0xC3, 0xA9]
+        );
+        assert_eq!(CsvEncoding::Utf8Bom.encode("é", false), vec![0xC3, 0xA9]);
+        assert_eq!(
+            CsvEncoding::Utf16Le.encode("aé", true),
+            vec![0xFF, 0xFE, 0x61, 0x00, 0xE9, 0x00]
+        );
+    }
+
+    #[test]
+    fn finds_format_from_path() {
+        assert_eq!(Format::from_path("a/b.csv"), Some(Format::Csv));
+        assert_eq!(
+            Format::from_path("a/b.json"),
+            Some(Format::Json(JsonFlavor::Plain))
+        );
+        assert_eq!(
+            Format::from_path("a/b.ARB"),
+            Some(Format::Json(JsonFlavor::Arb))
+        );
+        assert_eq!(Format::from_path("a/b.txt"), None);
+        assert_eq!(Format::from_path("a/b"), None);
+    }
+}
@@ -0,0 +1,656 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::android_plurals::{AndroidPlurals, Quantity};
+use crate::android_string::AndroidString;
+use crate::constants;
+use crate::error::{Error, ResultExt};
+use crate::ops::dedup;
+use crate::ops::filter;
+use crate::ops::merge;
+use crate::reader::apple_strings_reader;
+use crate::reader::stringsdict_reader;
+use crate::reader::stringsdict_reader::ApplePlurals;
+use crate::util::apple_text_converter;
+use crate::util::foreign_locale_ids_finder;
+use crate::util::staged_files::StagedFiles;
+use crate::util::xml_utilities;
+use crate::writer::apple_strings_writer;
+use crate::writer::stringsdict_writer;
+use crate::writer::xml_writer;
+
+/// Writes out the localizable strings & plurals of every locale into
+/// `<locale>.lproj/Localizable.strings` & `<locale>.lproj/Localizable.stringsdict`.
+/// Default strings go into `Base.lproj`. `.stringsdict` is only written if there
+/// are plurals. Existing files are overwritten, but only once all the files have
+/// been written out. If the map is empty, iOS locale IDs are assumed to be the
+/// same as those of Android
+///
+/// Returns the list of output files written by this call
+pub fn export<R: AsRef<Path>, I: AsRef<Path>, S: ::std::hash::BuildHasher>(
+    res_dir_path: R,
+    ios_dir_path: I,
+    locale_id_to_ios_id_map: HashMap<String, String, S>,
+) -> Result<Vec<PathBuf>, Error> {
+    let res_dir_path = res_dir_path.as_ref();
+    let ios_dir_path = ios_dir_path.as_ref();
+    let locale_id_to_ios_id_map = foreign_locale_ids_finder::build_map_if_empty_or_return(
+        locale_id_to_ios_id_map,
+        res_dir_path,
+    )?;
+
+    let (default_strings, default_plurals) =
+        xml_utilities::read_default_strings(res_dir_path)?.into_strings_and_plurals();
+    let default_strings = filter::find_strings_to_localize(default_strings);
+    let default_plurals: Vec<AndroidPlurals> = default_plurals
+        .into_iter()
+        .filter(|p| p.is_localizable())
+        .collect();
+
+    let localizable_names = find_names(&default_strings, &default_plurals);
+    let mut staged_files = StagedFiles::new();
+    let mut written_files_paths = write_lproj(
+        &mut staged_files,
+        ios_dir_path,
+        constants::apple::BASE_LPROJ_DIR_NAME,
+        default_strings,
+        default_plurals,
+    )?;
+
+    for (locale_id, ios_id) in locale_id_to_ios_id_map {
+        let (foreign_strings, foreign_plurals) =
+            xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?
+                .into_strings_and_plurals();
+
+        written_files_paths.append(&mut write_lproj(
+            &mut staged_files,
+            ios_dir_path,
+            &ios_id,
+            foreign_strings
+                .into_iter()
+                .filter(|s| localizable_names.contains(s.name()))
+                .collect(),
+            foreign_plurals
+                .into_iter()
+                .filter(|p| localizable_names.contains(p.name()))
+                .collect(),
+        )?);
+    }
+
+    staged_files.commit()?;
+    Ok(written_files_paths)
+}
+
+/// Reads `<locale>.lproj/Localizable.strings` & `<locale>.lproj/Localizable.stringsdict`
+/// (whichever of those are present) for every locale & merges them into the
+/// respective foreign strings files. Only strings & plurals that are localizable
+/// in the default locale are imported. If a string or plural is already present,
+/// the imported value wins. Foreign strings files are created if required. If the
+/// map is empty, iOS locale IDs are assumed to be the same as those of Android
+///
+/// Returns the list of foreign strings files updated by this call
+pub fn import<R: AsRef<Path>, I: AsRef<Path>, S: ::std::hash::BuildHasher>(
+    res_dir_path: R,
+    ios_dir_path: I,
+    locale_id_to_ios_id_map: HashMap<String, String, S>,
+) -> Result<Vec<PathBuf>, Error> {
+    let res_dir_path = res_dir_path.as_ref();
+    let ios_dir_path = ios_dir_path.as_ref();
+    let locale_id_to_ios_id_map = foreign_locale_ids_finder::build_map_if_empty_or_return(
+        locale_id_to_ios_id_map,
+        res_dir_path,
+    )?;
+
+    let (default_strings, default_plurals) =
+        xml_utilities::read_default_strings(res_dir_path)?.into_strings_and_plurals();
+    let localizable_names = find_names(
+        &filter::find_strings_to_localize(default_strings),
+        &default_plurals
+            .into_iter()
+            .filter(|p| p.is_localizable())
+            .collect::<Vec<AndroidPlurals>>(),
+    );
+
+    let mut updated_files_paths = vec![];
+    let mut staged_files = StagedFiles::new();
+    for (locale_id, ios_id) in locale_id_to_ios_id_map {
+        let lproj_dir_path = build_lproj_dir_path(ios_dir_path, &ios_id);
+        if !lproj_dir_path.is_dir() {
+            continue;
+        }
+
+        let (mut new_strings, new_plurals) = read_lproj(&lproj_dir_path, &localizable_names)?;
+        let (mut existing_strings, existing_plurals) =
+            if has_foreign_strings_file(res_dir_path, &locale_id) {
+                xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?
+                    .into_strings_and_plurals()
+            } else {
+                (vec![], vec![])
+            };
+
+        // What the file would be written out as if nothing changed. Metadata
+        // (like comments) is written out too, so it doesn't count as a change
+        let output_file_path = xml_utilities::foreign_strings_file_path(res_dir_path, &locale_id);
+        let existing_content =
+            xml_writer::serialize(existing_strings.clone(), existing_plurals.clone())
+                .with_context(output_file_path.clone())?;
+
+        let mut to_be_written_strings =
+            merge::merge_and_group_strings(&mut new_strings, &mut existing_strings);
+        dedup::dedup_grouped_strings(&mut to_be_written_strings);
+        let to_be_written_plurals = merge_plurals(new_plurals, existing_plurals);
+
+        let new_content = xml_writer::serialize(to_be_written_strings, to_be_written_plurals)
+            .with_context(output_file_path.clone())?;
+        if new_content != existing_content {
+            let mut file = staged_files.stage(output_file_path.clone())?;
+            file.write_all(&new_content)
+                .with_context(output_file_path.clone())?;
+            updated_files_paths.push(output_file_path);
+        }
+    }
+
+    staged_files.commit()?;
+    Ok(updated_files_paths)
+}
+
+fn find_names(strings: &[AndroidString], plurals: &[AndroidPlurals]) -> HashSet<String> {
+    strings
+        .iter()
+        .map(|s| String::from(s.name()))
+        .chain(plurals.iter().map(|p| String::from(p.name())))
+        .collect()
+}
+
+fn build_lproj_dir_path(ios_dir_path: &Path, ios_locale_id: &str) -> PathBuf {
+    let mut lproj_dir_path = PathBuf::from(ios_dir_path);
+    lproj_dir_path.push(format!(
+        "{}.{}",
+        ios_locale_id,
+        constants::apple::LPROJ_EXTN
+    ));
+
+    lproj_dir_path
+}
+
+fn has_foreign_strings_file(res_dir_path: &Path, locale_id: &str) -> bool {
+    let mut strings_file_path = res_dir_path.to_path_buf();
+    strings_file_path.push(format!(
+        "{}-{}",
+        constants::fs::BASE_VALUES_DIR_NAME,
+        locale_id
+    ));
+    strings_file_path.push(constants::fs::STRING_FILE_NAME);
+    strings_file_path.is_file()
+}
+
+/// Apple strings files have no place for metadata other than comments. So the
+/// max length (if any) is written out along with the context
+fn build_comment(string: &AndroidString) -> Option<String> {
+    let max_length = string
+        .max_length()
+        .map(|length| format!("Max length: {}", length));
+
+    match (string.context(), max_length) {
+        (Some(context), Some(max_length)) => Some(format!("{}\n{}", context, max_length)),
+        (context, max_length) => context.map(String::from).or(max_length),
+    }
+}
+
+fn write_lproj(
+    staged_files: &mut StagedFiles,
+    ios_dir_path: &Path,
+    ios_locale_id: &str,
+    strings: Vec<AndroidString>,
+    plurals: Vec<AndroidPlurals>,
+) -> Result<Vec<PathBuf>, Error> {
+    let lproj_dir_path = build_lproj_dir_path(ios_dir_path, ios_locale_id);
+
+    let mut written_files_paths = vec![];
+    let (mut file, path) = stage_file(
+        staged_files,
+        &lproj_dir_path,
+        constants::apple::STRINGS_FILE_NAME,
+    )?;
+    apple_strings_writer::write(
+        &mut file,
+        strings
+            .iter()
+            .map(|s| {
+                (
+                    String::from(s.name()),
+                    apple_text_converter::to_apple(s.value()),
+                    build_comment(s),
+                )
+            })
+            .collect(),
+    )
+    .with_context(path.clone())?;
+    written_files_paths.push(path);
+
+    if !plurals.is_empty() {
+        let (mut file, path) = stage_file(
+            staged_files,
+            &lproj_dir_path,
+            constants::apple::STRINGSDICT_FILE_NAME,
+        )?;
+        stringsdict_writer::write(
+            &mut file,
+            plurals
+                .iter()
+                .map(|p| {
+                    (
+                        String::from(p.name()),
+                        convert_items(p.items(), apple_text_converter::to_apple),
+                    )
+                })
+                .collect(),
+        )
+        .with_context(path.clone())?;
+        written_files_paths.push(path);
+    }
+
+    Ok(written_files_paths)
+}
+
+fn read_lproj(
+    lproj_dir_path: &Path,
+    allow_only_names: &HashSet<String>,
+) -> Result<(Vec<AndroidString>, Vec<AndroidPlurals>), Error> {
+    let mut strings = vec![];
+    if let Some((file, path)) =
+        open_file_if_present(lproj_dir_path, constants::apple::STRINGS_FILE_NAME)?
+    {
+        for (name, value) in apple_strings_reader::read(file).with_context(path)? {
+            if allow_only_names.contains(&name) {
+                let value = apple_text_converter::to_android(&value);
+                strings.push(AndroidString::new(name, value, true));
+            }
+        }
+    }
+
+    let mut plurals = vec![];
+    if let Some((file, path)) =
+        open_file_if_present(lproj_dir_path, constants::apple::STRINGSDICT_FILE_NAME)?
+    {
+        let apple_plurals: Vec<ApplePlurals> = stringsdict_reader::read(file).with_context(path)?;
+        for (name, items) in apple_plurals {
+            if allow_only_names.contains(&name) {
+                let items = convert_items(&items, apple_text_converter::to_android);
+                plurals.push(AndroidPlurals::new(name, items, true));
+            }
+        }
+    }
+
+    Ok((strings, plurals))
+}
+
+fn convert_items<F>(items: &BTreeMap<Quantity, String>, converter: F) -> BTreeMap<Quantity, String>
+where
+    F: Fn(&str) -> String,
+{
+    items
+        .iter()
+        .map(|(quantity, value)| (*quantity, converter(value)))
+        .collect()
+}
+
+/// Plurals from `new_plurals` replace those with the same name in `existing_plurals`.
+/// The rest of them are added at the end
+fn merge_plurals(
+    new_plurals: Vec<AndroidPlurals>,
+    existing_plurals: Vec<AndroidPlurals>,
+) -> Vec<AndroidPlurals> {
+    let mut new_plurals: Vec<Option<AndroidPlurals>> = new_plurals.into_iter().map(Some).collect();
+    let mut result: Vec<AndroidPlurals> = existing_plurals
+        .into_iter()
+        .map(|existing| {
+            new_plurals
+                .iter_mut()
+                .find(|new| new.as_ref().map(|n| n.name()) == Some(existing.name()))
+                .and_then(Option::take)
+                .unwrap_or(existing)
+        })
+        .collect();
+
+    result.extend(new_plurals.into_iter().flatten());
+    result
+}
+
+fn stage_file(
+    staged_files: &mut StagedFiles,
+    dir_path: &Path,
+    file_name: &str,
+) -> Result<(File, PathBuf), Error> {
+    let mut file_path = dir_path.to_path_buf();
+    file_path.push(file_name);
+    staged_files
+        .stage(file_path.clone())
+        .map(|file| (file, file_path))
+}
+
+fn open_file_if_present(
+    dir_path: &Path,
+    file_name: &str,
+) -> Result<Option<(File, PathBuf)>, Error> {
+    let mut file_path = dir_path.to_path_buf();
+    file_path.push(file_name);
+    if !file_path.is_file() {
+        return Ok(None);
+    }
+
+    File::open(&file_path)
+        .with_context(file_path.clone())
+        .map(|file| Some((file, file_path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use test_utilities;
+
+    use crate::android_plurals::{AndroidPlurals, Quantity};
+    use crate::android_string::AndroidString;
+    use crate::util::xml_utilities;
+    use crate::writer::xml_writer;
+
+    #[test]
+    fn builds_comment_from_context_and_max_length() {
+        assert_eq!(
+            super::build_comment(&AndroidString::localizable("s1", "value")),
+            None
+        );
+
+        assert_eq!(
+            super::build_comment(&AndroidString::localizable_with_max_length(
+                "s1", "value", 10
+            )),
+            Some(String::from("Max length: 10"))
+        );
+
+        assert_eq!(
+            super::build_comment(
+                &AndroidString::new(String::from("s1"), String::from("value"), true)
+                    .with_context(Some(String::from("Title")))
+                    .with_max_length(Some(10))
+            ),
+            Some(String::from("Title\nMax length: 10"))
+        );
+    }
+
+    #[test]
+    fn exports_strings_and_plurals() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+        let mut ios_path = temp_dir.path().to_path_buf();
+        ios_path.push("ios");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write_with_plurals(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "%1$s isn\\'t here"),
+                AndroidString::unlocalizable("s2", "unlocalizable"),
+            ],
+            vec![AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::One, "%d item"), (Quantity::Other, "%d items")],
+            )],
+        )
+        .unwrap();
+
+        xml_writer::write(
+            &mut fr_strings.file,
+            vec![
+                AndroidString::localizable("s1", "%1$s n\\'est pas là"),
+                AndroidString::localizable("s3", "stale"),
+            ],
+        )
+        .unwrap();
+
+        let mut map = HashMap::new();
+        map.insert(String::from("fr"), String::from("fr-CA"));
+        let written_files =
+            super::export(res_path.to_str().unwrap(), ios_path.to_str().unwrap(), map).unwrap();
+
+        let base_strings_path = build_path(&ios_path, "Base.lproj", "Localizable.strings");
+        let base_stringsdict_path = build_path(&ios_path, "Base.lproj", "Localizable.stringsdict");
+        let fr_strings_path = build_path(&ios_path, "fr-CA.lproj", "Localizable.strings");
+        test_utilities::list::assert_strict_list_eq(
+            written_files,
+            vec![
+                String::from(base_strings_path.to_str().unwrap()),
+                String::from(base_stringsdict_path.to_str().unwrap()),
+                String::from(fr_strings_path.to_str().unwrap()),
+            ],
+        );
+
+        assert_eq!(
+            test_utilities::file::read_content(&base_strings_path),
+            "\"s1\" = \"%1$@ isn't here\";\n"
+        );
+        assert!(test_utilities::file::read_content(&base_stringsdict_path)
+            .contains("<string>%d items</string>"));
+        assert_eq!(
+            test_utilities::file::read_content(&fr_strings_path),
+            "\"s1\" = \"%1$@ n'est pas là\";\n"
+        );
+    }
+
+    #[test]
+    fn imports_strings_and_plurals() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+        let mut ios_path = temp_dir.path().to_path_buf();
+        ios_path.push("ios");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write_with_plurals(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "%1$s is here"),
+                AndroidString::localizable("s2", "english 2"),
+                AndroidString::unlocalizable("s3", "unlocalizable"),
+            ],
+            vec![AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::One, "%d item"), (Quantity::Other, "%d items")],
+            )],
+        )
+        .unwrap();
+
+        xml_writer::write(
+            &mut fr_strings.file,
+            vec![
+                AndroidString::localizable("s1", "old french 1"),
+                AndroidString::localizable("s2", "old french 2"),
+            ],
+        )
+        .unwrap();
+
+        fs::create_dir_all(build_path(&ios_path, "fr.lproj", "")).unwrap();
+        fs::create_dir_all(build_path(&ios_path, "de.lproj", "")).unwrap();
+        test_utilities::file::write_content(
+            build_path(&ios_path, "fr.lproj", "Localizable.strings"),
+            "\"s1\" = \"%1$@ n'est pas là\";\n\"s3\" = \"ignored\";\n\"s4\" = \"ignored\";",
+        );
+        test_utilities::file::write_content(
+            build_path(&ios_path, "de.lproj", "Localizable.stringsdict"),
+            r#"<plist><dict><key>p1</key><dict>
+<key>NSStringLocalizedFormatKey</key><string>%#@value@</string>
+<key>value</key><dict><key>one</key><string>%lu Ding</string><key>other</key><string>%lu Dinge</string></dict>
+</dict></dict></plist>"#,
+        );
+
+        let mut map = HashMap::new();
+        map.insert(String::from("fr"), String::from("fr"));
+        map.insert(String::from("de"), String::from("de"));
+        map.insert(String::from("es"), String::from("es"));
+        let mut updated_files =
+            super::import(res_path.to_str().unwrap(), ios_path.to_str().unwrap(), map).unwrap();
+        updated_files.sort();
+
+        test_utilities::list::assert_strict_list_eq(
+            updated_files,
+            vec![
+                build_path(&res_path, "values-de", "strings.xml"),
+                fr_strings.path,
+            ],
+        );
+
+        let fr_strings = xml_utilities::read_foreign_strings(&res_path, "fr").unwrap();
+        test_utilities::list::assert_strict_list_eq(
+            fr_strings.strings().to_vec(),
+            vec![
+                AndroidString::localizable("s1", "%1$s n\\'est pas là"),
+                AndroidString::localizable("s2", "old french 2"),
+            ],
+        );
+
+        let (de_strings, de_plurals) = xml_utilities::read_foreign_strings(&res_path, "de")
+            .unwrap()
+            .into_strings_and_plurals();
+        assert!(de_strings.is_empty());
+        test_utilities::list::assert_strict_list_eq(
+            de_plurals,
+            vec![AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::One, "%d Ding"), (Quantity::Other, "%d Dinge")],
+            )],
+        );
+    }
+
+    #[test]
+    fn export_leaves_existing_files_untouched_if_any_locale_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+        let mut ios_path = temp_dir.path().to_path_buf();
+        ios_path.push("ios");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "english")],
+        )
+        .unwrap();
+        xml_writer::write(
+            &mut fr_strings.file,
+            vec![AndroidString::localizable("s1", "french")],
+        )
+        .unwrap();
+
+        let base_strings_path = build_path(&ios_path, "Base.lproj", "Localizable.strings");
+        fs::create_dir_all(build_path(&ios_path, "Base.lproj", "")).unwrap();
+        test_utilities::file::write_content(&base_strings_path, "\"s1\" = \"old\";\n");
+
+        // A file in place of the lproj dir can't be written into
+        test_utilities::file::write_content(build_path(&ios_path, "fr.lproj", ""), "");
+
+        let mut map = HashMap::new();
+        map.insert(String::from("fr"), String::from("fr"));
+        assert!(
+            super::export(res_path.to_str().unwrap(), ios_path.to_str().unwrap(), map).is_err()
+        );
+
+        assert_eq!(
+            test_utilities::file::read_content(&base_strings_path),
+            "\"s1\" = \"old\";\n"
+        );
+    }
+
+    #[test]
+    fn import_leaves_files_with_unchanged_texts_untouched() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+        let mut ios_path = temp_dir.path().to_path_buf();
+        ios_path.push("ios");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "english")],
+        )
+        .unwrap();
+
+        let fr_strings_content = r#"<resources xmlns:tools="http://schemas.android.com/tools">
+    <!-- Reviewed -->
+    <string name="s1" tools:maxLength="10">french</string>
+</resources>"#;
+        test_utilities::file::write_content(&fr_strings.path, fr_strings_content);
+
+        fs::create_dir_all(build_path(&ios_path, "fr.lproj", "")).unwrap();
+        test_utilities::file::write_content(
+            build_path(&ios_path, "fr.lproj", "Localizable.strings"),
+            "\"s1\" = \"french\";\n",
+        );
+
+        let mut map = HashMap::new();
+        map.insert(String::from("fr"), String::from("fr"));
+        let updated_files =
+            super::import(res_path.to_str().unwrap(), ios_path.to_str().unwrap(), map).unwrap();
+
+        test_utilities::list::assert_list_is_empty(updated_files);
+        assert_eq!(
+            test_utilities::file::read_content(&fr_strings.path),
+            fr_strings_content
+        );
+    }
+
+    #[test]
+    fn merge_plurals_replaces_existing_and_appends_new() {
+        let merged = super::merge_plurals(
+            vec![
+                AndroidPlurals::localizable("p3", vec![(Quantity::Other, "new 3")]),
+                AndroidPlurals::localizable("p1", vec![(Quantity::Other, "new 1")]),
+            ],
+            vec![
+                AndroidPlurals::localizable("p1", vec![(Quantity::Other, "old 1")]),
+                AndroidPlurals::localizable("p2", vec![(Quantity::Other, "old 2")]),
+            ],
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            merged,
+            vec![
+                AndroidPlurals::localizable("p1", vec![(Quantity::Other, "new 1")]),
+                AndroidPlurals::localizable("p2", vec![(Quantity::Other, "old 2")]),
+                AndroidPlurals::localizable("p3", vec![(Quantity::Other, "new 3")]),
+            ],
+        )
+    }
+
+    fn build_path(root: &Path, dir_name: &str, file_name: &str) -> PathBuf {
+        let mut path = root.to_path_buf();
+        path.push(dir_name);
+        if !file_name.is_empty() {
+            path.push(file_name);
+        }
+
+        path
+    }
+}
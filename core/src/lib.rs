@@ -1,9 +1,29 @@
+//! Reads, manipulates & writes out Android string resources. Apart from the
+//! commands exposed by the CLI (`localize`, `localized`, `validator` & `ios`),
+//! `resources` has the model of a `res` dir along with the ops to merge, diff &
+//! filter strings
+//!
+//! ```no_run
+//! use android_localization_core::resources::{self, ResourceSet};
+//!
+//! let resource_set = ResourceSet::read("app/src/main/res").unwrap();
+//! let default_strings = resource_set.default_strings().strings();
+//! for locale_id in resource_set.locale_ids() {
+//!     let foreign_strings = resource_set.foreign_strings(locale_id).unwrap();
+//!     let diff = resources::diff(default_strings, foreign_strings.strings());
+//!     println!("{} is missing {} strings", locale_id, diff.missing.len());
+//! }
+//! ```
+
 pub use validate::formatter;
 pub use validate::validator;
 
+mod android_plurals;
 mod android_string;
 mod constants;
-mod error;
+pub mod error;
+pub mod format;
+pub mod ios;
 mod localizable_strings;
 pub mod localize;
 pub mod localized;
@@ -11,6 +31,8 @@ mod localized_string;
 mod localized_strings;
 mod ops;
 mod reader;
+pub mod resources;
+pub mod translation_memory;
 mod util;
 mod validate;
 mod writer;
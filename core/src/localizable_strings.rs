@@ -1,19 +1,37 @@
+use std::collections::HashMap;
+
 use crate::android_string::AndroidString;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct LocalizableStrings {
     to_locale: String,
     default_locale_strings: Vec<AndroidString>,
+
+    /// String name to a localized text that is written out along with the string.
+    /// Either a suggestion (from the translation memory) or the current localized
+    /// text (when already localized strings are sent off for review)
+    prefilled_values: HashMap<String, String>,
 }
 
 impl LocalizableStrings {
+    /// Only tests build strings without looking up the translation memory
+    #[cfg(test)]
     pub fn new(
         to_locale: String,
         default_locale_strings: Vec<AndroidString>,
+    ) -> LocalizableStrings {
+        LocalizableStrings::with_prefilled_values(to_locale, default_locale_strings, HashMap::new())
+    }
+
+    pub fn with_prefilled_values(
+        to_locale: String,
+        default_locale_strings: Vec<AndroidString>,
+        prefilled_values: HashMap<String, String>,
     ) -> LocalizableStrings {
         LocalizableStrings {
             to_locale,
             default_locale_strings,
+            prefilled_values,
         }
     }
 
@@ -24,4 +42,8 @@ impl LocalizableStrings {
     pub fn default_locale_strings(&self) -> &[AndroidString] {
         &self.default_locale_strings
     }
+
+    pub fn prefilled_value(&self, string_name: &str) -> Option<&str> {
+        self.prefilled_values.get(string_name).map(String::as_str)
+    }
 }
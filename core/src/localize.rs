@@ -1,147 +1,551 @@
 use std::collections::HashMap;
 use std::fs;
-use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::android_plurals::AndroidPlurals;
 use crate::android_string::AndroidString;
 use crate::constants;
-use crate::error::{Error, ResultExt};
+use crate::error::{Error, ErrorKind, InnerError, ResultExt};
+use crate::format::{CsvDialect, CsvEncoding, Format};
 use crate::localizable_strings::LocalizableStrings;
+use crate::ops::dedup;
 use crate::ops::filter;
+use crate::ops::merge;
+use crate::translation_memory;
+use crate::translation_memory::{Leverage, TranslationMemory};
 use crate::util::foreign_locale_ids_finder;
+use crate::util::staged_files::StagedFiles;
+use crate::util::text_decoder;
+use crate::util::utc_time::UtcTime;
 use crate::util::xml_utilities;
+use crate::validate::duplicate_names;
 use crate::writer::csv_writer;
+use crate::writer::json_writer;
+use crate::writer::xml_writer;
 
-/// Returns the list of output files created by this call. These aren't guaranteed
-/// to be valid paths to files. Sometimes, if a file's path can't be expressed by
-/// `String` (in case it has non UTF-8 chars), it could just be the file's name
-pub fn localize<S: ::std::hash::BuildHasher>(
-    res_dir_path: &str,
-    output_dir_path: &str,
+/// Files written out by `localize` along with how much of the texts could be
+/// filled in from the translation memory (if it was put to use)
+#[derive(Debug, Default, PartialEq)]
+pub struct Localization {
+    pub created_files: Vec<PathBuf>,
+    pub applied_files: Vec<PathBuf>,
+    pub leverage: Vec<Leverage>,
+}
+
+const DEFAULT_CSV_FILE_NAME_TEMPLATE: &str = "to_localize_{index}";
+const DEFAULT_PER_LOCALE_CSV_FILE_NAME_TEMPLATE: &str = "to_localize_{locales}";
+const DEFAULT_JSON_FILE_NAME_TEMPLATE: &str = "to_localize_{locales}";
+const DEFAULT_MODULE_NAME: &str = "app";
+
+/// How the files written out by `localize` are to be named & laid out
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OutputOptions {
+    /// File name (without the extension) with placeholders for the locales
+    /// (`{locales}`), the current UTC date (`{date}`), the module the res dir
+    /// is in (`{module}`) & the file's index (`{index}`)
+    pub file_name_template: Option<String>,
+    pub existing_file_policy: ExistingFilePolicy,
+    pub csv_layout: CsvLayout,
+    pub csv_dialect: CsvDialect,
+
+    /// Strings that are already localized are also written out (with their
+    /// current localized text) so that they can be reviewed
+    pub include_localized: bool,
+}
+
+/// How locales are split across CSVs. JSON & ARB documents are always written
+/// out per locale
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CsvLayout {
+    /// Locales missing the same strings are written into the same file
+    #[default]
+    Grouped,
+    PerLocale,
+
+    /// All locales are written into a single file. Cells of strings that are
+    /// already localized are left empty
+    Wide,
+}
+
+/// What to do when a file with the same name is already in the output dir
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExistingFilePolicy {
+    #[default]
+    Fail,
+    Overwrite,
+
+    /// Only for CSVs with the same columns
+    Append,
+
+    /// Adds the current UTC time to the new file's name
+    Timestamp,
+}
+
+/// If translation memory options are passed in, texts to be localized that have
+/// already been translated elsewhere (with the exact same default text) are either
+/// pre-filled in the written out files or straight away written into `strings.xml`.
+///
+/// If the output dir path is `-`, a single CSV with all the locales is written
+/// out to stdout instead.
+///
+/// Errors with `ErrorKind::DuplicateKey` if a name is defined more than once in
+/// the values dir of the default locale or of any of the foreign locales, unless
+/// duplicate names are allowed (only the first definition is used then)
+pub fn localize<R: AsRef<Path>, O: AsRef<Path>, S: ::std::hash::BuildHasher>(
+    res_dir_path: R,
+    output_dir_path: O,
     locale_id_to_name_map: HashMap<String, String, S>,
-) -> Result<Vec<String>, Error> {
+    format: Format,
+    translation_memory_options: Option<translation_memory::Options>,
+    output_options: OutputOptions,
+    allow_duplicate_names: bool,
+) -> Result<Localization, Error> {
+    let res_dir_path = res_dir_path.as_ref();
+    let output_dir_path = output_dir_path.as_ref();
     let locale_id_to_name_map = foreign_locale_ids_finder::build_map_if_empty_or_return(
         locale_id_to_name_map,
         res_dir_path,
     )?;
 
     if locale_id_to_name_map.is_empty() {
+        return Err(Error::new(res_dir_path, ErrorKind::NoForeignStringsFiles));
+    }
+
+    let include_localized = output_options.include_localized;
+    let output = if output_dir_path != Path::new(constants::fs::STD_STREAM_PATH) {
+        create_output_dir_if_required(output_dir_path)?;
+        let file_provider = FileProvider::new(
+            output_dir_path.to_path_buf(),
+            output_options,
+            find_module_name(res_dir_path),
+        );
+
+        Output::Files(file_provider, format)
+    } else if format != Format::Csv {
         return Err(Error::new(
-            res_dir_path,
-            "Res dir doesn't have any non-default values dir with strings file!",
+            output_dir_path,
+            ErrorKind::UnsupportedFormat(String::from("Only CSVs can be written out to stdout!")),
         ));
+    } else {
+        Output::Stdout(output_options.csv_dialect)
+    };
+
+    if !allow_duplicate_names {
+        duplicate_names::ensure_unique_names(
+            res_dir_path,
+            locale_id_to_name_map.keys().map(String::as_str),
+        )?;
     }
 
-    create_output_dir_if_required(output_dir_path)?;
+    // Build memory out of this & other modules' translations
+    let translation_memory = match &translation_memory_options {
+        None => None,
+        Some(options) => {
+            let mut res_dir_paths = vec![res_dir_path];
+            res_dir_paths.extend(options.extra_res_dir_paths.iter().map(PathBuf::as_path));
+            Some(TranslationMemory::build(&res_dir_paths)?)
+        }
+    };
 
     // Read default strings
-    let res_dir_path = Path::new(res_dir_path);
-    let mut localizable_default_strings = filter::find_localizable_strings(
+    let mut localizable_default_strings = filter::find_strings_to_localize(
         xml_utilities::read_default_strings(res_dir_path)?.into_strings(),
     );
 
     // For all languages, write out strings requiring localization
     write_out_strings_to_localize(
         res_dir_path,
-        output_dir_path,
+        output,
         locale_id_to_name_map,
         &mut localizable_default_strings,
+        translation_memory.as_ref(),
+        translation_memory_options.is_some_and(|options| options.auto_apply),
+        include_localized,
     )
 }
 
-fn create_output_dir_if_required(output_dir_path: &str) -> Result<(), Error> {
-    let output_path = PathBuf::from(output_dir_path);
-    if output_path.is_file() {
-        Err(Error::new(
-            output_dir_path,
-            "Output directory path points to a file!",
-        ))
-    } else if output_path.exists() {
+fn create_output_dir_if_required(output_dir_path: &Path) -> Result<(), Error> {
+    if output_dir_path.is_file() {
+        Err(Error::new(output_dir_path, ErrorKind::InvalidOutputDir))
+    } else if output_dir_path.exists() {
         Ok(())
     } else {
-        fs::create_dir_all(PathBuf::from(output_dir_path))
-            .with_context(String::from(output_dir_path))
+        fs::create_dir_all(output_dir_path).with_context(output_dir_path)
     }
 }
 
 fn write_out_strings_to_localize<S: ::std::hash::BuildHasher>(
     res_dir_path: &Path,
-    output_dir_path: &str,
+    output: Output,
     locale_id_to_name_map: HashMap<String, String, S>,
     localizable_default_strings: &mut [AndroidString],
-) -> Result<Vec<String>, Error> {
+    translation_memory: Option<&TranslationMemory>,
+    auto_apply: bool,
+    include_localized: bool,
+) -> Result<Localization, Error> {
+    let mut localization = Localization::default();
     let mut localizable_strings_list = vec![];
+    let mut staged_files = StagedFiles::new();
     for (locale_id, locale_name) in locale_id_to_name_map {
-        let mut foreign_strings =
-            xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?.into_strings();
+        let (mut foreign_strings, foreign_plurals) =
+            xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?
+                .into_strings_and_plurals();
 
-        let strings_to_localize =
+        let mut strings_to_localize =
             filter::find_missing_strings(&mut foreign_strings, localizable_default_strings);
 
+        // Current localized texts go into the cells of already localized strings
+        let mut prefilled_values = HashMap::new();
+        if include_localized {
+            prefilled_values.extend(
+                foreign_strings
+                    .iter()
+                    .map(|s| (String::from(s.name()), String::from(s.value()))),
+            );
+        }
+
+        if let Some(translation_memory) = translation_memory {
+            let mut leverage = Leverage::new(locale_name.clone());
+            let mut matches = HashMap::new();
+            for string in &strings_to_localize {
+                let translation = translation_memory.find(&locale_id, string.value());
+                leverage.count(string, translation.is_some());
+                if let Some(translation) = translation {
+                    matches.insert(String::from(string.name()), String::from(translation));
+                }
+            }
+
+            localization.leverage.push(leverage);
+            if !auto_apply {
+                // Matched strings are still sent off so that they can be reviewed
+                prefilled_values.extend(matches);
+            } else if !matches.is_empty() {
+                // Applied strings needn't be localized anymore (unless already
+                // localized strings are being written out too)
+                strings_to_localize.retain(|s| !matches.contains_key(s.name()));
+                if include_localized {
+                    prefilled_values.extend(matches.clone());
+                }
+
+                let applied_strings = matches
+                    .into_iter()
+                    .map(|(name, translation)| AndroidString::new(name, translation, true))
+                    .collect();
+
+                localization.applied_files.push(apply_to_foreign_strings(
+                    &mut staged_files,
+                    res_dir_path,
+                    &locale_id,
+                    foreign_strings,
+                    foreign_plurals,
+                    applied_strings,
+                )?);
+            }
+        }
+
+        if include_localized {
+            strings_to_localize = localizable_default_strings.to_vec();
+        }
+
         if !strings_to_localize.is_empty() {
-            localizable_strings_list.push(LocalizableStrings::new(locale_name, strings_to_localize))
+            localizable_strings_list.push(LocalizableStrings::with_prefilled_values(
+                locale_name,
+                strings_to_localize,
+                prefilled_values,
+            ))
         }
     }
 
+    localization
+        .leverage
+        .sort_by(|a, b| a.locale.cmp(&b.locale));
+
     if !localizable_strings_list.is_empty() {
-        let mut sink_provider = FileProvider::new(String::from(output_dir_path));
-        csv_writer::write(localizable_strings_list, &mut sink_provider)?;
+        // Locales are iterated over in no particular order. Sort them so that
+        // the files are named & laid out the same way every time
+        localizable_strings_list.sort_by(|a, b| a.to_locale().cmp(b.to_locale()));
+        localization.created_files = write_out(output, localizable_strings_list)?;
+    }
 
-        Ok(sink_provider.into_created_files())
-    } else {
-        Ok(vec![])
+    // Applied strings are only written into place once all the locales are
+    // done & the texts to be localized are written out, so that the res dir is
+    // left untouched if anything fails
+    staged_files.commit()?;
+    Ok(localization)
+}
+
+/// Returns the paths of the files that were written out
+fn write_out(
+    output: Output,
+    localizable_strings_list: Vec<LocalizableStrings>,
+) -> Result<Vec<PathBuf>, Error> {
+    match output {
+        Output::Stdout(csv_dialect) => {
+            csv_writer::write_combined(
+                localizable_strings_list,
+                &csv_dialect,
+                &mut StdoutProvider,
+            )?;
+            Ok(vec![PathBuf::from(constants::fs::STD_STREAM_PATH)])
+        }
+
+        Output::Files(mut sink_provider, format) => {
+            let csv_dialect = sink_provider.options.csv_dialect.clone();
+            match (format, sink_provider.options.csv_layout) {
+                (Format::Csv, CsvLayout::Grouped) => {
+                    csv_writer::write(localizable_strings_list, &csv_dialect, &mut sink_provider)?
+                }
+                (Format::Csv, CsvLayout::PerLocale) => csv_writer::write_per_locale(
+                    localizable_strings_list,
+                    &csv_dialect,
+                    &mut sink_provider,
+                )?,
+                (Format::Csv, CsvLayout::Wide) => csv_writer::write_combined(
+                    localizable_strings_list,
+                    &csv_dialect,
+                    &mut sink_provider,
+                )?,
+                (Format::Json(flavor), _) => {
+                    json_writer::write(localizable_strings_list, flavor, &mut sink_provider)?
+                }
+            }
+
+            Ok(sink_provider.into_created_files())
+        }
     }
 }
 
+/// Merges the applied strings into the already localized strings & stages them
+/// to be written out. Returns the path of the file to be updated
+fn apply_to_foreign_strings(
+    staged_files: &mut StagedFiles,
+    res_dir_path: &Path,
+    locale_id: &str,
+    existing_foreign_strings: Vec<AndroidString>,
+    existing_foreign_plurals: Vec<AndroidPlurals>,
+    mut applied_strings: Vec<AndroidString>,
+) -> Result<PathBuf, Error> {
+    let mut already_localized_foreign_strings =
+        filter::find_localizable_strings(existing_foreign_strings);
+
+    let mut to_be_written_foreign_strings = merge::merge_and_group_strings(
+        &mut applied_strings,
+        &mut already_localized_foreign_strings,
+    );
+    dedup::dedup_grouped_strings(&mut to_be_written_foreign_strings);
+
+    let output_file_path = xml_utilities::foreign_strings_file_path(res_dir_path, locale_id);
+    let mut file = staged_files.stage(output_file_path.clone())?;
+    xml_writer::write_with_plurals(
+        &mut file,
+        to_be_written_foreign_strings,
+        existing_foreign_plurals,
+    )
+    .with_context(output_file_path.clone())?;
+
+    Ok(output_file_path)
+}
+
+/// Every sink is stdout. Look @ `csv_writer::write_combined` to write out
+/// a single document
+struct StdoutProvider;
+
+impl csv_writer::SinkProvider for StdoutProvider {
+    fn execute_with_new_sink(&mut self, writer: csv_writer::Writer) -> Result<(), Error> {
+        let stdout = io::stdout();
+        let mut sink = stdout.lock();
+        writer
+            .write(&mut sink)
+            .with_context(constants::fs::STD_STREAM_PATH)
+    }
+}
+
+/// Where the texts to be localized are written out to
+enum Output {
+    Stdout(CsvDialect),
+    Files(FileProvider, Format),
+}
+
 struct FileProvider {
     count_of_files_created: usize,
-    sink_dir: String,
-    created_files: Vec<String>,
+    sink_dir: PathBuf,
+    options: OutputOptions,
+    module_name: String,
+    now: UtcTime,
+    created_files: Vec<PathBuf>,
 }
 
 impl FileProvider {
-    fn new(sink_dir: String) -> FileProvider {
+    fn new(sink_dir: PathBuf, options: OutputOptions, module_name: String) -> FileProvider {
         FileProvider {
             sink_dir,
+            options,
+            module_name,
+            now: UtcTime::now(),
             created_files: Vec::new(),
             count_of_files_created: 0,
         }
     }
 
-    fn into_created_files(self) -> Vec<String> {
+    fn into_created_files(self) -> Vec<PathBuf> {
         self.created_files
     }
 
-    /// Returns the created output file along with its path (if path computation
-    /// is possible; if not, it passes out a fallback value)
-    fn create_output_file(&mut self, output_file_name: &str) -> Result<(File, String), Error> {
-        let mut output_path = PathBuf::from(&self.sink_dir);
-        output_path.push(output_file_name);
-        output_path.set_extension(constants::extn::CSV);
-        let output_path_or_fb = String::from(output_path.to_str().unwrap_or(output_file_name));
+    fn build_output_file_name(&self, locales: &[&str], format: Format) -> String {
+        let template = match (&self.options.file_name_template, format) {
+            (Some(template), _) => template.as_str(),
+            (None, Format::Csv) if self.options.csv_layout == CsvLayout::PerLocale => {
+                DEFAULT_PER_LOCALE_CSV_FILE_NAME_TEMPLATE
+            }
+            (None, Format::Csv) => DEFAULT_CSV_FILE_NAME_TEMPLATE,
+            (None, Format::Json(_)) => DEFAULT_JSON_FILE_NAME_TEMPLATE,
+        };
+
+        template
+            .replace(constants::templates::LOCALES, &locales.join("_"))
+            .replace(constants::templates::DATE, &self.now.date())
+            .replace(constants::templates::MODULE, &self.module_name)
+            .replace(
+                constants::templates::INDEX,
+                &self.count_of_files_created.to_string(),
+            )
+    }
+
+    /// Writes out the contents as per the policy for existing files & records
+    /// the path of the file
+    fn write_output_file(
+        &mut self,
+        output_file_name: &str,
+        format: Format,
+        contents: Vec<u8>,
+    ) -> Result<(), Error> {
+        let mut output_path = build_output_path(&self.sink_dir, output_file_name, format);
+        if self.created_files.contains(&output_path) {
+            return Err(Error::new(output_path, ErrorKind::DuplicateOutputFile));
+        }
 
         if output_path.exists() {
-            Err(Error::new(output_path_or_fb, "Output file already exists!"))
-        } else {
-            match File::create(output_path) {
-                Ok(file) => {
-                    self.created_files.push(output_path_or_fb.clone());
-                    Ok((file, output_path_or_fb))
+            match self.options.existing_file_policy {
+                ExistingFilePolicy::Fail => {
+                    return Err(Error::new(output_path, ErrorKind::OutputFileExists))
                 }
 
-                Err(error) => Err(Error::new(output_path_or_fb, error)),
+                ExistingFilePolicy::Overwrite => {}
+                ExistingFilePolicy::Append if format == Format::Csv => {
+                    append_csv(&output_path, &contents, self.options.csv_dialect.encoding)
+                        .with_context(output_path.clone())?;
+                    self.created_files.push(output_path);
+                    return Ok(());
+                }
+
+                ExistingFilePolicy::Append => {
+                    return Err(Error::new(
+                        output_path,
+                        ErrorKind::UnsupportedFormat(String::from("Only CSVs can be appended to!")),
+                    ))
+                }
+
+                ExistingFilePolicy::Timestamp => {
+                    let timestamped_file_name =
+                        format!("{}_{}", output_file_name, self.now.timestamp());
+                    output_path = build_output_path(&self.sink_dir, &timestamped_file_name, format);
+                    if output_path.exists() {
+                        return Err(Error::new(output_path, ErrorKind::OutputFileExists));
+                    }
+                }
             }
         }
+
+        fs::write(&output_path, contents).with_context(output_path.clone())?;
+        self.created_files.push(output_path);
+        Ok(())
+    }
+}
+
+fn build_output_path(sink_dir: &Path, output_file_name: &str, format: Format) -> PathBuf {
+    let mut output_path = sink_dir.to_path_buf();
+    output_path.push(format!("{}.{}", output_file_name, format.extension()));
+    output_path
+}
+
+/// Rows are only appended if the existing file has the same header. Both the
+/// files are expected to be in the same encoding
+fn append_csv(
+    output_path: &Path,
+    contents: &[u8],
+    encoding: CsvEncoding,
+) -> Result<(), InnerError> {
+    let existing_contents = text_decoder::decode(fs::read(output_path)?)?;
+    let contents = text_decoder::decode(contents.to_vec())?;
+    let (header, rows) = match contents.find('\n') {
+        None => (contents.as_str(), ""),
+        Some(index) => (&contents[..index], &contents[index + 1..]),
+    };
+
+    if existing_contents.lines().next().unwrap_or("") != header {
+        return Err(ErrorKind::MismatchedCsvColumns.into());
+    }
+
+    let mut file = OpenOptions::new().append(true).open(output_path)?;
+    if !existing_contents.ends_with('\n') {
+        file.write_all(&encoding.encode("\n", false))?;
     }
+
+    file.write_all(&encoding.encode(rows, false))?;
+    Ok(())
+}
+
+/// Android modules keep their resources in `<module>/src/<source set>/res`. If
+/// the res dir isn't laid out that way, the name of its parent dir is used
+fn find_module_name(res_dir_path: &Path) -> String {
+    let res_dir_path = fs::canonicalize(res_dir_path).unwrap_or_else(|_| res_dir_path.into());
+    let dir_names: Vec<&str> = res_dir_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    let count = dir_names.len();
+    let module_name = if count >= 4 && dir_names[count - 3] == constants::fs::SRC_DIR_NAME {
+        dir_names[count - 4]
+    } else if count >= 2 {
+        dir_names[count - 2]
+    } else {
+        DEFAULT_MODULE_NAME
+    };
+
+    String::from(module_name)
 }
 
 impl csv_writer::SinkProvider for FileProvider {
     fn execute_with_new_sink(&mut self, writer: csv_writer::Writer) -> Result<(), Error> {
         self.count_of_files_created += 1;
-        let filename = format!("to_localize_{}", self.count_of_files_created);
-        let (mut sink, path) = self.create_output_file(&filename)?;
-        writer.write(&mut sink).with_context(path)
+        let file_name = self.build_output_file_name(&writer.to_locales(), Format::Csv);
+        let mut contents = vec![];
+        writer
+            .write(&mut contents)
+            .with_context(file_name.clone())?;
+        self.write_output_file(&file_name, Format::Csv, contents)
+    }
+}
+
+impl json_writer::SinkProvider for FileProvider {
+    fn execute_with_new_sink(&mut self, writer: json_writer::Writer) -> Result<(), Error> {
+        self.count_of_files_created += 1;
+        let format = writer.format();
+        let file_name = self.build_output_file_name(&[writer.to_locale()], format);
+        let mut contents = vec![];
+        writer
+            .write(&mut contents)
+            .with_context(file_name.clone())?;
+        self.write_output_file(&file_name, format, contents)
     }
 }
 
@@ -158,6 +562,15 @@ mod tests {
     use test_utilities;
 
     use crate::android_string::AndroidString;
+    use crate::error::Error;
+    use crate::format::{Format, JsonFlavor};
+    use crate::translation_memory;
+    use crate::translation_memory::Leverage;
+    use crate::util::utc_time::UtcTime;
+    use crate::util::xml_utilities;
+    use crate::writer::xml_writer;
+
+    use super::{CsvLayout, ExistingFilePolicy, FileProvider, Localization, OutputOptions};
 
     #[test]
     fn errors_for_empty_locale_id_to_name_map() {
@@ -166,8 +579,16 @@ mod tests {
         res_dir_path.push("res");
         fs::create_dir(res_dir_path.clone()).unwrap();
 
-        let error =
-            super::localize(res_dir_path.to_str().unwrap(), "", HashMap::new()).unwrap_err();
+        let error = super::localize(
+            res_dir_path.to_str().unwrap(),
+            "",
+            HashMap::new(),
+            Format::Csv,
+            None,
+            Default::default(),
+            false,
+        )
+        .unwrap_err();
         assert_eq!(
             error.context(),
             &String::from(res_dir_path.to_str().unwrap())
@@ -177,6 +598,30 @@ mod tests {
             .ends_with("Res dir doesn't have any non-default values dir with strings file!"))
     }
 
+    #[test]
+    fn errors_for_json_to_stdout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_dir_path = temp_dir.path().to_path_buf();
+        res_dir_path.push("res");
+        test_utilities::res::setup_empty_strings_for_locale(&res_dir_path, "fr");
+
+        let error = super::localize(
+            res_dir_path.to_str().unwrap(),
+            "-",
+            HashMap::new(),
+            Format::Json(JsonFlavor::Plain),
+            None,
+            Default::default(),
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.context(), "-");
+        assert!(error
+            .to_string()
+            .ends_with("Only CSVs can be written out to stdout!"))
+    }
+
     #[test]
     fn create_output_dir_if_required_errors_if_output_dir_is_a_file_instead() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -185,27 +630,28 @@ mod tests {
 
         // Output directory should instead be made a path
         File::create(&output_dir_path).unwrap();
-        let output_dir_path = output_dir_path.to_str().unwrap();
 
-        let error = super::create_output_dir_if_required(output_dir_path).unwrap_err();
+        let error = super::create_output_dir_if_required(&output_dir_path).unwrap_err();
         assert!(error
             .to_string()
             .ends_with("Output directory path points to a file!"));
-        assert_eq!(error.context(), &String::from(output_dir_path));
+        assert_eq!(error.context(), output_dir_path.as_path());
     }
 
     #[test]
-    fn create_output_file_errors_if_output_file_exists() {
+    fn write_output_file_errors_if_output_file_exists() {
         let temp_dir = tempfile::tempdir().unwrap();
         let output_dir_path = temp_dir.path();
         let mut output_file_path = output_dir_path.to_path_buf();
         output_file_path.push("op_file.csv");
 
-        File::create(&output_file_path.clone()).unwrap();
+        File::create(output_file_path.clone()).unwrap();
         let output_dir_path = output_dir_path.to_str().unwrap();
 
-        let mut file_provider = super::FileProvider::new(String::from(output_dir_path));
-        let error = file_provider.create_output_file("op_file").unwrap_err();
+        let mut file_provider = build_file_provider(output_dir_path, None, Default::default());
+        let error = file_provider
+            .write_output_file("op_file", Format::Csv, vec![])
+            .unwrap_err();
 
         assert!(error.to_string().ends_with("Output file already exists!"));
         assert_eq!(
@@ -214,6 +660,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_output_file_errors_if_file_name_is_repeated() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir_path = temp_dir.path().to_str().unwrap();
+        let mut file_provider =
+            build_file_provider(output_dir_path, None, ExistingFilePolicy::Overwrite);
+
+        file_provider
+            .write_output_file("op_file", Format::Csv, vec![])
+            .unwrap();
+        let error = file_provider
+            .write_output_file("op_file", Format::Csv, vec![])
+            .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .ends_with("File name template doesn't give a unique name for every file!"));
+    }
+
+    #[test]
+    fn write_output_file_overwrites_existing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir_path = temp_dir.path().to_str().unwrap();
+        let output_file_path = format!("{}/op_file.csv", output_dir_path);
+        test_utilities::file::write_content(&output_file_path, "old");
+
+        let mut file_provider =
+            build_file_provider(output_dir_path, None, ExistingFilePolicy::Overwrite);
+        file_provider
+            .write_output_file("op_file", Format::Csv, b"new".to_vec())
+            .unwrap();
+
+        assert_eq!(test_utilities::file::read_content(&output_file_path), "new");
+        assert_eq!(file_provider.into_created_files(), vec![output_file_path]);
+    }
+
+    #[test]
+    fn write_output_file_appends_rows_to_csv_with_same_columns() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir_path = temp_dir.path().to_str().unwrap();
+        let output_file_path = format!("{}/op_file.csv", output_dir_path);
+        test_utilities::file::write_content(
+            &output_file_path,
+            "string_name,default_locale,french\ns1,v1,",
+        );
+
+        let mut file_provider =
+            build_file_provider(output_dir_path, None, ExistingFilePolicy::Append);
+        file_provider
+            .write_output_file(
+                "op_file",
+                Format::Csv,
+                b"string_name,default_locale,french\ns2,v2,\n".to_vec(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            test_utilities::file::read_content(&output_file_path),
+            "string_name,default_locale,french\ns1,v1,\ns2,v2,\n"
+        );
+
+        let mut file_provider =
+            build_file_provider(output_dir_path, None, ExistingFilePolicy::Append);
+        let error = file_provider
+            .write_output_file(
+                "op_file",
+                Format::Csv,
+                b"string_name,default_locale,spanish\ns3,v3,\n".to_vec(),
+            )
+            .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .ends_with("Can't append to a file with different columns"));
+    }
+
+    #[test]
+    fn write_output_file_errors_when_appending_to_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir_path = temp_dir.path().to_str().unwrap();
+        test_utilities::file::write_content(format!("{}/op_file.arb", output_dir_path), "{}");
+
+        let mut file_provider =
+            build_file_provider(output_dir_path, None, ExistingFilePolicy::Append);
+        let error = file_provider
+            .write_output_file("op_file", Format::Json(JsonFlavor::Arb), vec![])
+            .unwrap_err();
+
+        assert!(error.to_string().ends_with("Only CSVs can be appended to!"));
+    }
+
+    #[test]
+    fn write_output_file_adds_timestamp_if_file_exists() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir_path = temp_dir.path().to_str().unwrap();
+        test_utilities::file::write_content(format!("{}/op_file.csv", output_dir_path), "old");
+
+        let mut file_provider =
+            build_file_provider(output_dir_path, None, ExistingFilePolicy::Timestamp);
+        file_provider.now = UtcTime::from_seconds_since_epoch(0);
+        file_provider
+            .write_output_file("op_file", Format::Csv, b"new".to_vec())
+            .unwrap();
+
+        let timestamped_file_path = format!("{}/op_file_19700101T000000.csv", output_dir_path);
+        assert_eq!(
+            test_utilities::file::read_content(&timestamped_file_path),
+            "new"
+        );
+        assert_eq!(
+            file_provider.into_created_files(),
+            vec![timestamped_file_path]
+        );
+    }
+
+    #[test]
+    fn build_output_file_name_fills_in_template() {
+        let mut file_provider = build_file_provider(
+            "output",
+            Some("{module}_{locales}_{date}_{index}"),
+            Default::default(),
+        );
+        file_provider.now = UtcTime::from_seconds_since_epoch(0);
+        file_provider.count_of_files_created = 2;
+
+        assert_eq!(
+            file_provider.build_output_file_name(&["french", "spanish"], Format::Csv),
+            "module_french_spanish_1970-01-01_2"
+        );
+    }
+
+    #[test]
+    fn build_output_file_name_defaults_by_format() {
+        let mut file_provider = build_file_provider("output", None, Default::default());
+        file_provider.count_of_files_created = 1;
+
+        assert_eq!(
+            file_provider.build_output_file_name(&["french"], Format::Csv),
+            "to_localize_1"
+        );
+        assert_eq!(
+            file_provider.build_output_file_name(&["french"], Format::Json(JsonFlavor::Plain)),
+            "to_localize_french"
+        );
+    }
+
+    #[test]
+    fn find_module_name_looks_for_src_dir() {
+        assert_eq!(
+            super::find_module_name(Path::new("project/feature/src/main/res")),
+            "feature"
+        );
+        assert_eq!(super::find_module_name(Path::new("project/res")), "project");
+        assert_eq!(super::find_module_name(Path::new("res")), "app");
+    }
+
     #[test]
     fn write_out_strings_to_localize_does_not_write_out_if_there_is_no_strings_to_localize() {
         let contents = r##"
@@ -227,10 +829,12 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let (file_paths, output_dir) = test_write_out_strings_to_localize(
             &temp_dir,
-            &contents,
-            &contents,
-            &contents,
+            contents,
+            contents,
+            contents,
             default_strings,
+            Format::Csv,
+            OutputOptions::default(),
         );
 
         test_utilities::list::assert_list_is_empty(file_paths);
@@ -253,10 +857,12 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let (file_paths, output_dir) = test_write_out_strings_to_localize(
             &temp_dir,
-            &contents,
-            &contents,
-            &contents,
+            contents,
+            contents,
+            contents,
             default_strings,
+            Format::Csv,
+            OutputOptions::default(),
         );
 
         assert_eq!(file_paths.len(), 1);
@@ -277,13 +883,293 @@ mod tests {
             "to_localize_1.csv"
         );
 
-        test_utilities::eq::assert_eq_to_either_or(
-            test_utilities::file::read_content(&Path::new(&file_paths.into_iter().next().unwrap())),
-            String::from("string_name,default_locale,spanish,french\nstring_1,string value,,\nstring_2,string value,,\n"),
-            String::from("string_name,default_locale,french,spanish\nstring_1,string value,,\nstring_2,string value,,\n")
+        assert_eq!(
+            test_utilities::file::read_content(Path::new(&file_paths.into_iter().next().unwrap())),
+            "string_name,default_locale,french,spanish\nstring_1,string value,,\nstring_2,string value,,\n"
+        );
+    }
+
+    #[test]
+    fn write_out_strings_to_localize_writes_out_a_file_per_locale_for_arb() {
+        let contents = r##"
+			<?xml version="1.0" encoding="utf-8"?>
+			<resources>
+			</resources>
+		"##;
+
+        let default_strings = vec![AndroidString::localizable("string_1", "string value")];
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (mut file_paths, _) = test_write_out_strings_to_localize(
+            &temp_dir,
+            contents,
+            contents,
+            contents,
+            default_strings,
+            Format::Json(JsonFlavor::Arb),
+            OutputOptions::default(),
+        );
+
+        file_paths.sort();
+        let file_names: Vec<String> = file_paths
+            .iter()
+            .map(|p| String::from(Path::new(p).file_name().unwrap().to_str().unwrap()))
+            .collect();
+
+        test_utilities::list::assert_strict_list_eq(
+            file_names,
+            vec!["to_localize_french.arb", "to_localize_spanish.arb"],
+        );
+
+        assert_eq!(
+            test_utilities::file::read_content(&file_paths[0]),
+            r#"{
+  "@@locale": "french",
+  "string_1": "string value",
+  "@string_1": {
+    "x-source": "string value"
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn write_out_strings_to_localize_honors_csv_layout() {
+        let contents = r##"
+			<?xml version="1.0" encoding="utf-8"?>
+			<resources>
+			    <string name="string_1">french value</string>
+			</resources>
+		"##;
+
+        let default_strings = vec![
+            AndroidString::localizable("string_1", "string value"),
+            AndroidString::localizable("string_2", "string value"),
+        ];
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (file_paths, _) = test_write_out_strings_to_localize(
+            &temp_dir,
+            contents,
+            "<resources></resources>",
+            contents,
+            default_strings.clone(),
+            Format::Csv,
+            OutputOptions {
+                csv_layout: CsvLayout::PerLocale,
+                ..Default::default()
+            },
+        );
+
+        let file_names: Vec<String> = file_paths
+            .iter()
+            .map(|p| String::from(Path::new(p).file_name().unwrap().to_str().unwrap()))
+            .collect();
+        test_utilities::list::assert_strict_list_eq(
+            file_names,
+            vec!["to_localize_french.csv", "to_localize_spanish.csv"],
+        );
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (file_paths, _) = test_write_out_strings_to_localize(
+            &temp_dir,
+            contents,
+            "<resources></resources>",
+            contents,
+            default_strings,
+            Format::Csv,
+            OutputOptions {
+                csv_layout: CsvLayout::Wide,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(file_paths.len(), 1);
+        assert_eq!(
+            test_utilities::file::read_content(&file_paths[0]),
+            "string_name,default_locale,french,spanish\nstring_1,string value,,\nstring_2,string value,,\n"
+        );
+    }
+
+    #[test]
+    fn write_out_strings_to_localize_includes_localized_strings() {
+        let contents = r##"
+			<?xml version="1.0" encoding="utf-8"?>
+			<resources>
+			    <string name="string_1">french value</string>
+			</resources>
+		"##;
+
+        let default_strings = vec![
+            AndroidString::localizable("string_1", "string value"),
+            AndroidString::localizable("string_2", "string value"),
+        ];
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (file_paths, _) = test_write_out_strings_to_localize(
+            &temp_dir,
+            contents,
+            contents,
+            contents,
+            default_strings,
+            Format::Csv,
+            OutputOptions {
+                include_localized: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(file_paths.len(), 1);
+        assert_eq!(
+            test_utilities::file::read_content(&file_paths[0]),
+            "string_name,default_locale,french,spanish\nstring_1,string value,french value,french value\nstring_2,string value,,\n"
+        );
+    }
+
+    #[test]
+    fn prefills_strings_from_translation_memory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (localization, output_dir_path, _) = test_localize_with_translation_memory(
+            &temp_dir,
+            translation_memory::Options {
+                extra_res_dir_paths: vec![],
+                auto_apply: false,
+            },
+        );
+
+        assert_eq!(
+            localization.leverage,
+            vec![Leverage {
+                locale: String::from("french"),
+                strings_count: 2,
+                words_count: 3,
+                matched_strings_count: 1,
+                matched_words_count: 1,
+            }]
+        );
+
+        test_utilities::list::assert_list_is_empty(localization.applied_files);
+        assert_eq!(
+            test_utilities::file::read_content(format!("{}/to_localize_1.csv", output_dir_path)),
+            "string_name,default_locale,french\nstring_2,Cancel,Annuler\nstring_3,Start over,\n"
+        );
+    }
+
+    #[test]
+    fn applies_strings_from_translation_memory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (localization, output_dir_path, res_path) = test_localize_with_translation_memory(
+            &temp_dir,
+            translation_memory::Options {
+                extra_res_dir_paths: vec![],
+                auto_apply: true,
+            },
+        );
+
+        assert_eq!(localization.applied_files.len(), 1);
+        assert_eq!(
+            test_utilities::file::read_content(format!("{}/to_localize_1.csv", output_dir_path)),
+            "string_name,default_locale,french\nstring_3,Start over,\n"
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            xml_utilities::read_foreign_strings(&res_path, "fr")
+                .unwrap()
+                .into_strings(),
+            vec![
+                AndroidString::localizable("string_1", "Annuler"),
+                AndroidString::localizable("string_2", "Annuler"),
+            ],
         );
     }
 
+    #[test]
+    fn does_not_apply_strings_from_translation_memory_if_writing_out_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut existing_file_path = temp_dir.path().to_path_buf();
+        existing_file_path.push("output");
+        fs::create_dir_all(&existing_file_path).unwrap();
+        existing_file_path.push("to_localize_1.csv");
+        test_utilities::file::write_content(&existing_file_path, "string_name\n");
+
+        let (result, _, res_path) = localize_with_translation_memory(
+            &temp_dir,
+            translation_memory::Options {
+                extra_res_dir_paths: vec![],
+                auto_apply: true,
+            },
+        );
+
+        assert_eq!(result.unwrap_err().kind().code(), "output_file_exists");
+        test_utilities::list::assert_strict_list_eq(
+            xml_utilities::read_foreign_strings(&res_path, "fr")
+                .unwrap()
+                .into_strings(),
+            vec![AndroidString::localizable("string_1", "Annuler")],
+        );
+    }
+
+    /// Returns the output of `localize` (only for french), the output dir path
+    /// & the res dir path (built by the test)
+    fn test_localize_with_translation_memory(
+        temp_dir: &TempDir,
+        options: translation_memory::Options,
+    ) -> (Localization, String, PathBuf) {
+        let (result, output_dir_path, res_path) =
+            localize_with_translation_memory(temp_dir, options);
+        (result.unwrap(), output_dir_path, res_path)
+    }
+
+    fn localize_with_translation_memory(
+        temp_dir: &TempDir,
+        options: translation_memory::Options,
+    ) -> (Result<Localization, Error>, String, PathBuf) {
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+        let mut output_dir_path = temp_dir.path().to_path_buf();
+        output_dir_path.push("output");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("string_1", "Cancel"),
+                AndroidString::localizable("string_2", "Cancel"),
+                AndroidString::localizable("string_3", "Start over"),
+            ],
+        )
+        .unwrap();
+
+        xml_writer::write(
+            &mut fr_strings.file,
+            vec![AndroidString::localizable("string_1", "Annuler")],
+        )
+        .unwrap();
+
+        let mut locale_id_to_name_map = HashMap::new();
+        locale_id_to_name_map.insert(String::from("fr"), String::from("french"));
+
+        let localization = super::localize(
+            res_path.to_str().unwrap(),
+            output_dir_path.to_str().unwrap(),
+            locale_id_to_name_map,
+            Format::Csv,
+            Some(options),
+            Default::default(),
+            false,
+        );
+
+        (
+            localization,
+            String::from(output_dir_path.to_str().unwrap()),
+            res_path,
+        )
+    }
+
     /// Returns the output of the method call to `write_out_strings_to_localize`
     /// & the output dir path (built by the test)
     fn test_write_out_strings_to_localize(
@@ -292,7 +1178,9 @@ mod tests {
         spanish_values_file_content: &str,
         unmapped_german_values_file_content: &str,
         mut default_strings: Vec<AndroidString>,
-    ) -> (Vec<String>, PathBuf) {
+        format: Format,
+        output_options: OutputOptions,
+    ) -> (Vec<PathBuf>, PathBuf) {
         // Build paths
         let mut res_path = temp_dir.path().to_path_buf();
         res_path.push("res");
@@ -328,14 +1216,41 @@ mod tests {
         locale_id_to_name_map.insert(String::from("es"), String::from("spanish"));
 
         // Perform action
+        let include_localized = output_options.include_localized;
         let result = super::write_out_strings_to_localize(
             &res_path,
-            output_dir_path.to_str().unwrap(),
+            super::Output::Files(
+                FileProvider::new(
+                    output_dir_path.clone(),
+                    output_options,
+                    String::from("module"),
+                ),
+                format,
+            ),
             locale_id_to_name_map,
             &mut default_strings,
+            None,
+            false,
+            include_localized,
         )
         .unwrap();
 
-        (result, output_dir_path)
+        (result.created_files, output_dir_path)
+    }
+
+    fn build_file_provider(
+        output_dir_path: &str,
+        file_name_template: Option<&str>,
+        existing_file_policy: ExistingFilePolicy,
+    ) -> FileProvider {
+        FileProvider::new(
+            PathBuf::from(output_dir_path),
+            OutputOptions {
+                file_name_template: file_name_template.map(String::from),
+                existing_file_policy,
+                ..Default::default()
+            },
+            String::from("module"),
+        )
     }
 }
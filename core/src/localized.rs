@@ -1,92 +1,223 @@
-use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::hash::{Hash, Hasher};
-use std::ops::Add;
-use std::path::Path;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use crate::android_string::AndroidString;
 use crate::constants;
-use crate::error::{Error, ResultExt};
+use crate::error::{CsvDiagnostic, Error, ErrorKind, InnerError, MalformedText, ResultExt};
+use crate::format::{CsvDialect, Format, StringMetadata};
+use crate::localized_strings::LocalizedStrings;
 use crate::ops::dedup;
 use crate::ops::extract;
 use crate::ops::filter;
 use crate::ops::merge;
 use crate::reader::csv_reader;
+use crate::reader::csv_reader::CsvContents;
+use crate::reader::json_reader;
 use crate::util::foreign_locale_ids_finder;
+use crate::util::staged_files::StagedFiles;
 use crate::util::xml_utilities;
+use crate::validate::duplicate_names;
 use crate::writer::xml_writer;
 
-/// Returns the list of output files updated by this call. These aren't guaranteed
-/// to be valid paths to files. Sometimes, if a file's path can't be expressed by
-/// `String` (in case it has non UTF-8 chars), it could just be the file's name
-pub fn localized<S: ::std::hash::BuildHasher>(
-    res_dir_path: &str,
-    localized_text_file_path: &str,
+pub use crate::ops::extract::{FuzzyMatch, MatchPolicy};
+
+/// Files updated by `localized` along with the localized texts that were used
+/// even though the default text has changed since they were sent off, the
+/// localized texts that were skipped, the values of the metadata columns (notes,
+/// status, etc.) & the columns of the localized CSV that were skipped
+#[derive(Debug, Default, PartialEq)]
+pub struct LocalizedTexts {
+    pub updated_files: Vec<PathBuf>,
+    pub needing_review: Vec<NeedsReview>,
+    pub skipped: Vec<SkippedText>,
+    pub metadata: Vec<StringMetadata>,
+    pub warnings: Vec<CsvDiagnostic>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NeedsReview {
+    pub locale_id: String,
+    pub fuzzy_match: FuzzyMatch,
+}
+
+/// A text from a JSON or ARB document that is still the default text it was
+/// pre-filled with. It can't be told apart from a text that wasn't localized
+/// (even if it is meant to be the same, like `OK` or a brand name), so it isn't
+/// used. Only texts that aren't localized yet are listed out
+#[derive(Debug, PartialEq)]
+pub struct SkippedText {
+    pub locale_id: String,
+    pub name: String,
+}
+
+/// Localized texts are only used if the default text they were localized from
+/// matches the current default text as per the match policy.
+///
+/// The format of the localized text file is figured out from its extension (`.json`
+/// & `.arb` are read as JSON documents). Files with any other extension are read as CSVs.
+/// If the file path is `-`, a CSV is read from stdin. CSVs are read as per the dialect.
+///
+/// Errors with `ErrorKind::DuplicateKey` if a name is defined more than once in
+/// the values dir of the default locale or of any of the foreign locales, unless
+/// duplicate names are allowed (only the first definition is used then).
+///
+/// Errors with `ErrorKind::MalformedTexts` before touching any of the strings
+/// files if localized texts aren't well formed XML (like with a stray `<` or
+/// `&`), unless malformed texts are to be escaped (they are written out as plain
+/// text then).
+///
+/// Strings files are only replaced once all the locales are written out. If
+/// any of them fails, none of the strings files are touched
+pub fn localized<R: AsRef<Path>, L: AsRef<Path>, S: ::std::hash::BuildHasher>(
+    res_dir_path: R,
+    localized_text_file_path: L,
     locale_name_to_id_map: HashMap<String, String, S>,
-) -> Result<Vec<String>, Error> {
+    match_policy: MatchPolicy,
+    csv_dialect: CsvDialect,
+    allow_duplicate_names: bool,
+    escape_malformed_texts: bool,
+) -> Result<LocalizedTexts, Error> {
+    let res_dir_path = res_dir_path.as_ref();
     let locale_name_to_id_map = foreign_locale_ids_finder::build_map_if_empty_or_return(
         locale_name_to_id_map,
         res_dir_path,
     )?;
 
     if locale_name_to_id_map.is_empty() {
-        return Err(Error::new(
+        return Err(Error::new(res_dir_path, ErrorKind::NoForeignStringsFiles));
+    }
+
+    if !allow_duplicate_names {
+        duplicate_names::ensure_unique_names(
             res_dir_path,
-            "Res dir doesn't have any non-default values dir with strings file!",
-        ));
+            locale_name_to_id_map.values().map(String::as_str),
+        )?;
     }
 
     // Read default strings
-    let res_dir_path = Path::new(res_dir_path);
-    let mut localizable_default_strings = filter::find_localizable_strings(
+    let mut localizable_default_strings = filter::find_strings_to_localize(
         xml_utilities::read_default_strings(res_dir_path)?.into_strings(),
     );
 
     // For all languages, handle localized text
     handle_localized(
         res_dir_path,
-        localized_text_file_path,
+        localized_text_file_path.as_ref(),
         locale_name_to_id_map,
         &mut localizable_default_strings,
+        match_policy,
+        &csv_dialect,
+        escape_malformed_texts,
     )
 }
 
 fn handle_localized<S: ::std::hash::BuildHasher>(
     res_dir_path: &Path,
-    localized_text_file_path: &str,
+    localized_text_file_path: &Path,
     locale_name_to_id_map: HashMap<String, String, S>,
     localizable_default_strings: &mut [AndroidString],
-) -> Result<Vec<String>, Error> {
+    match_policy: MatchPolicy,
+    csv_dialect: &CsvDialect,
+    escape_malformed_texts: bool,
+) -> Result<LocalizedTexts, Error> {
     // Read all new localized strings
-    let new_localized_foreign_strings_list = csv_reader::read(
-        File::open(localized_text_file_path)
-            .with_context(String::from(localized_text_file_path))?,
+    let contents = read_localized_strings(
+        localized_text_file_path,
         locale_name_to_id_map
             .keys()
             .map(|s: &String| String::clone(s))
             .collect(),
+        csv_dialect,
+    )?;
+
+    // Nothing is written out if any of the texts can't be
+    let strings_list = check_localized_texts(
+        contents.strings_list,
+        &locale_name_to_id_map,
+        escape_malformed_texts,
     )
     .with_context(localized_text_file_path)?;
 
-    let mut updated_files_paths = vec![];
-    for new_localized_foreign_strings in new_localized_foreign_strings_list {
+    let mut localized_texts = LocalizedTexts {
+        metadata: contents.metadata,
+        warnings: contents.warnings,
+        ..Default::default()
+    };
+
+    // Files are only replaced once all the locales are written out. Staged
+    // files are cleaned up if any of the locales fails
+    let mut staged_files = StagedFiles::new();
+    for new_localized_foreign_strings in strings_list {
         let locale_id = locale_name_to_id_map
             .get(new_localized_foreign_strings.locale())
             .expect("Read locale doesn't have a mapping! Please let the dev know about this issue");
 
-        let existing_foreign_strings =
-            xml_utilities::read_foreign_strings(res_dir_path, locale_id)?.into_strings();
-        let existing_foreign_strings_hash = compute_hash_of(&existing_foreign_strings);
+        // Plurals aren't localized through this flow but they must be written back
+        let (existing_foreign_strings, existing_foreign_plurals) =
+            xml_utilities::read_foreign_strings(res_dir_path, locale_id)?
+                .into_strings_and_plurals();
+        let output_file_path = xml_utilities::foreign_strings_file_path(res_dir_path, locale_id);
+
+        // What the file would be written out as if nothing changed. Comparing
+        // against this (rather than the file's content) keeps hand formatted
+        // files untouched too
+        let existing_content = xml_writer::serialize(
+            existing_foreign_strings.clone(),
+            existing_foreign_plurals.clone(),
+        )
+        .with_context(output_file_path.clone())?;
 
         // Read already localized foreign strings for locale
         let mut already_localized_foreign_strings =
             filter::find_localizable_strings(existing_foreign_strings);
 
         // Extract android strings out of the newly localized strings
-        let mut new_localized_foreign_strings = extract::extract_android_strings_from_localized(
+        let extraction = extract::extract_android_strings_from_localized(
             &mut new_localized_foreign_strings.into_strings(),
             localizable_default_strings,
+            match_policy,
+        );
+
+        localized_texts.skipped.extend(
+            extraction
+                .identical_to_default
+                .into_iter()
+                .filter(|name| {
+                    !already_localized_foreign_strings
+                        .iter()
+                        .any(|s| s.name() == name)
+                })
+                .map(|name| SkippedText {
+                    locale_id: locale_id.clone(),
+                    name,
+                }),
+        );
+
+        // Only the changed texts are applied. When already localized texts are
+        // sent off for review, most of them would come back unchanged
+        let mut new_localized_foreign_strings =
+            filter::find_changed_strings(extraction.strings, &already_localized_foreign_strings);
+
+        if new_localized_foreign_strings.is_empty() {
+            continue;
+        }
+
+        localized_texts.needing_review.extend(
+            extraction
+                .fuzzy_matches
+                .into_iter()
+                .filter(|fuzzy_match| {
+                    new_localized_foreign_strings
+                        .iter()
+                        .any(|s| s.name() == fuzzy_match.name)
+                })
+                .map(|fuzzy_match| NeedsReview {
+                    locale_id: locale_id.clone(),
+                    fuzzy_match,
+                }),
         );
 
         // Merge already existing & newly localized strings
@@ -98,65 +229,148 @@ fn handle_localized<S: ::std::hash::BuildHasher>(
         // There could be duplicates!
         dedup::dedup_grouped_strings(&mut to_be_written_foreign_strings);
 
-        let new_foreign_strings_hash = compute_hash_of(&to_be_written_foreign_strings);
+        let new_content =
+            xml_writer::serialize(to_be_written_foreign_strings, existing_foreign_plurals)
+                .with_context(output_file_path.clone())?;
 
-        // Write out foreign strings back to file
-        let (mut file, output_file_path) =
-            writable_empty_foreign_strings_file(res_dir_path, locale_id)?;
-        xml_writer::write(&mut file, to_be_written_foreign_strings)
+        // If the file's content isn't getting updated, it is left alone (so that
+        // its modified time doesn't change) & isn't included in the updated files
+        if new_content == existing_content {
+            continue;
+        }
+
+        // Stage foreign strings to be written back to file
+        let mut file = staged_files.stage(output_file_path.clone())?;
+        file.write_all(&new_content)
             .with_context(output_file_path.clone())?;
+        localized_texts.updated_files.push(output_file_path);
+    }
+
+    staged_files.commit()?;
+    Ok(localized_texts)
+}
 
-        // If the file's content isn't getting updated, needn't include it in the
-        // updated files list
-        if new_foreign_strings_hash != existing_foreign_strings_hash {
-            updated_files_paths.push(output_file_path);
+/// Malformed texts are either escaped or all of them are listed out in the error
+fn check_localized_texts<S: ::std::hash::BuildHasher>(
+    strings_list: Vec<LocalizedStrings>,
+    locale_name_to_id_map: &HashMap<String, String, S>,
+    escape_malformed_texts: bool,
+) -> Result<Vec<LocalizedStrings>, InnerError> {
+    let mut malformed_texts = vec![];
+    let mut checked_strings_list = vec![];
+    for localized_strings in strings_list {
+        let locale = String::from(localized_strings.locale());
+        let mut checked_strings = vec![];
+        for localized_string in localized_strings.into_strings() {
+            match xml_writer::check_value(localized_string.localized()) {
+                Ok(()) => checked_strings.push(localized_string),
+                Err(_) if escape_malformed_texts => {
+                    let escaped_value = xml_writer::escape_value(localized_string.localized());
+                    checked_strings.push(localized_string.with_localized(escaped_value))
+                }
+                Err(problem) => malformed_texts.push(MalformedText {
+                    locale_id: locale_name_to_id_map
+                        .get(&locale)
+                        .cloned()
+                        .unwrap_or_else(|| locale.clone()),
+                    name: String::from(localized_string.name()),
+                    problem,
+                }),
+            }
         }
+
+        checked_strings_list.push(LocalizedStrings::new(locale, checked_strings));
     }
 
-    Ok(updated_files_paths)
+    if malformed_texts.is_empty() {
+        Ok(checked_strings_list)
+    } else {
+        Err(InnerError::from(ErrorKind::MalformedTexts(malformed_texts)))
+    }
 }
 
-/// Returns the created output file along with its path (if path computation
-/// is possible; if not, it passes out a fallback value)
-fn writable_empty_foreign_strings_file(
-    res_dir_path: &Path,
-    locale_id: &str,
-) -> Result<(File, String), Error> {
-    let values_dir_name = String::from(constants::fs::BASE_VALUES_DIR_NAME);
-    let values_dir_name = values_dir_name.add(&format!("-{}", locale_id));
-
-    let mut strings_file_path = res_dir_path.to_path_buf();
-    strings_file_path.push(values_dir_name);
-    strings_file_path.push(constants::fs::STRING_FILE_NAME);
-    let output_path_or_fb = String::from(strings_file_path.to_str().unwrap_or(locale_id));
-
-    // empties out the file if it has any content
-    Ok((
-        File::create(strings_file_path).with_context(output_path_or_fb.clone())?,
-        output_path_or_fb,
-    ))
+/// Metadata & warnings are only ever returned for CSVs
+fn read_localized_strings(
+    localized_text_file_path: &Path,
+    allow_only_locales: HashSet<String>,
+    csv_dialect: &CsvDialect,
+) -> Result<CsvContents, Error> {
+    if localized_text_file_path == Path::new(constants::fs::STD_STREAM_PATH) {
+        return csv_reader::read(io::stdin(), allow_only_locales, csv_dialect)
+            .with_context(localized_text_file_path);
+    }
+
+    let file = File::open(localized_text_file_path).with_context(localized_text_file_path)?;
+
+    let result: Result<CsvContents, InnerError> = match Format::from_path(localized_text_file_path)
+    {
+        None | Some(Format::Csv) => csv_reader::read(file, allow_only_locales, csv_dialect),
+        Some(Format::Json(_)) => {
+            // Plain JSON documents don't carry the locale. Fallback to file's name
+            let file_stem = localized_text_file_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("");
+            let fallback_locale = find_locale_in_file_stem(file_stem, &allow_only_locales)
+                .map(String::from)
+                .unwrap_or_else(|| String::from(file_stem));
+
+            json_reader::read(file, &fallback_locale, allow_only_locales).map(
+                |localized_strings_list| CsvContents {
+                    strings_list: localized_strings_list,
+                    metadata: vec![],
+                    warnings: vec![],
+                },
+            )
+        }
+    };
+
+    result.with_context(localized_text_file_path)
 }
 
-fn compute_hash_of(strings: &[AndroidString]) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    strings.hash(&mut hasher);
-    hasher.finish()
+/// File names are filled in from templates (`to_localize_{locales}` by default) &
+/// so, the locale is looked for as a whole `_` (or `-` or `.`) separated part of
+/// the name. The longest of the matching locales is picked
+fn find_locale_in_file_stem<'a>(
+    file_stem: &str,
+    allow_only_locales: &'a HashSet<String>,
+) -> Option<&'a str> {
+    allow_only_locales
+        .iter()
+        .filter(|locale| {
+            file_stem.match_indices(locale.as_str()).any(|(index, _)| {
+                let is_separator = |c: char| c == '_' || c == '-' || c == '.';
+                let end_index = index + locale.len();
+                file_stem[..index].chars().last().is_none_or(is_separator)
+                    && file_stem[end_index..]
+                        .chars()
+                        .next()
+                        .is_none_or(is_separator)
+            })
+        })
+        .max_by_key(|locale| locale.len())
+        .map(String::as_str)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::fs;
     use std::fs::File;
-    use std::io::Read;
     use std::io::Write;
 
     use test_utilities;
 
     use crate::android_string::AndroidString;
+    use crate::error::ErrorKind;
+    use crate::format::CsvDialect;
+    use crate::localized_string::LocalizedString;
+    use crate::localized_strings::LocalizedStrings;
     use crate::util::xml_utilities;
     use crate::writer::xml_writer;
 
+    use super::{FuzzyMatch, MatchPolicy, NeedsReview, SkippedText};
+
     #[test]
     fn errors_for_empty_locale_name_to_id_map() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -164,8 +378,16 @@ mod tests {
         res_dir_path.push("res");
         fs::create_dir(res_dir_path.clone()).unwrap();
 
-        let error =
-            super::localized(res_dir_path.to_str().unwrap(), "", HashMap::new()).unwrap_err();
+        let error = super::localized(
+            res_dir_path.to_str().unwrap(),
+            "",
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap_err();
         assert_eq!(
             error.context(),
             &String::from(res_dir_path.to_str().unwrap())
@@ -175,6 +397,267 @@ mod tests {
             .ends_with("Res dir doesn't have any non-default values dir with strings file!"))
     }
 
+    #[test]
+    fn refuses_duplicate_names_unless_allowed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "english value 1"),
+                AndroidString::localizable("s1", "english value 2"),
+            ],
+        )
+        .unwrap();
+        xml_writer::write(&mut fr_strings.file, vec![]).unwrap();
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("localized.csv");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            "string_name, default_locale, fr\ns1, english value 1, french value 1",
+        );
+
+        let error = super::localized(
+            &res_path,
+            &localized_file_path,
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(error.kind.code(), "duplicate_key");
+        assert_eq!(error.context, default_strings.path.parent().unwrap());
+
+        let localized_texts = super::localized(
+            &res_path,
+            &localized_file_path,
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(localized_texts.updated_files, vec![fr_strings.path]);
+    }
+
+    #[test]
+    fn refuses_malformed_texts_unless_escaped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+        let mut es_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "es");
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "Tom and Jerry"),
+                AndroidString::localizable("s2", "Save"),
+            ],
+        )
+        .unwrap();
+        xml_writer::write(
+            &mut fr_strings.file,
+            vec![AndroidString::localizable("s2", "Sauver")],
+        )
+        .unwrap();
+        xml_writer::write(&mut es_strings.file, vec![]).unwrap();
+        let fr_content = test_utilities::file::read_content(&fr_strings.path);
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("localized.csv");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            "string_name,default_locale,es,fr\ns1,Tom and Jerry,Tom y <b>Jerry</b>,Tom & Jerry\ns2,Save,Guardar <,Enregistrer",
+        );
+
+        let error = super::localized(
+            &res_path,
+            &localized_file_path,
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.context, localized_file_path);
+        match error.kind {
+            ErrorKind::MalformedTexts(malformed_texts) => assert_eq!(
+                malformed_texts
+                    .iter()
+                    .map(|malformed_text| (
+                        malformed_text.locale_id.as_str(),
+                        malformed_text.name.as_str()
+                    ))
+                    .collect::<Vec<(&str, &str)>>(),
+                vec![("es", "s1"), ("es", "s2"), ("fr", "s1")]
+            ),
+            error_kind => panic!("Expected malformed texts error. Received: {:?}", error_kind),
+        }
+
+        // Nothing should have been touched
+        assert_eq!(
+            test_utilities::file::read_content(&fr_strings.path),
+            fr_content
+        );
+
+        super::localized(
+            &res_path,
+            &localized_file_path,
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            true,
+        )
+        .unwrap();
+
+        assert!(test_utilities::file::read_content(&fr_strings.path)
+            .contains(r#"<string name="s1">Tom &amp; Jerry</string>"#));
+        assert!(test_utilities::file::read_content(&es_strings.path)
+            .contains(r#"<string name="s1">Tom y &lt;b>Jerry&lt;/b></string>"#));
+        assert!(test_utilities::file::read_content(&es_strings.path)
+            .contains(r#"<string name="s2">Guardar &lt;</string>"#));
+    }
+
+    #[test]
+    fn leaves_strings_files_untouched_if_any_locale_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+        let es_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "es");
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "Save")],
+        )
+        .unwrap();
+        test_utilities::file::write_content(
+            &fr_strings.path,
+            r#"<resources><string name="s1">Sauver</string></resources>"#,
+        );
+
+        // Entities are read back unescaped & so, the plurals can't be written back
+        test_utilities::file::write_content(
+            &es_strings.path,
+            r#"<resources><plurals name="p1"><item quantity="other">Tom &amp; Jerry</item></plurals></resources>"#,
+        );
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("localized.csv");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            "string_name,default_locale,fr,es\ns1,Save,Enregistrer,Guardar",
+        );
+
+        let error = super::localized(
+            &res_path,
+            &localized_file_path,
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.context, es_strings.path);
+        assert_eq!(
+            test_utilities::file::read_content(&fr_strings.path),
+            r#"<resources><string name="s1">Sauver</string></resources>"#
+        );
+        assert_eq!(
+            test_utilities::file::read_content(&es_strings.path),
+            r#"<resources><plurals name="p1"><item quantity="other">Tom &amp; Jerry</item></plurals></resources>"#
+        );
+        for values_dir_path in &[&fr_strings.path, &es_strings.path] {
+            assert_eq!(
+                fs::read_dir(values_dir_path.parent().unwrap())
+                    .unwrap()
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn leaves_unchanged_strings_files_untouched() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+        let mut es_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "es");
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "Save…")],
+        )
+        .unwrap();
+        xml_writer::write(&mut es_strings.file, vec![]).unwrap();
+
+        // Hand formatted & the same as the localized text once it is written out
+        let fr_content = "<resources>\n  <string name=\"s1\">Enregistrer…</string>\n</resources>";
+        test_utilities::file::write_content(&fr_strings.path, fr_content);
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("localized.csv");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            "string_name,default_locale,fr,es\ns1,Save…,Enregistrer&#8230;,Guardar…",
+        );
+
+        let localized_texts = super::localized(
+            &res_path,
+            &localized_file_path,
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(localized_texts.updated_files, vec![es_strings.path.clone()]);
+        assert_eq!(
+            test_utilities::file::read_content(&fr_strings.path),
+            fr_content
+        );
+        assert_eq!(
+            xml_utilities::read_foreign_strings(&res_path, "es")
+                .unwrap()
+                .into_strings(),
+            vec![AndroidString::localizable("s1", "Guardar…")]
+        );
+    }
+
     #[test]
     fn updates_strings_files() {
         // Build paths
@@ -257,18 +740,23 @@ s2, english value 2,,spanish new value 2,german new value 2,            ",
         map.insert(String::from("chinese"), String::from("zh"));
 
         // Perform action
-        let created_output_files_path = super::localized(
+        let localized_texts = super::localized(
             res_path.clone().to_str().unwrap(),
             localized_file_path.to_str().unwrap(),
             map,
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
         )
         .unwrap();
 
         // Assert appropriate output
         test_utilities::list::assert_strict_list_eq(
-            created_output_files_path,
+            localized_texts.updated_files,
             vec![fr_strings.path, es_strings.path],
         );
+        test_utilities::list::assert_list_is_empty(localized_texts.needing_review);
 
         test_utilities::list::assert_strict_list_eq(
             xml_utilities::read_foreign_strings(&res_path, "fr")
@@ -309,25 +797,254 @@ s2, english value 2,,spanish new value 2,german new value 2,            ",
     }
 
     #[test]
-    fn writable_empty_foreign_strings_file_creates_file() {
-        let res_path = tempfile::tempdir().unwrap();
+    fn does_not_rewrite_strings_files_without_changed_texts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
 
-        let mut fr_strings = test_utilities::res::setup_empty_strings_for_locale(&res_path, "fr");
-        fr_strings.file.write_all(b"example old content").unwrap();
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "Save")],
+        )
+        .unwrap();
 
-        let (mut file_with_new_content, file_path) =
-            super::writable_empty_foreign_strings_file(res_path.path(), "fr").unwrap();
-        file_with_new_content
-            .write_all(b"example new content")
-            .unwrap();
+        let fr_strings_content = r#"<resources>
+    <!-- Reviewed -->
+    <string name="s1">Enregistrer</string>
+</resources>"#;
+        test_utilities::file::write_content(&fr_strings.path, fr_strings_content);
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("localized.csv");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            "string_name,default_locale,fr\ns1,Save,Enregistrer",
+        );
+
+        let localized_texts = super::localized(
+            res_path.to_str().unwrap(),
+            localized_file_path.to_str().unwrap(),
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        test_utilities::list::assert_list_is_empty(localized_texts.updated_files);
+        assert_eq!(
+            test_utilities::file::read_content(&fr_strings.path),
+            fr_strings_content
+        );
+    }
+
+    #[test]
+    fn updates_strings_files_with_fuzzy_matches_needing_review() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write(&mut fr_strings.file, vec![]).unwrap();
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "Save changes."),
+                AndroidString::localizable("s2", "Delete all"),
+            ],
+        )
+        .unwrap();
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("localized.csv");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            "string_name,default_locale,fr\ns1,Save changes,Enregistrer\ns2,Delete,Supprimer",
+        );
+
+        let localized_texts = super::localized(
+            res_path.to_str().unwrap(),
+            localized_file_path.to_str().unwrap(),
+            HashMap::new(),
+            MatchPolicy::IgnoreWhitespaceAndPunctuation,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(localized_texts.updated_files, vec![fr_strings.path]);
+        assert_eq!(
+            localized_texts.needing_review,
+            vec![NeedsReview {
+                locale_id: String::from("fr"),
+                fuzzy_match: FuzzyMatch {
+                    name: String::from("s1"),
+                    localized_default: String::from("Save changes"),
+                    current_default: String::from("Save changes."),
+                },
+            }]
+        );
 
-        let mut file_contents = String::new();
-        File::open(&fr_strings.path)
+        test_utilities::list::assert_strict_list_eq(
+            xml_utilities::read_foreign_strings(&res_path, "fr")
+                .unwrap()
+                .into_strings(),
+            vec![AndroidString::localizable("s1", "Enregistrer")],
+        );
+    }
+
+    #[test]
+    fn reports_prefilled_texts_still_the_default_text_as_skipped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+
+        xml_writer::write(&mut fr_strings.file, vec![]).unwrap();
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "OK"),
+                AndroidString::localizable("s2", "Delete"),
+            ],
+        )
+        .unwrap();
+
+        let mut localized_file_path = temp_dir.path().to_path_buf();
+        localized_file_path.push("to_localize_fr.json");
+        test_utilities::file::write_content(
+            &localized_file_path,
+            r#"{"s1": "OK", "s2": "Supprimer"}"#,
+        );
+
+        let localized_texts = super::localized(
+            res_path.to_str().unwrap(),
+            localized_file_path.to_str().unwrap(),
+            HashMap::new(),
+            MatchPolicy::Exact,
+            CsvDialect::default(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            localized_texts.skipped,
+            vec![SkippedText {
+                locale_id: String::from("fr"),
+                name: String::from("s1"),
+            }]
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            xml_utilities::read_foreign_strings(&res_path, "fr")
+                .unwrap()
+                .into_strings(),
+            vec![AndroidString::localizable("s2", "Supprimer")],
+        );
+    }
+
+    #[test]
+    fn finds_locale_in_file_stem() {
+        let locales: HashSet<String> = vec!["fr", "fr_CA", "es"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(super::find_locale_in_file_stem("fr", &locales), Some("fr"));
+        assert_eq!(
+            super::find_locale_in_file_stem("to_localize_fr", &locales),
+            Some("fr")
+        );
+        assert_eq!(
+            super::find_locale_in_file_stem("app_fr_CA_2020-01-01_1", &locales),
+            Some("fr_CA")
+        );
+        assert_eq!(
+            super::find_locale_in_file_stem("to_localize_french", &locales),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_localized_strings_based_on_file_extension() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut arb_file_path = temp_dir.path().to_path_buf();
+        arb_file_path.push("some_name.arb");
+        test_utilities::file::write_content(
+            &arb_file_path,
+            r#"{"@@locale": "french", "s1": "french 1", "@s1": {"x-source": "english 1"}}"#,
+        );
+
+        let mut json_file_path = temp_dir.path().to_path_buf();
+        json_file_path.push("to_localize_french.json");
+        test_utilities::file::write_content(&json_file_path, r#"{"s1": "french 1"}"#);
+
+        let mut csv_file_path = temp_dir.path().to_path_buf();
+        csv_file_path.push("some_name.txt");
+        test_utilities::file::write_content(
+            &csv_file_path,
+            "string_name,default_locale,french\ns1,english 1,french 1",
+        );
+
+        let allow_only_locales: HashSet<String> =
+            vec![String::from("french")].into_iter().collect();
+
+        test_utilities::list::assert_strict_list_eq(
+            super::read_localized_strings(
+                &arb_file_path,
+                allow_only_locales.clone(),
+                &CsvDialect::default(),
+            )
             .unwrap()
-            .read_to_string(&mut file_contents)
-            .unwrap();
+            .strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::build("s1", "english 1", "french 1").prefilled()],
+            )],
+        );
 
-        assert_eq!(file_contents, "example new content");
-        assert_eq!(file_path, fr_strings.path)
+        test_utilities::list::assert_strict_list_eq(
+            super::read_localized_strings(
+                &json_file_path,
+                allow_only_locales.clone(),
+                &CsvDialect::default(),
+            )
+            .unwrap()
+            .strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::without_default("s1", "french 1").prefilled()],
+            )],
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            super::read_localized_strings(
+                &csv_file_path,
+                allow_only_locales,
+                &CsvDialect::default(),
+            )
+            .unwrap()
+            .strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::build("s1", "english 1", "french 1")],
+            )],
+        );
     }
 }
@@ -1,30 +1,51 @@
 #[derive(Clone, Debug, PartialEq)]
 pub struct LocalizedString {
     name: String,
-    default: String,
+    default: Option<String>,
     localized: String,
+    is_prefilled: bool,
 }
 
 impl LocalizedString {
-    pub fn new(name: String, default: String, localized: String) -> LocalizedString {
+    /// `default` could be `None` if the format the localized text was read from
+    /// doesn't carry the default text it was localized from (eg., plain JSON)
+    pub fn new(name: String, default: Option<String>, localized: String) -> LocalizedString {
         LocalizedString {
             name,
             default,
             localized,
+            is_prefilled: false,
         }
     }
 
+    /// Marks the text as read from a document that was written out pre-filled
+    /// with the default texts (like JSON & ARB documents). Such a text that is
+    /// still the default text can't be told apart from one that wasn't localized
+    pub fn prefilled(mut self) -> LocalizedString {
+        self.is_prefilled = true;
+        self
+    }
+
+    pub fn with_localized(mut self, localized: String) -> LocalizedString {
+        self.localized = localized;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
-    pub fn default(&self) -> &str {
-        &self.default
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
     }
 
     pub fn localized(&self) -> &str {
         &self.localized
     }
+
+    pub fn is_prefilled(&self) -> bool {
+        self.is_prefilled
+    }
 }
 
 #[cfg(test)]
@@ -38,7 +59,14 @@ mod tests {
             default: D,
             localized: L,
         ) -> LocalizedString {
-            LocalizedString::new(name.into(), default.into(), localized.into())
+            LocalizedString::new(name.into(), Some(default.into()), localized.into())
+        }
+
+        pub fn without_default<N: Into<String>, L: Into<String>>(
+            name: N,
+            localized: L,
+        ) -> LocalizedString {
+            LocalizedString::new(name.into(), None, localized.into())
         }
     }
 }
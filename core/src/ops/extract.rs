@@ -1,27 +1,100 @@
 use crate::android_string::AndroidString;
 use crate::localized_string::LocalizedString;
 use crate::ops::sort;
+use crate::util::text_similarity;
 use crate::util::two_pointer_traversal;
 
+/// How closely the default text that was localized should match the current
+/// default text for the localized text to still be used
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchPolicy {
+    Exact,
+
+    /// Texts are compared after dropping whitespace & punctuation
+    IgnoreWhitespaceAndPunctuation,
+
+    /// Texts that are at least this similar (between 0 & 1) are matched
+    Similarity(f32),
+}
+
+/// A localized text that was used even though the default text it was
+/// localized from isn't the same as the current default text
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuzzyMatch {
+    pub name: String,
+    pub localized_default: String,
+    pub current_default: String,
+}
+
+/// What was extracted out of the localized strings
+#[derive(Debug, Default, PartialEq)]
+pub struct Extraction {
+    pub strings: Vec<AndroidString>,
+
+    /// Used even though the default text they were localized from has changed
+    pub fuzzy_matches: Vec<FuzzyMatch>,
+
+    /// Names of the pre-filled texts that are still the default text. These
+    /// can't be told apart from texts that weren't localized & are skipped
+    pub identical_to_default: Vec<String>,
+}
+
 /// Localized strings will be converted into `AndroidString` only if both the name
 /// & the default value from `LocalizedString` match up with whatever is in the
-/// default string
+/// default string. If the default value isn't known, matching the name is enough.
+/// Values that only match as per the (non exact) policy are also returned as
+/// fuzzy matches, so that they could be reviewed. Pre-filled values that are
+/// still the default text are skipped & returned by name
 pub fn extract_android_strings_from_localized(
     localized_strings: &mut [LocalizedString],
     default_strings: &mut [AndroidString],
-) -> Vec<AndroidString> {
+    match_policy: MatchPolicy,
+) -> Extraction {
     // Sort both the incoming strings
     sort::sort_android_strings_by_name(default_strings);
     sort::sort_localized_strings_by_name(localized_strings);
 
-    let mut result = Vec::with_capacity(localized_strings.len()); // Max number of expected strings
+    let mut extraction = Extraction {
+        strings: Vec::with_capacity(localized_strings.len()), // Max number of expected strings
+        ..Default::default()
+    };
+
     two_pointer_traversal::compare(
         localized_strings,
         default_strings,
         |localized_string, default_string| localized_string.name().cmp(default_string.name()),
         |localized_string, default_string| {
-            if localized_string.default() == default_string.value() {
-                result.push(AndroidString::new(
+            let localized = localized_string.localized();
+            if localized_string.is_prefilled()
+                && (localized == default_string.value()
+                    || localized_string.default() == Some(localized))
+            {
+                extraction
+                    .identical_to_default
+                    .push(String::from(localized_string.name()));
+                return;
+            }
+
+            let is_default_same = match localized_string.default() {
+                None => true,
+                Some(default) if default == default_string.value() => true,
+                Some(default) => {
+                    let is_fuzzy_match =
+                        is_fuzzy_match(default, default_string.value(), match_policy);
+                    if is_fuzzy_match {
+                        extraction.fuzzy_matches.push(FuzzyMatch {
+                            name: String::from(localized_string.name()),
+                            localized_default: String::from(default),
+                            current_default: String::from(default_string.value()),
+                        });
+                    }
+
+                    is_fuzzy_match
+                }
+            };
+
+            if is_default_same {
+                extraction.strings.push(AndroidString::new(
                     String::from(localized_string.name()),
                     String::from(localized_string.localized()),
                     default_string.is_localizable(),
@@ -30,7 +103,33 @@ pub fn extract_android_strings_from_localized(
         },
     );
 
-    result
+    extraction
+}
+
+/// Format specifiers & numbers have to be the same under every policy as a text
+/// with any of them changed means something else
+fn is_fuzzy_match(
+    localized_default: &str,
+    current_default: &str,
+    match_policy: MatchPolicy,
+) -> bool {
+    if text_similarity::find_specifiers_and_numbers(localized_default)
+        != text_similarity::find_specifiers_and_numbers(current_default)
+    {
+        return false;
+    }
+
+    match match_policy {
+        MatchPolicy::Exact => false,
+        MatchPolicy::IgnoreWhitespaceAndPunctuation => {
+            text_similarity::strip_whitespace_and_punctuation(localized_default)
+                == text_similarity::strip_whitespace_and_punctuation(current_default)
+        }
+
+        MatchPolicy::Similarity(threshold) => {
+            text_similarity::similarity(localized_default, current_default) >= threshold
+        }
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +139,9 @@ mod tests {
     use crate::android_string::AndroidString;
     use crate::localized_string::LocalizedString;
 
+    use super::FuzzyMatch;
+    use super::MatchPolicy;
+
     #[test]
     fn extracts() {
         let mut default_strings = vec![
@@ -53,19 +155,155 @@ mod tests {
             LocalizedString::build("string_3", "english 3 value", "french 3 value"),
             LocalizedString::build("string_4", "english 4 value", "french 4 value"),
             LocalizedString::build("string_2", "english 2 value", "french 2 value"),
+            LocalizedString::without_default("string_1", "french 1 value"), // no default to make sure only name is matched
         ];
 
-        let strings = super::extract_android_strings_from_localized(
+        let extraction = super::extract_android_strings_from_localized(
             &mut localized_strings,
             &mut default_strings,
+            MatchPolicy::Exact,
         );
 
+        test_utilities::list::assert_list_is_empty(extraction.fuzzy_matches);
+        test_utilities::list::assert_list_is_empty(extraction.identical_to_default);
+
         test_utilities::list::assert_strict_list_eq(
-            strings,
+            extraction.strings,
             vec![
+                AndroidString::localizable("string_1", "french 1 value"),
                 AndroidString::unlocalizable("string_2", "french 2 value"),
                 AndroidString::localizable("string_3", "french 3 value"),
             ],
         )
     }
+
+    #[test]
+    fn skips_prefilled_values_that_are_still_the_default_text() {
+        let mut default_strings = vec![
+            AndroidString::localizable("string_1", "OK"),
+            AndroidString::localizable("string_2", "Delete"),
+            AndroidString::localizable("string_3", "english 3 value"),
+            AndroidString::localizable("string_4", "Acme"),
+        ];
+
+        let mut localized_strings = vec![
+            LocalizedString::without_default("string_1", "OK").prefilled(),
+            LocalizedString::build("string_2", "Delete", "Delete").prefilled(),
+            LocalizedString::without_default("string_3", "french 3 value").prefilled(),
+            LocalizedString::build("string_4", "Acme", "Acme"), // not pre-filled to make sure texts meant to be the same are used
+        ];
+
+        let extraction = super::extract_android_strings_from_localized(
+            &mut localized_strings,
+            &mut default_strings,
+            MatchPolicy::Exact,
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            extraction.strings,
+            vec![
+                AndroidString::localizable("string_3", "french 3 value"),
+                AndroidString::localizable("string_4", "Acme"),
+            ],
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            extraction.identical_to_default,
+            vec![String::from("string_1"), String::from("string_2")],
+        )
+    }
+
+    #[test]
+    fn extracts_ignoring_whitespace_and_punctuation() {
+        let (strings, fuzzy_matches) =
+            extract_with_policy(MatchPolicy::IgnoreWhitespaceAndPunctuation);
+        test_utilities::list::assert_strict_list_eq(
+            strings,
+            vec![
+                AndroidString::localizable("string_1", "french 1"),
+                AndroidString::localizable("string_2", "french 2"),
+            ],
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            fuzzy_matches,
+            vec![FuzzyMatch {
+                name: String::from("string_2"),
+                localized_default: String::from("Save  changes"),
+                current_default: String::from("Save changes."),
+            }],
+        );
+    }
+
+    #[test]
+    fn extracts_similar() {
+        let (strings, fuzzy_matches) = extract_with_policy(MatchPolicy::Similarity(0.8));
+        test_utilities::list::assert_strict_list_eq(
+            strings,
+            vec![
+                AndroidString::localizable("string_1", "french 1"),
+                AndroidString::localizable("string_2", "french 2"),
+                AndroidString::localizable("string_3", "french 3"),
+            ],
+        );
+
+        assert_eq!(
+            fuzzy_matches
+                .into_iter()
+                .map(|m| m.name)
+                .collect::<Vec<String>>(),
+            vec![String::from("string_2"), String::from("string_3")]
+        );
+    }
+
+    #[test]
+    fn does_not_fuzzy_match_different_format_specifiers_or_numbers() {
+        for match_policy in [
+            MatchPolicy::IgnoreWhitespaceAndPunctuation,
+            MatchPolicy::Similarity(0.8),
+        ] {
+            let mut default_strings = vec![
+                AndroidString::localizable("string_1", "%2$d of %1$d files"),
+                AndroidString::localizable("string_2", "6 items"),
+            ];
+
+            let mut localized_strings = vec![
+                LocalizedString::build("string_1", "%1$d of %2$d files", "french 1"),
+                LocalizedString::build("string_2", "5 items", "french 2"),
+            ];
+
+            let extraction = super::extract_android_strings_from_localized(
+                &mut localized_strings,
+                &mut default_strings,
+                match_policy,
+            );
+
+            test_utilities::list::assert_list_is_empty(extraction.strings);
+            test_utilities::list::assert_list_is_empty(extraction.fuzzy_matches);
+        }
+    }
+
+    fn extract_with_policy(match_policy: MatchPolicy) -> (Vec<AndroidString>, Vec<FuzzyMatch>) {
+        let mut default_strings = vec![
+            AndroidString::localizable("string_1", "Cancel"),
+            AndroidString::localizable("string_2", "Save changes."),
+            AndroidString::localizable("string_3", "Recieve messages"),
+            AndroidString::localizable("string_4", "Delete everything"),
+        ];
+
+        let mut localized_strings = vec![
+            LocalizedString::build("string_1", "Cancel", "french 1"),
+            LocalizedString::build("string_2", "Save  changes", "french 2"),
+            LocalizedString::build("string_3", "Receive messages", "french 3"),
+            LocalizedString::build("string_4", "Delete", "french 4"),
+        ];
+
+        let extraction = super::extract_android_strings_from_localized(
+            &mut localized_strings,
+            &mut default_strings,
+            match_policy,
+        );
+
+        (extraction.strings, extraction.fuzzy_matches)
+    }
 }
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 use crate::android_string::AndroidString;
 use crate::ops::sort;
@@ -7,6 +8,15 @@ pub fn find_localizable_strings(strings: Vec<AndroidString>) -> Vec<AndroidStrin
     strings.into_iter().filter(|s| s.is_localizable()).collect()
 }
 
+/// Localizable strings that aren't aliases of other strings (`@string/name`).
+/// Aliases pick up the localized texts of the strings they point to
+pub fn find_strings_to_localize(strings: Vec<AndroidString>) -> Vec<AndroidString> {
+    strings
+        .into_iter()
+        .filter(|s| s.is_localizable() && !s.is_reference())
+        .collect()
+}
+
 /// It is assumed that neither lists have strings with the same names. If they
 /// do, the result is undefined! This method doesn't check whether `all_strings`
 /// contains everything that is contained in `lacking_strings`
@@ -53,6 +63,23 @@ pub fn find_missing_strings(
     result
 }
 
+/// Returns the new strings that either aren't in the existing strings or have
+/// a different value there
+pub fn find_changed_strings(
+    new_strings: Vec<AndroidString>,
+    existing_strings: &[AndroidString],
+) -> Vec<AndroidString> {
+    let existing_values: HashMap<&str, &str> = existing_strings
+        .iter()
+        .map(|s| (s.name(), s.value()))
+        .collect();
+
+    new_strings
+        .into_iter()
+        .filter(|s| existing_values.get(s.name()) != Some(&s.value()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use test_utilities;
@@ -76,6 +103,47 @@ mod tests {
         )
     }
 
+    #[test]
+    fn finds_strings_to_localize() {
+        let strings_to_localize = super::find_strings_to_localize(vec![
+            AndroidString::localizable("localizable_string", "string value"),
+            AndroidString::unlocalizable("non_localizable_string", "string value"),
+            AndroidString::localizable("reference_string", "@string/localizable_string"),
+            AndroidString::localizable("android_reference_string", "@android:string/ok"),
+        ]);
+
+        test_utilities::list::assert_strict_list_eq(
+            strings_to_localize,
+            vec![AndroidString::localizable(
+                "localizable_string",
+                "string value",
+            )],
+        )
+    }
+
+    #[test]
+    fn finds_changed_strings() {
+        let changed_strings = super::find_changed_strings(
+            vec![
+                AndroidString::localizable("same", "value"),
+                AndroidString::localizable("changed", "new value"),
+                AndroidString::localizable("new", "value"),
+            ],
+            &[
+                AndroidString::localizable("changed", "old value"),
+                AndroidString::localizable("same", "value"),
+            ],
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            changed_strings,
+            vec![
+                AndroidString::localizable("changed", "new value"),
+                AndroidString::localizable("new", "value"),
+            ],
+        )
+    }
+
     #[test]
     fn finds_missing_strings() {
         let mut lacking_strings = vec![
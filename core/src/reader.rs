@@ -1,2 +1,5 @@
+pub mod apple_strings_reader;
 pub mod csv_reader;
+pub mod json_reader;
+pub mod stringsdict_reader;
 pub mod xml_reader;
@@ -0,0 +1,206 @@
+use std::io::Read;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::error::{ErrorKind, InnerError};
+use crate::util::text_decoder;
+
+/// Reads key-value pairs out of an Apple `.strings` file. Values are unescaped
+/// (`\"` => `"`, `\n` => new line etc.) but are otherwise returned as is
+pub fn read<S: Read>(source: S) -> Result<Vec<(String, String)>, InnerError> {
+    let text = text_decoder::read_to_string(source)?;
+    let mut tokenizer = Tokenizer {
+        chars: text.chars().peekable(),
+        line: 1,
+    };
+
+    let mut pairs = vec![];
+    while let Some(key) = tokenizer.next_text()? {
+        tokenizer.expect('=')?;
+        let value = match tokenizer.next_text()? {
+            None => return Err(tokenizer.error("Expected value but the file ended")),
+            Some(value) => value,
+        };
+
+        tokenizer.expect(';')?;
+        pairs.push((key, value));
+    }
+
+    Ok(pairs)
+}
+
+struct Tokenizer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    /// Returns `None` when there are no more tokens
+    fn next_text(&mut self) -> Result<Option<String>, InnerError> {
+        self.skip_whitespace_and_comments()?;
+        match self.chars.peek().cloned() {
+            None => Ok(None),
+            Some('"') => {
+                self.chars.next();
+                self.read_quoted_text().map(Some)
+            }
+
+            Some(c) if is_unquoted_char(c) => Ok(Some(self.read_unquoted_text())),
+            Some(c) => Err(self.error(&format!("Unexpected character: {}", c))),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), InnerError> {
+        self.skip_whitespace_and_comments()?;
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(self.error(&format!("Expected {} but found {}", expected, c))),
+            None => Err(self.error(&format!("Expected {} but the file ended", expected))),
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), InnerError> {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next_char();
+                }
+
+                Some('/') => {
+                    self.chars.next();
+                    match self.chars.next() {
+                        Some('/') => while !matches!(self.next_char(), None | Some('\n')) {},
+                        Some('*') => self.skip_block_comment()?,
+                        _ => return Err(self.error("Expected a comment after /")),
+                    }
+                }
+
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), InnerError> {
+        loop {
+            match self.next_char() {
+                None => return Err(self.error("Comment isn't closed")),
+                Some('*') if self.chars.peek() == Some(&'/') => {
+                    self.chars.next();
+                    return Ok(());
+                }
+
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn read_quoted_text(&mut self) -> Result<String, InnerError> {
+        let mut text = String::new();
+        loop {
+            match self.next_char() {
+                None => return Err(self.error("Quoted text isn't closed")),
+                Some('"') => return Ok(text),
+                Some('\\') => text.push(self.read_escaped_char()?),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn read_escaped_char(&mut self) -> Result<char, InnerError> {
+        match self.next_char() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('0') => Ok('\0'),
+            Some('U') | Some('u') => {
+                let hex: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(std::char::from_u32)
+                    .ok_or_else(|| self.error(&format!("Invalid unicode escape: {}", hex)))
+            }
+
+            Some(c) => Ok(c),
+            None => Err(self.error("Escape sequence isn't complete")),
+        }
+    }
+
+    fn read_unquoted_text(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.chars.peek() {
+            if is_unquoted_char(*c) {
+                text.push(*c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        text
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let next = self.chars.next();
+        if next == Some('\n') {
+            self.line += 1;
+        }
+
+        next
+    }
+
+    fn error(&self, message: &str) -> InnerError {
+        InnerError::from(ErrorKind::InvalidAppleStrings(format!(
+            "Line {}: {}",
+            self.line, message
+        )))
+    }
+}
+
+fn is_unquoted_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utilities;
+
+    #[test]
+    fn reads_strings() {
+        let pairs = super::read(
+            r#"
+/* A block comment */
+"string_1" = "value 1";
+// A line comment
+"string_2"="value \"2\"\nwith\\new line";
+string_3 = "\U00e9té";
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            pairs,
+            vec![
+                (String::from("string_1"), String::from("value 1")),
+                (
+                    String::from("string_2"),
+                    String::from("value \"2\"\nwith\\new line"),
+                ),
+                (String::from("string_3"), String::from("été")),
+            ],
+        )
+    }
+
+    #[test]
+    fn errors_with_line_number_for_missing_semicolon() {
+        let error = super::read("\"s1\" = \"v1\";\n\"s2\" = \"v2\"\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "Line 3: Expected ; but the file ended")
+    }
+
+    #[test]
+    fn errors_for_unclosed_quote() {
+        let error = super::read("\"s1\" = \"v1;".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "Line 1: Quoted text isn't closed");
+        assert_eq!(error.kind().code(), "invalid_apple_strings")
+    }
+}
@@ -1,36 +1,140 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
 
 use csv;
-use csv::ReaderBuilder;
+use csv::{ReaderBuilder, StringRecord};
 
-use android_localization_utilities::DevExpt;
-
-use crate::error::InnerError;
+use crate::constants;
+use crate::error::{CsvDiagnostic, ErrorKind, InnerError};
+use crate::format::{CsvDialect, StringMetadata};
 use crate::localized_string::LocalizedString;
 use crate::localized_strings::LocalizedStrings;
+use crate::util::text_decoder;
+
+/// Metadata columns (if present) are never read as locales. Headers are matched
+/// after being normalized (`Max Length` => `max_length`)
+const METADATA_HEADERS: [&str; 5] = [
+    constants::csv::CONTEXT_HEADER,
+    constants::csv::MAX_LENGTH_HEADER,
+    constants::csv::NOTES_HEADER,
+    constants::csv::STATUS_HEADER,
+    constants::csv::REVIEWER_HEADER,
+];
+
+#[derive(Debug)]
+pub struct CsvContents {
+    pub strings_list: Vec<LocalizedStrings>,
+    pub metadata: Vec<StringMetadata>,
+    pub warnings: Vec<CsvDiagnostic>,
+}
 
+/// The source is decoded as per its BOM (UTF-8 if it doesn't have one). Problems
+/// that make the CSV unusable aren't bailed out on right away. They are all
+/// gathered & returned together as `ErrorKind::InvalidCsv`. Columns that are
+/// skipped (without a header or not for any of the allowed locales) are returned
+/// as warnings along with the read strings & the values of the metadata columns
 pub fn read<S: Read>(
     source: S,
     allow_only_locales: HashSet<String>,
-) -> Result<Vec<LocalizedStrings>, InnerError> {
+    dialect: &CsvDialect,
+) -> Result<CsvContents, InnerError> {
+    let text = text_decoder::read_to_string(source)?;
     let mut reader = ReaderBuilder::new()
         .has_headers(true) // To treat first row specially
-        .flexible(false) // Takes care of making sure that all records are of the same size
-        .trim(csv::Trim::All) // To skip whitespace around commas
-        .from_reader(source); // Read is automatically buffered
+        .flexible(true) // Rows with a different number of values are reported below
+        .trim(csv::Trim::All) // To skip whitespace around delimiters
+        .delimiter(dialect.delimiter)
+        .quote(dialect.quote)
+        .from_reader(text.as_bytes());
 
     // Get foreign_locales
-    let filtered_headers = extract_filtered_headers(reader.headers()?, allow_only_locales)?;
+    let headers = reader.headers()?.clone();
+    let mut warnings = vec![];
+    let filtered_headers =
+        extract_filtered_headers(&headers, &allow_only_locales, dialect, &mut warnings)
+            .map_err(|errors| InnerError::from(ErrorKind::InvalidCsv(errors)))?;
     let mut localized_strings_list: Vec<Vec<LocalizedString>> =
         vec![Vec::new(); filtered_headers.foreign_locales.len()];
 
     // Extract localized record
+    let mut metadata = vec![];
+    let mut errors = vec![];
+    let mut string_name_to_row: HashMap<String, u64> = HashMap::new();
     for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(error) => {
+                errors.push(CsvDiagnostic {
+                    row: error.position().map(|position| position.line()),
+                    column: None,
+                    problem: error.to_string(),
+                    suggestion: None,
+                });
+
+                continue;
+            }
+        };
+
+        let row = record.position().map_or(0, |position| position.line());
+        if record.len() != headers.len() {
+            errors.push(CsvDiagnostic {
+                row: Some(row),
+                column: None,
+                problem: format!(
+                    "Row has {} values but the header has {} columns",
+                    record.len(),
+                    headers.len()
+                ),
+                suggestion: Some(String::from(
+                    "Every row should have a value (even if it is empty) for every column",
+                )),
+            });
+
+            continue;
+        }
+
         let localized_record =
-            extract_localized_record(&record?, &filtered_headers.foreign_indices_allow_flags)?;
-        let string_name = localized_record.string_name.clone();
-        let default_value = localized_record.default_value.clone();
+            extract_localized_record(&record, &filtered_headers.foreign_indices_allow_flags);
+        let string_name = localized_record.string_name;
+        let default_value = localized_record.default_value;
+        if string_name.is_empty() {
+            errors.push(CsvDiagnostic {
+                row: Some(row),
+                column: Some(dialect.key_header.clone()),
+                problem: format!("{} can't be empty for any record", dialect.key_header),
+                suggestion: Some(String::from(
+                    "Fill in the name of the string or remove the row",
+                )),
+            });
+
+            continue;
+        }
+
+        if let Some(first_row) = string_name_to_row.get(&string_name) {
+            errors.push(CsvDiagnostic {
+                row: Some(row),
+                column: Some(dialect.key_header.clone()),
+                problem: format!("{} is already on row {}", string_name, first_row),
+                suggestion: Some(String::from("Keep only one row per string")),
+            });
+
+            continue;
+        }
+
+        string_name_to_row.insert(string_name.clone(), row);
+        let metadata_values: Vec<(String, String)> = filtered_headers
+            .metadata_columns
+            .iter()
+            .filter(|(index, _)| !record[*index].is_empty())
+            .map(|(index, header)| (header.clone(), String::from(&record[*index])))
+            .collect();
+
+        if !metadata_values.is_empty() {
+            metadata.push(StringMetadata {
+                name: string_name.clone(),
+                values: metadata_values,
+            })
+        }
 
         // Go through all localized values & add them to respective lists
         for (index, foreign_value) in localized_record.foreign_values.into_iter().enumerate() {
@@ -43,76 +147,167 @@ pub fn read<S: Read>(
 
                 localized_strings.push(LocalizedString::new(
                     string_name.clone(),
-                    default_value.clone(),
+                    Some(default_value.clone()),
                     foreign_value,
                 ))
             }
         }
     }
 
-    Ok(filtered_headers
+    if !errors.is_empty() {
+        return Err(ErrorKind::InvalidCsv(errors).into());
+    }
+
+    let localized_strings_list = filtered_headers
         .foreign_locales
         .into_iter()
         .zip(localized_strings_list)
         .map(|(locale, strings)| LocalizedStrings::new(locale, strings))
-        .collect())
+        .collect();
+
+    Ok(CsvContents {
+        strings_list: localized_strings_list,
+        metadata,
+        warnings,
+    })
 }
 
 fn extract_filtered_headers(
-    record: &csv::StringRecord,
-    allow_only_locales: HashSet<String>,
-) -> Result<FilteredHeaders, InnerError> {
-    if record.len() < 3 {
-        return Err("Too few values in header (at least 3 required)".into());
+    headers: &StringRecord,
+    allow_only_locales: &HashSet<String>,
+    dialect: &CsvDialect,
+    warnings: &mut Vec<CsvDiagnostic>,
+) -> Result<FilteredHeaders, Vec<CsvDiagnostic>> {
+    if headers.len() < 3 {
+        return Err(vec![header_diagnostic(
+            None,
+            "Too few values in header (at least 3 required)",
+            &format!(
+                "Header should be {}, {} & a column per locale",
+                dialect.key_header, dialect.source_header
+            ),
+        )]);
     }
 
-    let mut iterator = record.into_iter();
-    let header1 = iterator
-        .next()
-        .expt("Already checked the length but still fails!");
-    let header2 = iterator
-        .next()
-        .expt("Already checked the length but still fails!");
-
-    if header1 != "string_name" {
-        return Err("First header should be named string_name".into());
+    let mut errors = vec![];
+    if headers[0] != dialect.key_header {
+        errors.push(header_diagnostic(
+            Some(column_name(0, &headers[0])),
+            &format!("First header should be named {}", dialect.key_header),
+            "Rename the column or move the string names to the first column",
+        ));
     }
 
-    if header2 != "default_locale" {
-        return Err("Second header should be named default_locale".into());
+    if headers[1] != dialect.source_header {
+        errors.push(header_diagnostic(
+            Some(column_name(1, &headers[1])),
+            &format!("Second header should be named {}", dialect.source_header),
+            "Rename the column or move the default texts to the second column",
+        ));
     }
 
     let mut foreign_indices_allow_flags = vec![];
     let mut foreign_locales = vec![];
-    for foriegn_locale in iterator {
-        let foreign_locale = String::from(foriegn_locale);
-        let allow_index = allow_only_locales.contains(&foreign_locale);
+    let mut metadata_columns = vec![];
+    let mut seen_headers = HashSet::new();
+    for (index, header) in headers.iter().enumerate().skip(2) {
+        let column = Some(column_name(index, header));
+        let allow_index = if header.is_empty() {
+            warnings.push(header_diagnostic(
+                column,
+                "Column doesn't have a header & is skipped",
+                "Remove the column if it is empty or name it after its locale",
+            ));
+
+            false
+        } else if dialect
+            .ignored_headers
+            .iter()
+            .any(|ignored| ignored == header)
+        {
+            false
+        } else if is_metadata_header(header, dialect) {
+            metadata_columns.push((index, String::from(header)));
+            false
+        } else if !seen_headers.insert(header) {
+            errors.push(header_diagnostic(
+                column,
+                "Column is repeated",
+                "Keep only one column per locale",
+            ));
+
+            false
+        } else if !allow_only_locales.contains(header) {
+            let mut known_locales: Vec<&str> =
+                allow_only_locales.iter().map(String::as_str).collect();
+            known_locales.sort_unstable();
+            warnings.push(header_diagnostic(
+                column,
+                "Column isn't for any of the known locales & is skipped",
+                &format!(
+                    "Known locales: {}. Pass in a mapping if it is for one of these",
+                    known_locales.join(", ")
+                ),
+            ));
+
+            false
+        } else {
+            true
+        };
+
         foreign_indices_allow_flags.push(allow_index);
         if allow_index {
-            foreign_locales.push(foreign_locale);
+            foreign_locales.push(String::from(header));
         }
     }
 
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
     Ok(FilteredHeaders {
         foreign_locales,
         foreign_indices_allow_flags,
+        metadata_columns,
     })
 }
 
+fn is_metadata_header(header: &str, dialect: &CsvDialect) -> bool {
+    let normalized_header = header.trim().to_lowercase().replace([' ', '-'], "_");
+    METADATA_HEADERS.contains(&normalized_header.as_str())
+        || dialect
+            .metadata_headers
+            .iter()
+            .any(|metadata_header| metadata_header == header)
+}
+
+/// Columns without a header are named by their 1-based position (`#4`)
+fn column_name(index: usize, header: &str) -> String {
+    if header.is_empty() {
+        format!("#{}", index + 1)
+    } else {
+        String::from(header)
+    }
+}
+
+fn header_diagnostic(column: Option<String>, problem: &str, suggestion: &str) -> CsvDiagnostic {
+    CsvDiagnostic {
+        row: Some(1),
+        column,
+        problem: String::from(problem),
+        suggestion: Some(String::from(suggestion)),
+    }
+}
+
+/// Record is expected to be as long as the headers record
 fn extract_localized_record(
-    record: &csv::StringRecord,
+    record: &StringRecord,
     foreign_indices_allow_flags: &[bool],
-) -> Result<LocalizedRecord, InnerError> {
-    // Since `ReaderBuilder` is set to be not flexible, we can be sure
-    // that the this record is going to be as long as the headers record
+) -> LocalizedRecord {
     let mut iterator = record.into_iter();
     let string_name = iterator.next().unwrap_or("");
     let default_value = iterator.next().unwrap_or("");
 
-    if string_name.is_empty() {
-        return Err("string_name can't be empty for any record".into());
-    }
-
     let mut foreign_values = vec![];
     for (index, foreign_value) in iterator.enumerate() {
         if foreign_indices_allow_flags.get(index) == Some(&true) {
@@ -120,16 +315,19 @@ fn extract_localized_record(
         }
     }
 
-    Ok(LocalizedRecord {
+    LocalizedRecord {
         string_name: String::from(string_name),
         default_value: String::from(default_value),
         foreign_values,
-    })
+    }
 }
 
 struct FilteredHeaders {
     foreign_locales: Vec<String>,
     foreign_indices_allow_flags: Vec<bool>,
+
+    /// Index of the column in the record to its header
+    metadata_columns: Vec<(usize, String)>,
 }
 
 #[derive(Debug)]
@@ -146,13 +344,20 @@ mod tests {
 
     use test_utilities;
 
-    use crate::error::InnerError;
+    use crate::error::{CsvDiagnostic, ErrorKind, InnerError};
+    use crate::format::{CsvDialect, CsvEncoding, StringMetadata};
     use crate::localized_string::LocalizedString;
     use crate::localized_strings::LocalizedStrings;
 
+    use super::CsvContents;
+
     #[test]
     fn reads_strings_from_valid_file() {
-        let strings_list = read_strings_from_file(
+        let CsvContents {
+            strings_list,
+            warnings,
+            ..
+        } = read_strings_from_file(
             r#"string_name, default_locale, french, german, spanish
             string_1, english 1, french 1, german 1, spanish 1
             string_2, english 2, , german 2, spanish 2"#,
@@ -160,6 +365,16 @@ mod tests {
         )
         .unwrap();
 
+        assert_eq!(
+            warnings,
+            vec![diagnostic(
+                1,
+                "german",
+                "Column isn't for any of the known locales & is skipped",
+                "Known locales: french, some_random_thing, spanish. Pass in a mapping if it is for one of these"
+            )]
+        );
+
         test_utilities::list::assert_strict_list_eq(
             strings_list,
             vec![
@@ -178,53 +393,241 @@ mod tests {
         )
     }
 
+    #[test]
+    fn reads_metadata_columns() {
+        let CsvContents {
+            strings_list,
+            metadata,
+            warnings,
+        } = read_strings_from_file(
+            r#"string_name, default_locale, context, Max Length, Notes, status, reviewer, french
+            string_1, english 1, Title of the screen, 20, Keep it short, Done, , french 1
+            string_2, english 2, , , , , , "#,
+            vec!["context", "max_length", "notes", "french"],
+        )
+        .unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(
+            metadata,
+            vec![StringMetadata {
+                name: String::from("string_1"),
+                values: vec![
+                    (String::from("context"), String::from("Title of the screen")),
+                    (String::from("Max Length"), String::from("20")),
+                    (String::from("Notes"), String::from("Keep it short")),
+                    (String::from("status"), String::from("Done")),
+                ],
+            }]
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::build("string_1", "english 1", "french 1")],
+            )],
+        )
+    }
+
     #[test]
     fn errors_if_enough_header_values_are_not_as_expected() {
         let error =
             read_strings_from_file("string_name, default_locale", vec!["french"]).unwrap_err();
         assert_eq!(
             error.to_string(),
-            "Too few values in header (at least 3 required)"
+            "Found 1 issue(s) in the CSV\nRow 1: Too few values in header (at least 3 required) \
+             (Header should be string_name, default_locale & a column per locale)"
         );
     }
 
     #[test]
-    fn errors_if_first_header_is_not_as_expected() {
-        let error =
-            read_strings_from_file("header_1, default_locale, french", vec!["french"]).unwrap_err();
+    fn errors_with_all_header_issues() {
+        let diagnostics = read_diagnostics(
+            "header_1, header_2, french, french\ns1, a, b, c",
+            vec!["french"],
+        );
+
         assert_eq!(
-            error.to_string(),
-            "First header should be named string_name"
+            diagnostics,
+            vec![
+                diagnostic(
+                    1,
+                    "header_1",
+                    "First header should be named string_name",
+                    "Rename the column or move the string names to the first column"
+                ),
+                diagnostic(
+                    1,
+                    "header_2",
+                    "Second header should be named default_locale",
+                    "Rename the column or move the default texts to the second column"
+                ),
+                diagnostic(
+                    1,
+                    "french",
+                    "Column is repeated",
+                    "Keep only one column per locale"
+                ),
+            ]
         );
     }
 
     #[test]
-    fn errors_if_second_header_is_not_as_expected() {
-        let error =
-            read_strings_from_file("string_name, header_2, french", vec!["french"]).unwrap_err();
+    fn errors_with_all_row_issues() {
+        let diagnostics = read_diagnostics(
+            "string_name, default_locale, french\n, a, b\ns1, a, b\ns2, a\ns1, a, c",
+            vec!["french"],
+        );
+
         assert_eq!(
-            error.to_string(),
-            "Second header should be named default_locale"
+            diagnostics,
+            vec![
+                diagnostic(
+                    2,
+                    "string_name",
+                    "string_name can't be empty for any record",
+                    "Fill in the name of the string or remove the row"
+                ),
+                CsvDiagnostic {
+                    row: Some(4),
+                    column: None,
+                    problem: String::from("Row has 2 values but the header has 3 columns"),
+                    suggestion: Some(String::from(
+                        "Every row should have a value (even if it is empty) for every column"
+                    )),
+                },
+                diagnostic(
+                    5,
+                    "string_name",
+                    "s1 is already on row 3",
+                    "Keep only one row per string"
+                ),
+            ]
         );
     }
 
     #[test]
-    fn errors_if_string_name_is_empty() {
-        let error = read_strings_from_file(
-            "string_name, default_locale, french\n, a, b",
+    fn warns_about_columns_without_header() {
+        let CsvContents {
+            strings_list,
+            warnings,
+            ..
+        } = read_strings_from_file(
+            "string_name, default_locale, french,\ns1, a, b,",
             vec!["french"],
         )
-        .unwrap_err();
+        .unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::build("s1", "a", "b")],
+            )],
+        );
+
+        assert_eq!(
+            warnings,
+            vec![diagnostic(
+                1,
+                "#4",
+                "Column doesn't have a header & is skipped",
+                "Remove the column if it is empty or name it after its locale"
+            )]
+        );
+    }
+
+    #[test]
+    fn reads_strings_as_per_dialect() {
+        let dialect = CsvDialect {
+            delimiter: b';',
+            quote: b'\'',
+            key_header: String::from("key"),
+            source_header: String::from("source"),
+            ignored_headers: vec![String::from("notes")],
+            ..Default::default()
+        };
+
+        let text = "key;source;notes;french\ns1;'Save; now';For a button;'Enregistrer; maintenant'";
+        let contents = super::read(
+            CsvEncoding::Utf16Le.encode(text, true).as_slice(),
+            vec![String::from("french")].into_iter().collect(),
+            &dialect,
+        )
+        .unwrap();
+
+        assert!(contents.metadata.is_empty());
+        assert!(contents.warnings.is_empty());
         assert_eq!(
-            error.to_string(),
-            "string_name can't be empty for any record"
+            contents.strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::build(
+                    "s1",
+                    "Save; now",
+                    "Enregistrer; maintenant"
+                )],
+            )]
         );
     }
 
+    #[test]
+    fn reads_declared_metadata_columns() {
+        let dialect = CsvDialect {
+            metadata_headers: vec![String::from("Vendor ID")],
+            ..Default::default()
+        };
+
+        let contents = super::read(
+            "string_name,default_locale,Vendor ID,french
+s1,Save,V-42,Enregistrer"
+                .as_bytes(),
+            vec![String::from("french")].into_iter().collect(),
+            &dialect,
+        )
+        .unwrap();
+
+        assert!(contents.warnings.is_empty());
+        assert_eq!(
+            contents.metadata,
+            vec![StringMetadata {
+                name: String::from("s1"),
+                values: vec![(String::from("Vendor ID"), String::from("V-42"))],
+            }]
+        );
+
+        assert_eq!(
+            contents.strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![LocalizedString::build("s1", "Save", "Enregistrer")],
+            )]
+        );
+    }
+
+    fn read_diagnostics(file_content: &str, allow_only_locales: Vec<&str>) -> Vec<CsvDiagnostic> {
+        let error = read_strings_from_file(file_content, allow_only_locales).unwrap_err();
+        assert_eq!(error.kind().code(), "invalid_csv");
+        match error.kind() {
+            ErrorKind::InvalidCsv(diagnostics) => diagnostics.clone(),
+            error_kind => panic!("Expected invalid CSV error. Received: {:?}", error_kind),
+        }
+    }
+
+    fn diagnostic(row: u64, column: &str, problem: &str, suggestion: &str) -> CsvDiagnostic {
+        CsvDiagnostic {
+            row: Some(row),
+            column: Some(String::from(column)),
+            problem: String::from(problem),
+            suggestion: Some(String::from(suggestion)),
+        }
+    }
+
     fn read_strings_from_file(
         file_content: &str,
         allow_only_locales: Vec<&str>,
-    ) -> Result<Vec<LocalizedStrings>, InnerError> {
+    ) -> Result<CsvContents, InnerError> {
         // Write content to file
         let mut tmpfile: File = tempfile::tempfile().unwrap();
         tmpfile.write_all(file_content.as_bytes()).unwrap();
@@ -236,6 +639,7 @@ mod tests {
         super::read(
             tmpfile.try_clone().unwrap(),
             allow_only_locales.into_iter().map(String::from).collect(),
+            &CsvDialect::default(),
         )
     }
 }
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use std::io::Read;
+
+use serde_json::{Map, Value};
+
+use crate::constants;
+use crate::error::{ErrorKind, InnerError};
+use crate::localized_string::LocalizedString;
+use crate::localized_strings::LocalizedStrings;
+
+/// Reads both plain JSON & ARB documents. The locale is read from `@@locale` if
+/// it is present & `fallback_locale` is used otherwise. The default text a string
+/// was localized from is read from the string's metadata (`@string_name`) if
+/// available. Localized texts read from documents without this metadata can't be
+/// checked against the current default text. All the values are marked as
+/// pre-filled (documents are written out pre-filled with the default texts) so
+/// that the ones still the same as the default text can be told apart
+pub fn read<S: Read>(
+    source: S,
+    fallback_locale: &str,
+    allow_only_locales: HashSet<String>,
+) -> Result<Vec<LocalizedStrings>, InnerError> {
+    let document = match serde_json::from_reader(source)? {
+        Value::Object(document) => document,
+        _ => {
+            return Err(
+                ErrorKind::InvalidJson(String::from("Document should be a JSON object")).into(),
+            )
+        }
+    };
+
+    let locale = match document.get(constants::json::LOCALE_KEY) {
+        None => String::from(fallback_locale),
+        Some(Value::String(locale)) => locale.clone(),
+        Some(_) => {
+            return Err(ErrorKind::InvalidJson(format!(
+                "{} should be a string",
+                constants::json::LOCALE_KEY
+            ))
+            .into());
+        }
+    };
+
+    if !allow_only_locales.contains(&locale) {
+        return Ok(vec![]);
+    }
+
+    let mut localized_strings = vec![];
+    for (key, value) in &document {
+        // Metadata & the locale are handled separately
+        if key.starts_with(constants::json::METADATA_KEY_PREFIX) {
+            continue;
+        }
+
+        let localized_value = match value {
+            Value::String(localized_value) => localized_value,
+            _ => {
+                return Err(
+                    ErrorKind::InvalidJson(format!("Value for {} should be a string", key)).into(),
+                )
+            }
+        };
+
+        let default_value = read_default_value(&document, key)?;
+        if !localized_value.is_empty() {
+            localized_strings.push(
+                LocalizedString::new(key.clone(), default_value, localized_value.clone())
+                    .prefilled(),
+            );
+        }
+    }
+
+    Ok(vec![LocalizedStrings::new(locale, localized_strings)])
+}
+
+fn read_default_value(
+    document: &Map<String, Value>,
+    string_name: &str,
+) -> Result<Option<String>, InnerError> {
+    let metadata_key = format!("{}{}", constants::json::METADATA_KEY_PREFIX, string_name);
+    let default_value = document
+        .get(&metadata_key)
+        .and_then(|metadata| metadata.get(constants::json::SOURCE_METADATA_KEY));
+
+    match default_value {
+        None => Ok(None),
+        Some(Value::String(default_value)) => Ok(Some(default_value.clone())),
+        Some(_) => Err(ErrorKind::InvalidJson(format!(
+            "{} in {} should be a string",
+            constants::json::SOURCE_METADATA_KEY,
+            metadata_key
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utilities;
+
+    use crate::error::InnerError;
+    use crate::localized_string::LocalizedString;
+    use crate::localized_strings::LocalizedStrings;
+
+    #[test]
+    fn reads_strings_from_arb() {
+        let strings_list = read_strings(
+            r#"{
+                "@@locale": "french",
+                "string_1": "french 1",
+                "@string_1": {
+                    "description": "some description",
+                    "x-source": "english 1"
+                },
+                "string_2": "",
+                "@string_2": {
+                    "x-source": "english 2"
+                },
+                "string_3": "french 3"
+            }"#,
+            "fallback",
+            vec!["french"],
+        )
+        .unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![
+                    LocalizedString::build("string_1", "english 1", "french 1").prefilled(),
+                    LocalizedString::without_default("string_3", "french 3").prefilled(),
+                ],
+            )],
+        )
+    }
+
+    #[test]
+    fn marks_values_as_prefilled() {
+        let strings_list = read_strings(
+            r#"{
+                "@@locale": "french",
+                "string_1": "Delete",
+                "@string_1": {"x-source": "Delete"},
+                "string_2": "Supprimer tout",
+                "@string_2": {"x-source": "Delete all"}
+            }"#,
+            "fallback",
+            vec!["french"],
+        )
+        .unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![
+                    LocalizedString::build("string_1", "Delete", "Delete").prefilled(),
+                    LocalizedString::build("string_2", "Delete all", "Supprimer tout").prefilled(),
+                ],
+            )],
+        )
+    }
+
+    #[test]
+    fn reads_strings_from_plain_json_with_fallback_locale() {
+        let strings_list = read_strings(
+            r#"{"string_1": "french 1", "string_2": "french 2"}"#,
+            "french",
+            vec!["french"],
+        )
+        .unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            strings_list,
+            vec![LocalizedStrings::build(
+                "french",
+                vec![
+                    LocalizedString::without_default("string_1", "french 1").prefilled(),
+                    LocalizedString::without_default("string_2", "french 2").prefilled(),
+                ],
+            )],
+        )
+    }
+
+    #[test]
+    fn skips_locales_not_allowed() {
+        let strings_list = read_strings(
+            r#"{"@@locale": "german", "string_1": "german 1"}"#,
+            "french",
+            vec!["french"],
+        )
+        .unwrap();
+
+        test_utilities::list::assert_list_is_empty(strings_list)
+    }
+
+    #[test]
+    fn errors_if_document_is_not_an_object() {
+        let error = read_strings(r#"["string_1"]"#, "french", vec!["french"]).unwrap_err();
+        assert_eq!(error.to_string(), "Document should be a JSON object");
+        assert_eq!(error.kind().code(), "invalid_json");
+    }
+
+    #[test]
+    fn errors_if_value_is_not_a_string() {
+        let error = read_strings(r#"{"string_1": 1}"#, "french", vec!["french"]).unwrap_err();
+        assert_eq!(error.to_string(), "Value for string_1 should be a string");
+        assert_eq!(error.kind().code(), "invalid_json");
+    }
+
+    fn read_strings(
+        content: &str,
+        fallback_locale: &str,
+        allow_only_locales: Vec<&str>,
+    ) -> Result<Vec<LocalizedStrings>, InnerError> {
+        super::read(
+            content.as_bytes(),
+            fallback_locale,
+            allow_only_locales.into_iter().map(String::from).collect(),
+        )
+    }
+}
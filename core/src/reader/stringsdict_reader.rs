@@ -0,0 +1,328 @@
+use std::collections::BTreeMap;
+use std::io::BufReader;
+use std::io::Read;
+
+use xml::reader::XmlEvent;
+use xml::ParserConfig;
+
+use crate::android_plurals::Quantity;
+use crate::constants;
+use crate::error::{ErrorKind, InnerError};
+
+/// Name of the plurals along with their items
+pub type ApplePlurals = (String, BTreeMap<Quantity, String>);
+
+/// Reads plurals out of an Apple `.stringsdict` file. Only plural rules with
+/// a single variable are supported. If the localized format has text around the
+/// variable (`You have %#@apples@ left`), that text is folded into every item.
+/// Values are returned as is (without converting format specifiers)
+pub fn read<S: Read>(source: S) -> Result<Vec<ApplePlurals>, InnerError> {
+    let root = parse_root(source)?;
+    let entries = match root {
+        PlistValue::Dict(entries) => entries,
+        _ => {
+            return Err(ErrorKind::InvalidStringsdict(String::from(
+                "Root of stringsdict should be a dict",
+            ))
+            .into())
+        }
+    };
+
+    let mut plurals = vec![];
+    for (name, value) in entries {
+        let items = match value {
+            PlistValue::Dict(entries) => build_items(&name, entries)?,
+            _ => {
+                return Err(ErrorKind::InvalidStringsdict(format!(
+                    "Value for {} should be a dict",
+                    name
+                ))
+                .into())
+            }
+        };
+
+        plurals.push((name, items));
+    }
+
+    Ok(plurals)
+}
+
+fn build_items(
+    name: &str,
+    entries: Vec<(String, PlistValue)>,
+) -> Result<BTreeMap<Quantity, String>, InnerError> {
+    let mut format = None;
+    let mut variables = vec![];
+    for (key, value) in entries {
+        match value {
+            PlistValue::Text(text) => {
+                if key == constants::stringsdict::LOCALIZED_FORMAT_KEY {
+                    format = Some(text)
+                }
+            }
+
+            PlistValue::Dict(entries) => variables.push((key, entries)),
+            PlistValue::Unsupported => {}
+        }
+    }
+
+    let format = format.ok_or_else(|| {
+        InnerError::from(ErrorKind::InvalidStringsdict(format!(
+            "{} is missing {}",
+            name,
+            constants::stringsdict::LOCALIZED_FORMAT_KEY
+        )))
+    })?;
+
+    if variables.len() != 1 {
+        return Err(ErrorKind::InvalidStringsdict(format!(
+            "{} should have exactly one variable. Found {}",
+            name,
+            variables.len()
+        ))
+        .into());
+    }
+
+    let (variable_name, variable_entries) = variables.remove(0);
+    let placeholder = format!("%#@{}@", variable_name);
+    if !format.contains(&placeholder) {
+        return Err(ErrorKind::InvalidStringsdict(format!(
+            "{} doesn't reference {}",
+            name, placeholder
+        ))
+        .into());
+    }
+
+    let mut items = BTreeMap::new();
+    for (key, value) in variable_entries {
+        if let (Some(quantity), PlistValue::Text(text)) = (Quantity::from_name(&key), value) {
+            items.insert(quantity, format.replace(&placeholder, &text));
+        }
+    }
+
+    Ok(items)
+}
+
+enum PlistValue {
+    Text(String),
+    Dict(Vec<(String, PlistValue)>),
+
+    /// Arrays, numbers, booleans etc. aren't of any use while reading plurals
+    Unsupported,
+}
+
+fn parse_root<S: Read>(source: S) -> Result<PlistValue, InnerError> {
+    let mut events = ParserConfig::new()
+        .ignore_comments(true)
+        .create_reader(BufReader::new(source))
+        .into_iter();
+
+    loop {
+        match events.next() {
+            None => {
+                return Err(ErrorKind::InvalidStringsdict(String::from(
+                    "stringsdict doesn't have any value",
+                ))
+                .into())
+            }
+            Some(event) => match event? {
+                XmlEvent::StartElement { name, .. }
+                    if name.local_name != constants::stringsdict::PLIST =>
+                {
+                    return parse_value(&name.local_name, &mut events)
+                }
+
+                XmlEvent::EndDocument => {
+                    return Err(ErrorKind::InvalidStringsdict(String::from(
+                        "stringsdict doesn't have any value",
+                    ))
+                    .into())
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Start element of the value must have already been consumed. Consumes
+/// everything till (& including) the matching end element
+fn parse_value<I>(element_name: &str, events: &mut I) -> Result<PlistValue, InnerError>
+where
+    I: Iterator<Item = Result<XmlEvent, xml::reader::Error>>,
+{
+    match element_name {
+        constants::stringsdict::DICT => parse_dict(events),
+        constants::stringsdict::STRING => parse_text(events).map(PlistValue::Text),
+        _ => {
+            skip_element(events)?;
+            Ok(PlistValue::Unsupported)
+        }
+    }
+}
+
+fn parse_dict<I>(events: &mut I) -> Result<PlistValue, InnerError>
+where
+    I: Iterator<Item = Result<XmlEvent, xml::reader::Error>>,
+{
+    let mut entries = vec![];
+    let mut key = None;
+    while let Some(event) = events.next() {
+        match event? {
+            XmlEvent::StartElement { name, .. } => {
+                if name.local_name == constants::stringsdict::KEY {
+                    key = Some(parse_text(events)?);
+                } else {
+                    let value = parse_value(&name.local_name, events)?;
+                    match key.take() {
+                        None => {
+                            return Err(ErrorKind::InvalidStringsdict(String::from(
+                                "dict has a value without a key",
+                            ))
+                            .into())
+                        }
+                        Some(key) => entries.push((key, value)),
+                    }
+                }
+            }
+
+            XmlEvent::EndElement { .. } => return Ok(PlistValue::Dict(entries)),
+            _ => {}
+        }
+    }
+
+    Err(ErrorKind::InvalidStringsdict(String::from("dict isn't closed")).into())
+}
+
+fn parse_text<I>(events: &mut I) -> Result<String, InnerError>
+where
+    I: Iterator<Item = Result<XmlEvent, xml::reader::Error>>,
+{
+    let mut text = String::new();
+    for event in events.by_ref() {
+        match event? {
+            XmlEvent::Characters(chars) | XmlEvent::CData(chars) | XmlEvent::Whitespace(chars) => {
+                text.push_str(&chars)
+            }
+            XmlEvent::EndElement { .. } => return Ok(text),
+            XmlEvent::StartElement { name, .. } => {
+                return Err(ErrorKind::InvalidStringsdict(format!(
+                    "Unexpected {} element in text",
+                    name.local_name
+                ))
+                .into())
+            }
+
+            _ => {}
+        }
+    }
+
+    Err(ErrorKind::InvalidStringsdict(String::from("Text element isn't closed")).into())
+}
+
+fn skip_element<I>(events: &mut I) -> Result<(), InnerError>
+where
+    I: Iterator<Item = Result<XmlEvent, xml::reader::Error>>,
+{
+    let mut depth = 1;
+    for event in events.by_ref() {
+        match event? {
+            XmlEvent::StartElement { .. } => depth += 1,
+            XmlEvent::EndElement { .. } => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    Err(ErrorKind::InvalidStringsdict(String::from("Element isn't closed")).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use test_utilities;
+
+    use crate::android_plurals::Quantity;
+
+    #[test]
+    fn reads_plurals() {
+        let plurals = super::read(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>apples</key>
+    <dict>
+        <key>NSStringLocalizedFormatKey</key>
+        <string>%#@value@</string>
+        <key>value</key>
+        <dict>
+            <key>NSStringFormatSpecTypeKey</key>
+            <string>NSStringPluralRuleType</string>
+            <key>NSStringFormatValueTypeKey</key>
+            <string>d</string>
+            <key>one</key>
+            <string>%d apple</string>
+            <key>other</key>
+            <string>%d apples</string>
+        </dict>
+    </dict>
+    <key>days_left</key>
+    <dict>
+        <key>NSStringLocalizedFormatKey</key>
+        <string>Only %#@days@ &amp; counting</string>
+        <key>days</key>
+        <dict>
+            <key>NSStringFormatSpecTypeKey</key>
+            <string>NSStringPluralRuleType</string>
+            <key>other</key>
+            <string>%d days</string>
+        </dict>
+    </dict>
+</dict>
+</plist>
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let mut apples = BTreeMap::new();
+        apples.insert(Quantity::One, String::from("%d apple"));
+        apples.insert(Quantity::Other, String::from("%d apples"));
+
+        let mut days_left = BTreeMap::new();
+        days_left.insert(Quantity::Other, String::from("Only %d days & counting"));
+
+        test_utilities::list::assert_strict_list_eq(
+            plurals,
+            vec![
+                (String::from("apples"), apples),
+                (String::from("days_left"), days_left),
+            ],
+        )
+    }
+
+    #[test]
+    fn errors_for_multiple_variables() {
+        let error = super::read(
+            r#"<plist><dict><key>p</key><dict>
+<key>NSStringLocalizedFormatKey</key><string>%#@a@ %#@b@</string>
+<key>a</key><dict><key>other</key><string>a</string></dict>
+<key>b</key><dict><key>other</key><string>b</string></dict>
+</dict></dict></plist>"#
+                .as_bytes(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "p should have exactly one variable. Found 2"
+        );
+        assert_eq!(error.kind().code(), "invalid_stringsdict")
+    }
+}
@@ -1,7 +1,10 @@
-pub use self::reader::read;
+pub use self::reader::{read_defined_names, read_with_plurals, DefinedName};
 
 mod event_handler;
 mod events_handler;
+mod placeholder_event_handler;
+mod plural_item_event_handler;
+mod plurals_event_handler;
 mod reader;
 mod resources_event_handler;
 mod root_event_handler;
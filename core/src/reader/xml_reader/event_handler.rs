@@ -1,12 +1,15 @@
 use xml::attribute::OwnedAttribute;
 
+use crate::android_plurals::{AndroidPlurals, Quantity};
 use crate::android_string::AndroidString;
 use crate::error::InnerError;
+use crate::reader::xml_reader::placeholder_event_handler::Placeholder;
 
-/// One instance of `EventHandler` is only expected to ever build one `AndroidString`
+/// One instance of `EventHandler` is only expected to ever build one `AndroidString`,
+/// one `AndroidPlurals`, one plural item or one placeholder
 pub trait EventHandler {
     fn build_handler(
-        &self,
+        &mut self,
         tag_name: String,
         attributes: Vec<OwnedAttribute>,
     ) -> Result<Box<dyn EventHandler>, InnerError>;
@@ -19,6 +22,14 @@ pub trait EventHandler {
         // No op
     }
 
+    fn handle_comment_event(&mut self, _text: String) {
+        // No op
+    }
+
+    fn handle_whitespace_event(&mut self, _text: String) {
+        // No op
+    }
+
     // It would be great if a way can be found to make this consume self instead of
     // just take in a reference. Compiler complains if this is made a consumer as
     // `EventHandler` is used as a trait object & boxed, un-sized objects can't be
@@ -26,4 +37,28 @@ pub trait EventHandler {
     fn built_string(&self) -> Option<AndroidString> {
         None
     }
+
+    fn built_plurals(&self) -> Option<AndroidPlurals> {
+        None
+    }
+
+    /// Plural items are handed over to the handler of the enclosing element
+    /// through `handle_plural_item`
+    fn built_plural_item(&self) -> Option<(Quantity, String)> {
+        None
+    }
+
+    fn handle_plural_item(&mut self, _quantity: Quantity, _value: String) {
+        // No op
+    }
+
+    /// Placeholders are handed over to the handler of the enclosing element
+    /// through `handle_placeholder`
+    fn built_placeholder(&self) -> Option<Placeholder> {
+        None
+    }
+
+    fn handle_placeholder(&mut self, _placeholder: Placeholder) {
+        // No op
+    }
 }
@@ -2,6 +2,7 @@ use xml::attribute::OwnedAttribute;
 
 use android_localization_utilities::DevExpt;
 
+use crate::android_plurals::AndroidPlurals;
 use crate::android_string::AndroidString;
 use crate::error::InnerError;
 use crate::reader::xml_reader::event_handler::EventHandler;
@@ -9,6 +10,7 @@ use crate::reader::xml_reader::root_event_handler::RootEventHandler;
 
 pub struct EventsHandler {
     android_strings: Vec<AndroidString>,
+    android_plurals: Vec<AndroidPlurals>,
     event_handlers: Vec<Box<dyn EventHandler>>,
 }
 
@@ -16,6 +18,7 @@ impl EventsHandler {
     pub fn new() -> EventsHandler {
         EventsHandler {
             android_strings: vec![],
+            android_plurals: vec![],
             event_handlers: vec![Box::new(RootEventHandler::new())],
         }
     }
@@ -48,16 +51,46 @@ impl EventsHandler {
             .handle_cdata_event(text);
     }
 
+    pub fn handle_comment_event(&mut self, text: String) {
+        self.event_handlers
+            .last_mut()
+            .expt("There must have been at least one event handler!")
+            .handle_comment_event(text);
+    }
+
+    pub fn handle_whitespace_event(&mut self, text: String) {
+        self.event_handlers
+            .last_mut()
+            .expt("There must have been at least one event handler!")
+            .handle_whitespace_event(text);
+    }
+
     pub fn handle_end_element_event(&mut self) {
         if let Some(event_handler) = self.event_handlers.pop() {
             if let Some(android_string) = event_handler.built_string() {
                 self.android_strings.push(android_string);
             }
+
+            if let Some(android_plurals) = event_handler.built_plurals() {
+                self.android_plurals.push(android_plurals);
+            }
+
+            if let Some((quantity, value)) = event_handler.built_plural_item() {
+                if let Some(parent_event_handler) = self.event_handlers.last_mut() {
+                    parent_event_handler.handle_plural_item(quantity, value);
+                }
+            }
+
+            if let Some(placeholder) = event_handler.built_placeholder() {
+                if let Some(parent_event_handler) = self.event_handlers.last_mut() {
+                    parent_event_handler.handle_placeholder(placeholder);
+                }
+            }
         }
     }
 
-    pub fn strings(mut self) -> Vec<AndroidString> {
+    pub fn strings_and_plurals(mut self) -> (Vec<AndroidString>, Vec<AndroidPlurals>) {
         self.event_handlers.clear();
-        self.android_strings
+        (self.android_strings, self.android_plurals)
     }
 }
@@ -0,0 +1,140 @@
+use xml::attribute::OwnedAttribute;
+
+use crate::constants;
+use crate::error::InnerError;
+use crate::reader::xml_reader::event_handler::EventHandler;
+use crate::reader::xml_reader::sinking_event_handler::SinkingEventHandler;
+
+/// Placeholder (`xliff:g`) in the value of a string or a plural item
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placeholder {
+    pub id: Option<String>,
+    pub example: Option<String>,
+
+    /// What the placeholder stands in for in the value (`%s`, `{user}` etc.)
+    pub content: String,
+}
+
+impl Placeholder {
+    /// Example of what the placeholder will be replaced with (if there is one)
+    /// to be shared with translators
+    pub fn build_context(&self) -> Option<String> {
+        let example = self.example.as_ref()?;
+        let subject = match (&self.id, self.content.is_empty()) {
+            (None, true) => return Some(format!("Example: {}", example)),
+            (None, false) => self.content.clone(),
+            (Some(id), true) => id.clone(),
+            (Some(id), false) => format!("{} ({})", id, self.content),
+        };
+
+        Some(format!("Example for {}: {}", subject, example))
+    }
+}
+
+pub struct PlaceholderEventHandler {
+    placeholder: Placeholder,
+}
+
+impl PlaceholderEventHandler {
+    pub fn build(attributes: Vec<OwnedAttribute>) -> PlaceholderEventHandler {
+        let mut id = None;
+        let mut example = None;
+        for attribute in attributes {
+            match attribute.name.local_name.as_str() {
+                constants::attributes::ID => id = Some(attribute.value),
+                constants::attributes::EXAMPLE => example = Some(attribute.value),
+                _ => {}
+            }
+        }
+
+        PlaceholderEventHandler {
+            placeholder: Placeholder {
+                id,
+                example,
+                content: String::new(),
+            },
+        }
+    }
+}
+
+impl EventHandler for PlaceholderEventHandler {
+    fn build_handler(
+        &mut self,
+        _tag_name: String,
+        _attributes: Vec<OwnedAttribute>,
+    ) -> Result<Box<dyn EventHandler>, InnerError> {
+        Ok(Box::new(SinkingEventHandler::new()))
+    }
+
+    fn handle_characters_event(&mut self, text: String) {
+        self.placeholder.content.push_str(&text)
+    }
+
+    fn handle_cdata_event(&mut self, text: String) {
+        self.placeholder
+            .content
+            .push_str(&format!("<![CDATA[{}]]>", text))
+    }
+
+    fn built_placeholder(&self) -> Option<Placeholder> {
+        Some(self.placeholder.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xml::attribute::OwnedAttribute;
+    use xml::name::OwnedName;
+
+    use crate::reader::xml_reader::event_handler::EventHandler;
+
+    use super::{Placeholder, PlaceholderEventHandler};
+
+    #[test]
+    fn builds_placeholder_with_its_content() {
+        let mut handler = PlaceholderEventHandler::build(vec![
+            OwnedAttribute::new(OwnedName::local("id"), "user"),
+            OwnedAttribute::new(OwnedName::local("example"), "Bob"),
+        ]);
+
+        handler.handle_characters_event(String::from("%s"));
+        assert_eq!(
+            handler.built_placeholder().unwrap(),
+            Placeholder {
+                id: Some(String::from("user")),
+                example: Some(String::from("Bob")),
+                content: String::from("%s"),
+            }
+        )
+    }
+
+    #[test]
+    fn builds_context_from_example() {
+        let build = |id: Option<&str>, example: Option<&str>, content: &str| {
+            Placeholder {
+                id: id.map(String::from),
+                example: example.map(String::from),
+                content: String::from(content),
+            }
+            .build_context()
+        };
+
+        assert_eq!(build(Some("user"), None, "%s"), None);
+        assert_eq!(
+            build(Some("user"), Some("Bob"), "%s"),
+            Some(String::from("Example for user (%s): Bob"))
+        );
+        assert_eq!(
+            build(Some("user"), Some("Bob"), ""),
+            Some(String::from("Example for user: Bob"))
+        );
+        assert_eq!(
+            build(None, Some("Bob"), "%s"),
+            Some(String::from("Example for %s: Bob"))
+        );
+        assert_eq!(
+            build(None, Some("Bob"), ""),
+            Some(String::from("Example: Bob"))
+        );
+    }
+}
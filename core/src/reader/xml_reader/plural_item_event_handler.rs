@@ -0,0 +1,111 @@
+use xml::attribute::OwnedAttribute;
+
+use crate::android_plurals::Quantity;
+use crate::constants;
+use crate::error::{ErrorKind, InnerError};
+use crate::reader::xml_reader::event_handler::EventHandler;
+use crate::reader::xml_reader::placeholder_event_handler::{Placeholder, PlaceholderEventHandler};
+use crate::reader::xml_reader::sinking_event_handler::SinkingEventHandler;
+
+pub struct PluralItemEventHandler {
+    quantity: Quantity,
+    value: String,
+}
+
+impl PluralItemEventHandler {
+    pub fn build(attributes: Vec<OwnedAttribute>) -> Result<PluralItemEventHandler, InnerError> {
+        let quantity = attributes
+            .into_iter()
+            .find(|attribute| attribute.name.local_name == constants::attributes::QUANTITY)
+            .map(|attribute| attribute.value);
+
+        match quantity {
+            None => Err(ErrorKind::InvalidStringsXml(String::from(
+                "item element is missing required quantity attribute",
+            ))
+            .into()),
+            Some(quantity) => match Quantity::from_name(&quantity) {
+                None => Err(ErrorKind::InvalidStringsXml(format!(
+                    "item element has unknown quantity: {}",
+                    quantity
+                ))
+                .into()),
+                Some(quantity) => Ok(PluralItemEventHandler {
+                    quantity,
+                    value: String::new(),
+                }),
+            },
+        }
+    }
+}
+
+impl EventHandler for PluralItemEventHandler {
+    fn build_handler(
+        &mut self,
+        tag_name: String,
+        attributes: Vec<OwnedAttribute>,
+    ) -> Result<Box<dyn EventHandler>, InnerError> {
+        if tag_name == constants::elements::XLIFF_G {
+            Ok(Box::new(PlaceholderEventHandler::build(attributes)))
+        } else {
+            Ok(Box::new(SinkingEventHandler::new()))
+        }
+    }
+
+    fn handle_characters_event(&mut self, text: String) {
+        self.value.push_str(&text)
+    }
+
+    fn handle_cdata_event(&mut self, text: String) {
+        self.value.push_str(&format!("<![CDATA[{}]]>", text))
+    }
+
+    /// Plurals carry no context, so only the content of the placeholder is kept
+    fn handle_placeholder(&mut self, placeholder: Placeholder) {
+        self.value.push_str(&placeholder.content)
+    }
+
+    fn built_plural_item(&self) -> Option<(Quantity, String)> {
+        Some((self.quantity, self.value.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xml::attribute::OwnedAttribute;
+    use xml::name::OwnedName;
+
+    use crate::android_plurals::Quantity;
+    use crate::reader::xml_reader::event_handler::EventHandler;
+
+    use super::PluralItemEventHandler;
+
+    #[test]
+    fn builds_item_with_character_and_cdata_events() {
+        let mut handler = PluralItemEventHandler::build(vec![build_quantity("few")]).unwrap();
+        handler.handle_characters_event(String::from("character event "));
+        handler.handle_cdata_event(String::from("cdata event"));
+        assert_eq!(
+            handler.built_plural_item().unwrap(),
+            (
+                Quantity::Few,
+                String::from("character event <![CDATA[cdata event]]>")
+            )
+        )
+    }
+
+    #[test]
+    fn errors_for_unknown_quantity() {
+        let error = PluralItemEventHandler::build(vec![build_quantity("several")])
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "item element has unknown quantity: several"
+        )
+    }
+
+    fn build_quantity(quantity: &str) -> OwnedAttribute {
+        OwnedAttribute::new(OwnedName::local("quantity"), quantity)
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use xml::attribute::OwnedAttribute;
+
+use crate::android_plurals::{AndroidPlurals, Quantity};
+use crate::constants;
+use crate::error::{ErrorKind, InnerError};
+use crate::reader::xml_reader::event_handler::EventHandler;
+use crate::reader::xml_reader::plural_item_event_handler::PluralItemEventHandler;
+use crate::reader::xml_reader::sinking_event_handler::SinkingEventHandler;
+
+pub struct PluralsEventHandler {
+    name: String,
+    is_localizable: bool,
+    items: BTreeMap<Quantity, String>,
+}
+
+impl PluralsEventHandler {
+    pub fn build(attributes: Vec<OwnedAttribute>) -> Result<PluralsEventHandler, InnerError> {
+        let mut plurals_name = None;
+        let mut is_localizable = true;
+        for attribute in attributes {
+            match attribute.name.local_name.as_str() {
+                constants::attributes::NAME => plurals_name = Some(attribute.value),
+                constants::attributes::LOCALIZABLE => {
+                    if let constants::flags::FALSE = attribute.value.as_str() {
+                        is_localizable = false
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match plurals_name {
+            None => Err(ErrorKind::InvalidStringsXml(String::from(
+                "plurals element is missing required name attribute",
+            ))
+            .into()),
+            Some(name) => Ok(PluralsEventHandler {
+                name,
+                is_localizable,
+                items: BTreeMap::new(),
+            }),
+        }
+    }
+}
+
+impl EventHandler for PluralsEventHandler {
+    fn build_handler(
+        &mut self,
+        tag_name: String,
+        attributes: Vec<OwnedAttribute>,
+    ) -> Result<Box<dyn EventHandler>, InnerError> {
+        match tag_name.as_str() {
+            constants::elements::ITEM => Ok(Box::new(PluralItemEventHandler::build(attributes)?)),
+            _ => Ok(Box::new(SinkingEventHandler::new())),
+        }
+    }
+
+    fn built_plurals(&self) -> Option<AndroidPlurals> {
+        Some(AndroidPlurals::new(
+            self.name.clone(),
+            self.items.clone(),
+            self.is_localizable,
+        ))
+    }
+
+    fn handle_plural_item(&mut self, quantity: Quantity, value: String) {
+        self.items.insert(quantity, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::android_plurals::{AndroidPlurals, Quantity};
+    use crate::reader::xml_reader::event_handler::EventHandler;
+
+    use super::PluralsEventHandler;
+
+    #[test]
+    fn builds_plurals_from_handed_over_items() {
+        let mut handler = PluralsEventHandler {
+            name: String::from("test_plurals"),
+            is_localizable: true,
+            items: BTreeMap::new(),
+        };
+
+        handler.handle_plural_item(Quantity::Other, String::from("%d items"));
+        handler.handle_plural_item(Quantity::One, String::from("%d item"));
+
+        assert_eq!(
+            handler.built_plurals().unwrap(),
+            AndroidPlurals::localizable(
+                "test_plurals",
+                vec![(Quantity::One, "%d item"), (Quantity::Other, "%d items")]
+            )
+        )
+    }
+}
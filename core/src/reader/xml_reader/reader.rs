@@ -1,16 +1,23 @@
 use std::io::BufReader;
 use std::io::Read;
 
+use xml::common::Position;
 use xml::reader::XmlEvent;
 use xml::ParserConfig;
 
+use crate::android_plurals::AndroidPlurals;
 use crate::android_string::AndroidString;
+use crate::constants;
 use crate::error::InnerError;
 use crate::reader::xml_reader::events_handler::EventsHandler;
 
-pub fn read<S: Read>(source: S) -> Result<Vec<AndroidString>, InnerError> {
+pub fn read_with_plurals<S: Read>(
+    source: S,
+) -> Result<(Vec<AndroidString>, Vec<AndroidPlurals>), InnerError> {
     let mut events_handler = EventsHandler::new();
-    let reader = ParserConfig::new().create_reader(BufReader::new(source));
+    let reader = ParserConfig::new()
+        .ignore_comments(false) // Comments carry context for translators
+        .create_reader(BufReader::new(source));
 
     for element_or_error in reader {
         match element_or_error {
@@ -21,13 +28,59 @@ pub fn read<S: Read>(source: S) -> Result<Vec<AndroidString>, InnerError> {
                 } => events_handler.handle_start_element_event(name.local_name, attributes)?,
                 XmlEvent::Characters(text) => events_handler.handle_characters_event(text),
                 XmlEvent::CData(text) => events_handler.handle_cdata_event(text),
+                XmlEvent::Comment(text) => events_handler.handle_comment_event(text),
+                XmlEvent::Whitespace(text) => events_handler.handle_whitespace_event(text),
                 XmlEvent::EndElement { .. } => events_handler.handle_end_element_event(),
                 _ => {} // No op for other events
             },
         }
     }
 
-    Ok(events_handler.strings())
+    Ok(events_handler.strings_and_plurals())
+}
+
+/// A `string` or `plurals` element defined right under `resources`
+#[derive(Debug, PartialEq)]
+pub struct DefinedName {
+    pub tag_name: String,
+    pub name: String,
+
+    /// 1-based line on which the element starts
+    pub line: u64,
+}
+
+/// Only reads the names of `string` & `plurals` elements along with where they
+/// are defined. Unlike `read_with_plurals`, this works with any resources file
+pub fn read_defined_names<S: Read>(source: S) -> Result<Vec<DefinedName>, InnerError> {
+    let mut reader = ParserConfig::new().create_reader(BufReader::new(source));
+    let mut defined_names = vec![];
+    let mut depth = 0;
+    loop {
+        match reader.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                depth += 1;
+                let is_named_element = name.local_name == constants::elements::STRING
+                    || name.local_name == constants::elements::PLURALS;
+                if depth == 2 && is_named_element {
+                    if let Some(attribute) = attributes
+                        .into_iter()
+                        .find(|attribute| attribute.name.local_name == constants::attributes::NAME)
+                    {
+                        defined_names.push(DefinedName {
+                            tag_name: name.local_name,
+                            name: attribute.value,
+                            line: reader.position().row + 1,
+                        })
+                    }
+                }
+            }
+            XmlEvent::EndElement { .. } => depth -= 1,
+            XmlEvent::EndDocument => return Ok(defined_names),
+            _ => {} // No op for other events
+        }
+    }
 }
 
 #[cfg(test)]
@@ -37,8 +90,68 @@ mod tests {
 
     use test_utilities;
 
+    use crate::android_plurals::{AndroidPlurals, Quantity};
     use crate::android_string::AndroidString;
 
+    use super::DefinedName;
+
+    #[test]
+    fn reads_context_of_strings() {
+        let strings = write_to_file_and_read_strings_out(
+            r##"
+			<?xml version="1.0" encoding="utf-8"?>
+			<resources xmlns:xliff="urn:oasis:names:tc:xliff:document:1.2">
+			    <!-- Shown as the subtitle -->
+			    <string name="string_1">string 1 value</string>
+			    <!-- Shown as the title -->
+			    <string name="string_2" description="Keep it short">string 2 value</string>
+			    <string name="string_3">Hi <xliff:g id="user" example="Bob">%s</xliff:g></string>
+			</resources>
+		"##,
+        );
+
+        assert_strings_with_context(
+            strings,
+            vec![
+                ("string_1", "string 1 value", Some("Shown as the subtitle")),
+                (
+                    "string_2",
+                    "string 2 value",
+                    Some("Shown as the title\nKeep it short"),
+                ),
+                ("string_3", "Hi %s", Some("Example for user (%s): Bob")),
+            ],
+        )
+    }
+
+    #[test]
+    fn reads_only_comments_right_above_strings_as_context() {
+        let strings = write_to_file_and_read_strings_out(
+            r##"
+			<?xml version="1.0" encoding="utf-8"?>
+			<resources>
+			    <!-- Settings screen -->
+
+			    <string name="string_1">string 1 value</string>
+			    <!-- Shown as the title of the plurals -->
+			    <plurals name="plurals_1"><item quantity="other">Files</item></plurals>
+			    <string name="string_2">string 2 value</string>
+			    <!-- Shown as the subtitle -->
+			    <string name="string_3">string 3 value</string>
+			</resources>
+		"##,
+        );
+
+        assert_strings_with_context(
+            strings,
+            vec![
+                ("string_1", "string 1 value", None),
+                ("string_2", "string 2 value", None),
+                ("string_3", "string 3 value", Some("Shown as the subtitle")),
+            ],
+        )
+    }
+
     #[test]
     fn reads_strings_from_valid_clean_file() {
         let strings = write_to_file_and_read_strings_out(
@@ -141,6 +254,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reads_plurals_along_with_strings() {
+        let mut tmpfile: File = tempfile::tempfile().unwrap();
+        tmpfile
+            .write_all(
+                r##"
+            <?xml version="1.0" encoding="utf-8"?>
+            <resources xmlns:xliff="urn:oasis:names:tc:xliff:document:1.2">
+                <string name="s1">string value</string>
+                <plurals name="p1">
+                    <item quantity="one"><xliff:g id="count" example="1">%d</xliff:g> item</item>
+                    <item quantity="other">%d items</item>
+                </plurals>
+                <plurals name="p2" translatable="false">
+                    <item quantity="other"><![CDATA[<b>%d</b>]]></item>
+                </plurals>
+            </resources>
+        "##
+                .as_bytes(),
+            )
+            .unwrap();
+        tmpfile.seek(SeekFrom::Start(0)).unwrap();
+
+        let (strings, plurals) = super::read_with_plurals(tmpfile).unwrap();
+        test_utilities::list::assert_strict_list_eq(
+            strings,
+            vec![AndroidString::localizable("s1", "string value")],
+        );
+
+        test_utilities::list::assert_strict_list_eq(
+            plurals,
+            vec![
+                AndroidPlurals::localizable(
+                    "p1",
+                    vec![(Quantity::One, "%d item"), (Quantity::Other, "%d items")],
+                ),
+                AndroidPlurals::unlocalizable(
+                    "p2",
+                    vec![(Quantity::Other, "<![CDATA[<b>%d</b>]]>")],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn reads_defined_names_with_lines() {
+        let defined_names = super::read_defined_names(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <string name="s1">value</string>
+    <plurals name="p1">
+        <item quantity="other">value</item>
+    </plurals>
+    <color name="c1">#FFF</color>
+    <string
+        name="s2">value</string>
+</resources>"#
+                .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            defined_names,
+            vec![
+                DefinedName {
+                    tag_name: String::from("string"),
+                    name: String::from("s1"),
+                    line: 3,
+                },
+                DefinedName {
+                    tag_name: String::from("plurals"),
+                    name: String::from("p1"),
+                    line: 4,
+                },
+                DefinedName {
+                    tag_name: String::from("string"),
+                    name: String::from("s2"),
+                    line: 8,
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn errors_for_strings_without_name() {
+        let error = super::read_with_plurals(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <string>value</string>
+</resources>"#
+                .as_bytes(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "string element is missing required name attribute"
+        );
+        assert_eq!(error.kind().code(), "invalid_strings_xml")
+    }
+
+    /// Context isn't part of the equality of strings & is compared explicitly
+    fn assert_strings_with_context(
+        strings: Vec<AndroidString>,
+        expected: Vec<(&str, &str, Option<&str>)>,
+    ) {
+        test_utilities::list::assert_strict_list_eq(
+            strings
+                .iter()
+                .map(|s| (s.name(), s.value(), s.context()))
+                .collect::<Vec<(&str, &str, Option<&str>)>>(),
+            expected,
+        )
+    }
+
     fn write_to_file_and_read_strings_out(file_content: &str) -> Vec<AndroidString> {
         // Write content to file
         let mut tmpfile: File = tempfile::tempfile().unwrap();
@@ -150,6 +378,8 @@ mod tests {
         tmpfile.seek(SeekFrom::Start(0)).unwrap();
 
         // Read strings from file
-        super::read(tmpfile.try_clone().unwrap()).unwrap()
+        super::read_with_plurals(tmpfile.try_clone().unwrap())
+            .unwrap()
+            .0
     }
 }
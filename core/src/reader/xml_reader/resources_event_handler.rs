@@ -3,26 +3,89 @@ use xml::attribute::OwnedAttribute;
 use crate::constants;
 use crate::error::InnerError;
 use crate::reader::xml_reader::event_handler::EventHandler;
+use crate::reader::xml_reader::plurals_event_handler::PluralsEventHandler;
 use crate::reader::xml_reader::sinking_event_handler::SinkingEventHandler;
 use crate::reader::xml_reader::string_event_handler::StringEventHandler;
 
-pub struct ResourcesEventHandler {}
+/// Comments are only attached to the element that immediately follows them. A
+/// blank line after a comment (like after a section header) detaches it
+pub struct ResourcesEventHandler {
+    pending_comment: Option<String>,
+}
 
 impl ResourcesEventHandler {
     pub fn new() -> ResourcesEventHandler {
-        ResourcesEventHandler {}
+        ResourcesEventHandler {
+            pending_comment: None,
+        }
     }
 }
 
 impl EventHandler for ResourcesEventHandler {
     fn build_handler(
-        &self,
+        &mut self,
         tag_name: String,
         attributes: Vec<OwnedAttribute>,
     ) -> Result<Box<dyn EventHandler>, InnerError> {
+        let comment = self.pending_comment.take();
         match tag_name.as_str() {
-            constants::elements::STRING => Ok(Box::new(StringEventHandler::build(attributes)?)),
+            constants::elements::STRING => {
+                Ok(Box::new(StringEventHandler::build(attributes, comment)?))
+            }
+            constants::elements::PLURALS => Ok(Box::new(PluralsEventHandler::build(attributes)?)),
             _ => Ok(Box::new(SinkingEventHandler::new())),
         }
     }
+
+    fn handle_comment_event(&mut self, text: String) {
+        let text = text.trim();
+        if !text.is_empty() {
+            self.pending_comment = Some(match self.pending_comment.take() {
+                None => String::from(text),
+                Some(comment) => format!("{}\n{}", comment, text),
+            })
+        }
+    }
+
+    fn handle_whitespace_event(&mut self, text: String) {
+        if text.matches('\n').count() > 1 {
+            self.pending_comment = None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reader::xml_reader::event_handler::EventHandler;
+
+    use super::ResourcesEventHandler;
+
+    #[test]
+    fn comments_are_only_handed_to_the_next_element() {
+        let mut handler = ResourcesEventHandler::new();
+        handler.handle_comment_event(String::from(" first comment "));
+        handler.handle_comment_event(String::from(" second comment "));
+        assert_eq!(
+            handler.pending_comment,
+            Some(String::from("first comment\nsecond comment"))
+        );
+
+        handler
+            .build_handler(String::from("string"), vec![])
+            .err()
+            .unwrap();
+        assert_eq!(handler.pending_comment, None)
+    }
+
+    #[test]
+    fn comments_followed_by_a_blank_line_are_dropped() {
+        let mut handler = ResourcesEventHandler::new();
+        handler.handle_comment_event(String::from(" section "));
+        handler.handle_whitespace_event(String::from("\n    \n    "));
+        assert_eq!(handler.pending_comment, None);
+
+        handler.handle_comment_event(String::from(" comment "));
+        handler.handle_whitespace_event(String::from("\n    "));
+        assert_eq!(handler.pending_comment, Some(String::from("comment")))
+    }
 }
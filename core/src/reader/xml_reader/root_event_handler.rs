@@ -16,7 +16,7 @@ impl RootEventHandler {
 
 impl EventHandler for RootEventHandler {
     fn build_handler(
-        &self,
+        &mut self,
         tag_name: String,
         _attributes: Vec<OwnedAttribute>,
     ) -> Result<Box<dyn EventHandler>, InnerError> {
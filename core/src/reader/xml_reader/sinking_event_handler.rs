@@ -13,7 +13,7 @@ impl SinkingEventHandler {
 
 impl EventHandler for SinkingEventHandler {
     fn build_handler(
-        &self,
+        &mut self,
         _tag_name: String,
         _attributes: Vec<OwnedAttribute>,
     ) -> Result<Box<dyn EventHandler>, InnerError> {
@@ -2,20 +2,29 @@ use xml::attribute::OwnedAttribute;
 
 use crate::android_string::AndroidString;
 use crate::constants;
-use crate::error::InnerError;
+use crate::error::{ErrorKind, InnerError};
 use crate::reader::xml_reader::event_handler::EventHandler;
+use crate::reader::xml_reader::placeholder_event_handler::{Placeholder, PlaceholderEventHandler};
 use crate::reader::xml_reader::sinking_event_handler::SinkingEventHandler;
 
 pub struct StringEventHandler {
     name: String,
     is_localizable: bool,
-    built_android_string: Option<AndroidString>,
+    context_parts: Vec<String>,
+    max_length: Option<usize>,
+    value: Option<String>,
 }
 
 impl StringEventHandler {
-    pub fn build(attributes: Vec<OwnedAttribute>) -> Result<StringEventHandler, InnerError> {
+    /// `comment` is the one immediately preceding the string element (if any)
+    pub fn build(
+        attributes: Vec<OwnedAttribute>,
+        comment: Option<String>,
+    ) -> Result<StringEventHandler, InnerError> {
         let mut string_name = None;
         let mut is_localizable = true;
+        let mut context_parts: Vec<String> = comment.into_iter().collect();
+        let mut max_length = None;
         for attribute in attributes {
             match attribute.name.local_name.as_str() {
                 constants::attributes::NAME => string_name = Some(attribute.value),
@@ -24,41 +33,66 @@ impl StringEventHandler {
                         is_localizable = false
                     }
                 }
+                constants::attributes::DESCRIPTION if !attribute.value.trim().is_empty() => {
+                    context_parts.push(String::from(attribute.value.trim()))
+                }
+                constants::attributes::MAX_LENGTH => match attribute.value.trim().parse() {
+                    Ok(length) => max_length = Some(length),
+                    Err(_) => {
+                        return Err(ErrorKind::InvalidStringsXml(format!(
+                            "{} should be a non negative number. Found: {}",
+                            constants::attributes::MAX_LENGTH,
+                            attribute.value
+                        ))
+                        .into())
+                    }
+                },
                 _ => {}
             }
         }
 
         match string_name {
-            None => Err("string element is missing required name attribute".into()),
+            None => Err(ErrorKind::InvalidStringsXml(String::from(
+                "string element is missing required name attribute",
+            ))
+            .into()),
             Some(name) => Ok(StringEventHandler {
                 name,
                 is_localizable,
-                built_android_string: None,
+                context_parts,
+                max_length,
+                value: None,
             }),
         }
     }
 
+    fn build_context(&self) -> Option<String> {
+        if self.context_parts.is_empty() {
+            None
+        } else {
+            Some(self.context_parts.join("\n"))
+        }
+    }
+
     fn append_or_create_string(&mut self, text: String) {
-        let text = match &self.built_android_string {
+        self.value = Some(match self.value.take() {
             None => text,
-            Some(s) => format!("{}{}", s.value(), text),
-        };
-
-        self.built_android_string = Some(AndroidString::new(
-            self.name.clone(),
-            text,
-            self.is_localizable,
-        ));
+            Some(value) => format!("{}{}", value, text),
+        });
     }
 }
 
 impl EventHandler for StringEventHandler {
     fn build_handler(
-        &self,
-        _tag_name: String,
-        _attributes: Vec<OwnedAttribute>,
+        &mut self,
+        tag_name: String,
+        attributes: Vec<OwnedAttribute>,
     ) -> Result<Box<dyn EventHandler>, InnerError> {
-        Ok(Box::new(SinkingEventHandler::new()))
+        if tag_name == constants::elements::XLIFF_G {
+            Ok(Box::new(PlaceholderEventHandler::build(attributes)))
+        } else {
+            Ok(Box::new(SinkingEventHandler::new()))
+        }
     }
 
     fn handle_characters_event(&mut self, text: String) {
@@ -69,14 +103,29 @@ impl EventHandler for StringEventHandler {
         self.append_or_create_string(format!("<![CDATA[{}]]>", text))
     }
 
+    /// The content of the placeholder stays in the value & its example goes
+    /// into the context
+    fn handle_placeholder(&mut self, placeholder: Placeholder) {
+        self.context_parts.extend(placeholder.build_context());
+        self.append_or_create_string(placeholder.content)
+    }
+
     fn built_string(&self) -> Option<AndroidString> {
-        self.built_android_string.clone()
+        self.value.as_ref().map(|value| {
+            AndroidString::new(self.name.clone(), value.clone(), self.is_localizable)
+                .with_context(self.build_context())
+                .with_max_length(self.max_length)
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use xml::attribute::OwnedAttribute;
+    use xml::name::OwnedName;
+
     use crate::reader::xml_reader::event_handler::EventHandler;
+    use crate::reader::xml_reader::placeholder_event_handler::Placeholder;
 
     use super::StringEventHandler;
 
@@ -123,14 +172,79 @@ mod tests {
         assert_string(handler, "character event 1 <![CDATA[cdata event 1]]> character event 2 <![CDATA[cdata event 2]]> <![CDATA[cdata event 3]]> character event 3")
     }
 
+    #[test]
+    fn builds_context_from_comment_description_and_examples() {
+        let mut handler = StringEventHandler::build(
+            vec![
+                build_attribute("name", "test_string"),
+                build_attribute("description", "Shown on the home screen"),
+            ],
+            Some(String::from("Greets the user")),
+        )
+        .unwrap();
+
+        handler.handle_characters_event(String::from("Hello "));
+        handler.handle_placeholder(Placeholder {
+            id: Some(String::from("user_name")),
+            example: Some(String::from("Bob")),
+            content: String::from("%s"),
+        });
+
+        let string = handler.built_string().unwrap();
+        assert_eq!(string.value(), "Hello %s");
+        assert_eq!(
+            string.context(),
+            Some("Greets the user\nShown on the home screen\nExample for user_name (%s): Bob")
+        )
+    }
+
+    #[test]
+    fn builds_string_with_max_length() {
+        let mut handler = StringEventHandler::build(
+            vec![
+                build_attribute("name", "test_string"),
+                build_attribute("maxLength", "12"),
+            ],
+            None,
+        )
+        .unwrap();
+
+        handler.handle_characters_event(String::from("Short"));
+        assert_eq!(handler.built_string().unwrap().max_length(), Some(12))
+    }
+
+    #[test]
+    fn errors_for_invalid_max_length() {
+        let error = StringEventHandler::build(
+            vec![
+                build_attribute("name", "test_string"),
+                build_attribute("maxLength", "twelve"),
+            ],
+            None,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "maxLength should be a non negative number. Found: twelve"
+        )
+    }
+
     fn build_event_handler() -> StringEventHandler {
         StringEventHandler {
             name: String::from("test_string"),
             is_localizable: true,
-            built_android_string: None,
+            context_parts: vec![],
+            max_length: None,
+            value: None,
         }
     }
 
+    fn build_attribute(name: &str, value: &str) -> OwnedAttribute {
+        OwnedAttribute::new(OwnedName::local(name), value)
+    }
+
     fn assert_string(handler: StringEventHandler, expected: &str) {
         assert_eq!(handler.built_string().unwrap().value(), expected)
     }
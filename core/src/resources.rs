@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, ResultExt};
+use crate::ops::dedup;
+use crate::ops::filter;
+use crate::ops::merge;
+use crate::ops::sort;
+use crate::reader::xml_reader;
+use crate::util::foreign_locale_ids_finder;
+use crate::util::staged_files::StagedFiles;
+use crate::util::two_pointer_traversal;
+use crate::util::xml_utilities;
+use crate::writer::xml_writer;
+
+pub use crate::android_plurals::{AndroidPlurals, Quantity};
+pub use crate::android_string::AndroidString;
+
+/// Strings & plurals of a single `strings.xml` file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StringsFile {
+    strings: Vec<AndroidString>,
+    plurals: Vec<AndroidPlurals>,
+}
+
+impl StringsFile {
+    pub fn new(strings: Vec<AndroidString>, plurals: Vec<AndroidPlurals>) -> StringsFile {
+        StringsFile { strings, plurals }
+    }
+
+    /// Reads a `strings.xml` document. Elements other than `string` & `plurals`
+    /// are skipped. Errors are reported against `context` (like the path the
+    /// document is read from)
+    pub fn read<R: Read, P: Into<PathBuf>>(source: R, context: P) -> Result<StringsFile, Error> {
+        xml_reader::read_with_plurals(source)
+            .map(|(strings, plurals)| StringsFile::new(strings, plurals))
+            .with_context(context)
+    }
+
+    /// Writes out a `strings.xml` document. Plurals are written out after all
+    /// the strings. Errors are reported against `context` (like the path the
+    /// document is written to)
+    pub fn write<W: Write, P: Into<PathBuf>>(&self, sink: &mut W, context: P) -> Result<(), Error> {
+        xml_writer::write_with_plurals(sink, self.strings.clone(), self.plurals.clone())
+            .with_context(context)
+    }
+
+    pub fn strings(&self) -> &[AndroidString] {
+        &self.strings
+    }
+
+    pub fn plurals(&self) -> &[AndroidPlurals] {
+        &self.plurals
+    }
+
+    pub fn into_strings_and_plurals(self) -> (Vec<AndroidString>, Vec<AndroidPlurals>) {
+        (self.strings, self.plurals)
+    }
+}
+
+/// All the `strings.xml` files of a `res` dir. Foreign strings files are keyed
+/// by their locale ID (`fr` for `values-fr`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceSet {
+    res_dir_path: PathBuf,
+    default_strings: StringsFile,
+    foreign_strings: BTreeMap<String, StringsFile>,
+}
+
+impl ResourceSet {
+    pub fn read<P: AsRef<Path>>(res_dir_path: P) -> Result<ResourceSet, Error> {
+        let path = res_dir_path.as_ref();
+        let mut foreign_strings = BTreeMap::new();
+        for locale_id in foreign_locale_ids_finder::find(path)? {
+            let (strings, plurals) =
+                xml_utilities::read_foreign_strings(path, &locale_id)?.into_strings_and_plurals();
+            foreign_strings.insert(locale_id, StringsFile::new(strings, plurals));
+        }
+
+        let (strings, plurals) =
+            xml_utilities::read_default_strings(path)?.into_strings_and_plurals();
+        Ok(ResourceSet {
+            res_dir_path: path.to_path_buf(),
+            default_strings: StringsFile::new(strings, plurals),
+            foreign_strings,
+        })
+    }
+
+    pub fn res_dir_path(&self) -> &Path {
+        &self.res_dir_path
+    }
+
+    pub fn default_strings(&self) -> &StringsFile {
+        &self.default_strings
+    }
+
+    /// Sorted locale IDs of all the foreign strings files
+    pub fn locale_ids(&self) -> Vec<&str> {
+        self.foreign_strings.keys().map(String::as_str).collect()
+    }
+
+    pub fn foreign_strings(&self, locale_id: &str) -> Option<&StringsFile> {
+        self.foreign_strings.get(locale_id)
+    }
+
+    /// Writes the strings file into the `res` dir (creating the values dir if
+    /// required) & keeps it around in this set. The existing file is only
+    /// replaced once the new one is fully written out. Returns the path of the file
+    pub fn write_foreign_strings(
+        &mut self,
+        locale_id: &str,
+        strings_file: StringsFile,
+    ) -> Result<PathBuf, Error> {
+        let path = xml_utilities::foreign_strings_file_path(&self.res_dir_path, locale_id);
+        let mut staged_files = StagedFiles::new();
+        strings_file.write(&mut staged_files.stage(path.clone())?, path.clone())?;
+        staged_files.commit()?;
+        self.foreign_strings
+            .insert(String::from(locale_id), strings_file);
+        Ok(path)
+    }
+}
+
+/// Strings that are in one list but not in the other (matched by name)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diff {
+    /// Localizable default strings that the foreign strings don't have
+    pub missing: Vec<AndroidString>,
+
+    /// Foreign strings that aren't in the default strings
+    pub extra: Vec<AndroidString>,
+}
+
+/// Strings that can't be localized (`translatable="false"`) are dropped
+pub fn filter_localizable(strings: &[AndroidString]) -> Vec<AndroidString> {
+    filter::find_localizable_strings(strings.to_vec())
+}
+
+/// Returns strings from both the lists sorted by name. Strings from `preferred`
+/// are kept in case both the lists have strings with the same name
+pub fn merge(preferred: &[AndroidString], others: &[AndroidString]) -> Vec<AndroidString> {
+    let mut merged = merge::merge_and_group_strings(&mut preferred.to_vec(), &mut others.to_vec());
+    dedup::dedup_grouped_strings(&mut merged);
+    merged
+}
+
+/// Both the missing & the extra strings are sorted by name
+pub fn diff(default_strings: &[AndroidString], foreign_strings: &[AndroidString]) -> Diff {
+    let mut default_strings = default_strings.to_vec();
+    let mut foreign_strings = foreign_strings.to_vec();
+    sort::sort_android_strings_by_name(&mut default_strings);
+    sort::sort_android_strings_by_name(&mut foreign_strings);
+
+    let mut missing = vec![];
+    let mut extra = vec![];
+    two_pointer_traversal::diff(
+        &default_strings,
+        &foreign_strings,
+        |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+        |default_string| {
+            if default_string.is_localizable() {
+                missing.push(default_string.clone())
+            }
+        },
+        |foreign_string| extra.push(foreign_string.clone()),
+    );
+
+    Diff { missing, extra }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use test_utilities;
+
+    use crate::android_string::AndroidString;
+    use crate::writer::xml_writer;
+
+    use super::Diff;
+    use super::ResourceSet;
+    use super::StringsFile;
+
+    #[test]
+    fn reads_and_writes_strings_file() {
+        let strings_file = StringsFile::new(
+            vec![
+                AndroidString::localizable("s1", "value 1"),
+                AndroidString::unlocalizable("s2", "value 2"),
+            ],
+            vec![],
+        );
+
+        let mut sink = vec![];
+        strings_file.write(&mut sink, "strings.xml").unwrap();
+        assert_eq!(
+            StringsFile::read(sink.as_slice(), "strings.xml").unwrap(),
+            strings_file
+        );
+    }
+
+    #[test]
+    fn reads_and_writes_resource_set() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut res_path = temp_dir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        let mut fr_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_path.clone(), "fr");
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "value")],
+        )
+        .unwrap();
+        xml_writer::write(&mut fr_strings.file, vec![]).unwrap();
+        default_strings.file.flush().unwrap();
+        fr_strings.file.flush().unwrap();
+
+        let mut resource_set = ResourceSet::read(res_path.to_str().unwrap()).unwrap();
+        assert_eq!(resource_set.locale_ids(), vec!["fr"]);
+        assert_eq!(
+            resource_set.default_strings().strings(),
+            &[AndroidString::localizable("s1", "value")]
+        );
+        assert_eq!(
+            resource_set.foreign_strings("fr"),
+            Some(&StringsFile::default())
+        );
+
+        let es_strings_file =
+            StringsFile::new(vec![AndroidString::localizable("s1", "valor")], vec![]);
+        resource_set
+            .write_foreign_strings("es", es_strings_file.clone())
+            .unwrap();
+
+        let resource_set = ResourceSet::read(res_path.to_str().unwrap()).unwrap();
+        assert_eq!(resource_set.locale_ids(), vec!["es", "fr"]);
+        assert_eq!(resource_set.foreign_strings("es"), Some(&es_strings_file));
+    }
+
+    #[test]
+    fn filters_localizable_strings() {
+        test_utilities::list::assert_strict_list_eq(
+            super::filter_localizable(&[
+                AndroidString::localizable("s1", "value"),
+                AndroidString::unlocalizable("s2", "value"),
+            ]),
+            vec![AndroidString::localizable("s1", "value")],
+        )
+    }
+
+    #[test]
+    fn merges_strings() {
+        test_utilities::list::assert_strict_list_eq(
+            super::merge(
+                &[
+                    AndroidString::localizable("s2", "preferred"),
+                    AndroidString::localizable("s1", "preferred"),
+                ],
+                &[
+                    AndroidString::localizable("s3", "other"),
+                    AndroidString::localizable("s2", "other"),
+                ],
+            ),
+            vec![
+                AndroidString::localizable("s1", "preferred"),
+                AndroidString::localizable("s2", "preferred"),
+                AndroidString::localizable("s3", "other"),
+            ],
+        )
+    }
+
+    #[test]
+    fn diffs_strings() {
+        assert_eq!(
+            super::diff(
+                &[
+                    AndroidString::localizable("s3", "default"),
+                    AndroidString::localizable("s1", "default"),
+                    AndroidString::unlocalizable("s2", "default"),
+                ],
+                &[
+                    AndroidString::localizable("s4", "foreign"),
+                    AndroidString::localizable("s1", "foreign"),
+                ],
+            ),
+            Diff {
+                missing: vec![AndroidString::localizable("s3", "default")],
+                extra: vec![AndroidString::localizable("s4", "foreign")],
+            }
+        )
+    }
+}
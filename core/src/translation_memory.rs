@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::android_string::AndroidString;
+use crate::error::Error;
+use crate::ops::sort;
+use crate::util::foreign_locale_ids_finder;
+use crate::util::two_pointer_traversal;
+use crate::util::xml_utilities;
+
+/// How the translation memory should be put to use while localizing
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// Res dirs of other modules whose translations should also be remembered
+    pub extra_res_dir_paths: Vec<PathBuf>,
+
+    /// Exact matches are written straight into the `strings.xml` files instead
+    /// of being sent off (pre-filled) to be localized
+    pub auto_apply: bool,
+}
+
+/// How much of the texts to be localized could be filled in from the memory
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leverage {
+    pub locale: String,
+    pub strings_count: usize,
+    pub words_count: usize,
+    pub matched_strings_count: usize,
+    pub matched_words_count: usize,
+}
+
+impl Leverage {
+    pub(crate) fn new(locale: String) -> Leverage {
+        Leverage {
+            locale,
+            strings_count: 0,
+            words_count: 0,
+            matched_strings_count: 0,
+            matched_words_count: 0,
+        }
+    }
+
+    pub(crate) fn count(&mut self, default_string: &AndroidString, is_matched: bool) {
+        let words_count = default_string.value().split_whitespace().count();
+        self.strings_count += 1;
+        self.words_count += words_count;
+        if is_matched {
+            self.matched_strings_count += 1;
+            self.matched_words_count += words_count;
+        }
+    }
+}
+
+/// Remembers how default texts have already been translated into every locale.
+/// If the same default text has been translated differently, the first one found
+/// is remembered (res dirs are looked into in the order they are passed in)
+pub(crate) struct TranslationMemory {
+    locale_id_to_translations: HashMap<String, HashMap<String, String>>,
+}
+
+impl TranslationMemory {
+    pub(crate) fn build(res_dir_paths: &[&Path]) -> Result<TranslationMemory, Error> {
+        let mut memory = TranslationMemory {
+            locale_id_to_translations: HashMap::new(),
+        };
+
+        for res_dir_path in res_dir_paths {
+            let mut default_strings =
+                xml_utilities::read_default_strings(res_dir_path)?.into_strings();
+            sort::sort_android_strings_by_name(&mut default_strings);
+
+            for locale_id in foreign_locale_ids_finder::find(res_dir_path)? {
+                let mut foreign_strings =
+                    xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?.into_strings();
+
+                memory.remember(locale_id, &default_strings, &mut foreign_strings);
+            }
+        }
+
+        Ok(memory)
+    }
+
+    /// Default strings are expected to be sorted by name
+    fn remember(
+        &mut self,
+        locale_id: String,
+        default_strings: &[AndroidString],
+        foreign_strings: &mut [AndroidString],
+    ) {
+        sort::sort_android_strings_by_name(foreign_strings);
+        let translations = self.locale_id_to_translations.entry(locale_id).or_default();
+
+        two_pointer_traversal::compare(
+            default_strings,
+            foreign_strings,
+            |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+            |default_string, foreign_string| {
+                if default_string.is_localizable()
+                    && !default_string.is_reference()
+                    && !foreign_string.value().is_empty()
+                {
+                    translations
+                        .entry(String::from(default_string.value()))
+                        .or_insert_with(|| String::from(foreign_string.value()));
+                }
+            },
+        );
+    }
+
+    pub(crate) fn find(&self, locale_id: &str, default_text: &str) -> Option<&str> {
+        self.locale_id_to_translations
+            .get(locale_id)
+            .and_then(|translations| translations.get(default_text))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use test_utilities;
+
+    use crate::android_string::AndroidString;
+    use crate::writer::xml_writer;
+
+    use super::Leverage;
+    use super::TranslationMemory;
+
+    #[test]
+    fn builds_memory_across_res_dirs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut app_res_path = temp_dir.path().to_path_buf();
+        app_res_path.push("app");
+        let mut lib_res_path = temp_dir.path().to_path_buf();
+        lib_res_path.push("lib");
+
+        for (res_path, default_strings, french_strings) in [
+            (
+                app_res_path.clone(),
+                vec![
+                    AndroidString::localizable("cancel", "Cancel"),
+                    AndroidString::localizable("new", "New"),
+                    AndroidString::unlocalizable("app_name", "App"),
+                ],
+                vec![
+                    AndroidString::localizable("cancel", "Annuler"),
+                    AndroidString::localizable("app_name", "Appli"),
+                ],
+            ),
+            (
+                lib_res_path.clone(),
+                vec![
+                    AndroidString::localizable("dismiss", "Cancel"),
+                    AndroidString::localizable("ok", "OK"),
+                ],
+                vec![
+                    AndroidString::localizable("dismiss", "Abandonner"),
+                    AndroidString::localizable("ok", "D'accord"),
+                ],
+            ),
+        ] {
+            let mut default_file =
+                test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+            let mut french_file =
+                test_utilities::res::setup_empty_strings_for_locale(res_path, "fr");
+
+            xml_writer::write(&mut default_file.file, default_strings).unwrap();
+            xml_writer::write(&mut french_file.file, french_strings).unwrap();
+            default_file.file.flush().unwrap();
+            french_file.file.flush().unwrap();
+        }
+
+        let memory =
+            TranslationMemory::build(&[app_res_path.as_path(), lib_res_path.as_path()]).unwrap();
+
+        assert_eq!(memory.find("fr", "Cancel"), Some("Annuler"));
+        assert_eq!(memory.find("fr", "OK"), Some("D'accord"));
+        assert_eq!(memory.find("fr", "New"), None);
+        assert_eq!(memory.find("fr", "App"), None);
+        assert_eq!(memory.find("es", "Cancel"), None);
+    }
+
+    #[test]
+    fn counts_leverage() {
+        let mut leverage = Leverage::new(String::from("french"));
+        leverage.count(&AndroidString::localizable("s1", "Cancel this"), true);
+        leverage.count(&AndroidString::localizable("s2", "Go  back now"), false);
+
+        assert_eq!(
+            leverage,
+            Leverage {
+                locale: String::from("french"),
+                strings_count: 2,
+                words_count: 5,
+                matched_strings_count: 1,
+                matched_words_count: 2,
+            }
+        )
+    }
+}
@@ -1,3 +1,8 @@
+pub mod apple_text_converter;
 pub mod foreign_locale_ids_finder;
+pub mod staged_files;
+pub mod text_decoder;
+pub mod text_similarity;
 pub mod two_pointer_traversal;
+pub mod utc_time;
 pub mod xml_utilities;
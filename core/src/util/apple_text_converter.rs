@@ -0,0 +1,171 @@
+//! Android & Apple differ in how they escape text & how they specify string
+//! arguments in format strings (Android: `%1$s`; Apple: `%1$@`). These help in
+//! moving text between the two platforms
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+const CDATA_START: &str = "<![CDATA[";
+const CDATA_END: &str = "]]>";
+
+/// Converts text as it is read out of an Android strings file into text that
+/// could be written out to an Apple strings file
+pub fn to_apple(android_text: &str) -> String {
+    let android_text = android_text.replace(CDATA_START, "").replace(CDATA_END, "");
+
+    let mut apple_text = String::new();
+    let mut chars = android_text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => apple_text.push('\n'),
+                Some('t') => apple_text.push('\t'),
+                Some('u') => {
+                    let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+                    match u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(std::char::from_u32)
+                    {
+                        Some(unicode_char) => apple_text.push(unicode_char),
+                        None => apple_text.push_str(&format!("\\u{}", hex)),
+                    }
+                }
+
+                Some(escaped_char) => apple_text.push(escaped_char),
+                None => apple_text.push('\\'),
+            },
+
+            // Unescaped double quotes are used by Android to preserve whitespace
+            '"' => {}
+            '%' => {
+                push_format_specifier(&mut apple_text, &mut chars, |conversion| match conversion {
+                    's' | 'S' => Some('@'),
+                    other => Some(other),
+                })
+            }
+
+            c => apple_text.push(c),
+        }
+    }
+
+    apple_text
+}
+
+/// Converts text as it is read out of an Apple strings file into text that
+/// could be written out to an Android strings file
+pub fn to_android(apple_text: &str) -> String {
+    let mut android_text = String::new();
+    let mut chars = apple_text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => android_text.push_str("\\\\"),
+            '\'' => android_text.push_str("\\'"),
+            '"' => android_text.push_str("\\\""),
+            '\n' => android_text.push_str("\\n"),
+            '\t' => android_text.push_str("\\t"),
+            '&' => android_text.push_str("&amp;"),
+            '<' => android_text.push_str("&lt;"),
+            '@' | '?' if android_text.is_empty() => {
+                android_text.push('\\');
+                android_text.push(c)
+            }
+
+            '%' => {
+                push_format_specifier(
+                    &mut android_text,
+                    &mut chars,
+                    |conversion| match conversion {
+                        '@' => Some('s'),
+                        'u' => Some('d'),
+                        'l' | 'h' | 'q' | 'z' | 't' | 'j' | 'L' => None,
+                        other => Some(other),
+                    },
+                )
+            }
+
+            c => android_text.push(c),
+        }
+    }
+
+    android_text
+}
+
+/// `%` must have already been consumed. Argument index, flags, width &
+/// precision are copied over as is. Every char after that is passed to
+/// `conversion_mapper` till it returns a char other than a length modifier
+/// (which is signalled by returning `None`)
+fn push_format_specifier<F>(text: &mut String, chars: &mut Peekable<Chars>, conversion_mapper: F)
+where
+    F: Fn(char) -> Option<char>,
+{
+    text.push('%');
+    while let Some(c) = chars.peek() {
+        if c.is_ascii_digit() || "$-#+0,(.".contains(*c) {
+            text.push(*c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+
+    while let Some(c) = chars.peek().cloned() {
+        if c == '%' {
+            text.push(c);
+            chars.next();
+            return;
+        }
+
+        if !c.is_ascii_alphabetic() && c != '@' {
+            // Not a format specifier at all. Let the caller deal with this char
+            return;
+        }
+
+        chars.next();
+        if let Some(conversion) = conversion_mapper(c) {
+            text.push(conversion);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn to_apple_converts_format_specifiers() {
+        assert_eq!(
+            super::to_apple("%1$s has %2$d items & %s costs %.2f"),
+            "%1$@ has %2$d items & %@ costs %.2f"
+        );
+        assert_eq!(super::to_apple("100%% done"), "100%% done")
+    }
+
+    #[test]
+    fn to_apple_unescapes_text() {
+        assert_eq!(
+            super::to_apple(r#"it\'s \"quoted\"\nnext line \@ é"#),
+            "it's \"quoted\"\nnext line @ é"
+        );
+        assert_eq!(super::to_apple(r#""  spaced  ""#), "  spaced  ");
+        assert_eq!(super::to_apple("<![CDATA[<b>bold</b>]]>"), "<b>bold</b>")
+    }
+
+    #[test]
+    fn to_android_converts_format_specifiers() {
+        assert_eq!(
+            super::to_android("%1$@ has %2$lu items & %@ costs %.2f"),
+            "%1$s has %2$d items &amp; %s costs %.2f"
+        );
+        assert_eq!(super::to_android("%lld and %ld"), "%d and %d");
+        assert_eq!(super::to_android("100%% done"), "100%% done")
+    }
+
+    #[test]
+    fn to_android_escapes_text() {
+        assert_eq!(
+            super::to_android("it's \"quoted\"\nnext\tline <b>"),
+            r#"it\'s \"quoted\"\nnext\tline &lt;b>"#
+        );
+        assert_eq!(super::to_android("@home?"), r#"\@home?"#);
+        assert_eq!(super::to_android("?what"), r#"\?what"#)
+    }
+}
@@ -7,8 +7,8 @@ use regex::Regex;
 use android_localization_utilities::DevExpt;
 
 use crate::constants;
-use crate::error::Error;
 use crate::error::ResultExt;
+use crate::error::{Error, ErrorKind};
 
 lazy_static::lazy_static! {
     static ref LOCALE_ID_REGEX: Regex = Regex::new("-([a-zA-z]+)$").expt("Invalid regex!");
@@ -17,16 +17,14 @@ lazy_static::lazy_static! {
 /// Finds language IDs from the folder names. Only folders whose name are of the
 /// format `values-...` that have a file with the name `strings.xml` are considered.
 /// What is after the last `-` in the folder name is returned as the lang id
-pub fn find(res_dir_path: &str) -> Result<Vec<String>, Error> {
-    if !Path::new(res_dir_path).is_dir() {
-        return Err(Error::new(
-            res_dir_path,
-            "Res dir path doesn't exist or it is not a directory",
-        ));
+pub fn find<P: AsRef<Path>>(res_dir_path: P) -> Result<Vec<String>, Error> {
+    let res_dir_path = res_dir_path.as_ref();
+    if !res_dir_path.is_dir() {
+        return Err(Error::new(res_dir_path, ErrorKind::MissingResDir));
     }
 
     let locale_ids = fs::read_dir(res_dir_path)
-        .with_context(String::from(res_dir_path))?
+        .with_context(res_dir_path)?
         .filter_map(|dir_entry| match dir_entry {
             Err(_) => None,
             Ok(dir_entry) => match dir_entry.file_type() {
@@ -48,10 +46,7 @@ pub fn find(res_dir_path: &str) -> Result<Vec<String>, Error> {
         })
         .filter_map(|file_name| match LOCALE_ID_REGEX.captures(&file_name) {
             None => None,
-            Some(capture) => match capture.get(1) {
-                None => None,
-                Some(m) => Some(String::from(m.as_str())),
-            },
+            Some(capture) => capture.get(1).map(|m| String::from(m.as_str())),
         })
         .collect();
 
@@ -61,7 +56,7 @@ pub fn find(res_dir_path: &str) -> Result<Vec<String>, Error> {
 /// Look @ `find`'s doc to figure out how the lang IDs are figured out
 pub fn build_map_if_empty_or_return<S: ::std::hash::BuildHasher>(
     mut map: HashMap<String, String, S>,
-    res_dir_path: &str,
+    res_dir_path: &Path,
 ) -> Result<HashMap<String, String, S>, Error> {
     if map.is_empty() {
         for locale_id in find(res_dir_path)? {
@@ -75,6 +70,7 @@ pub fn build_map_if_empty_or_return<S: ::std::hash::BuildHasher>(
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::path::Path;
 
     use test_utilities;
 
@@ -121,7 +117,7 @@ mod tests {
         let mut map = HashMap::new();
         map.insert(String::from("a"), String::from("a"));
         assert_eq!(
-            super::build_map_if_empty_or_return(map.clone(), "").unwrap(),
+            super::build_map_if_empty_or_return(map.clone(), Path::new("")).unwrap(),
             map
         )
     }
@@ -139,8 +135,7 @@ mod tests {
         map.insert(String::from("fr"), String::from("fr"));
         map.insert(String::from("it"), String::from("it"));
         assert_eq!(
-            super::build_map_if_empty_or_return(HashMap::new(), res_dir_path.to_str().unwrap())
-                .unwrap(),
+            super::build_map_if_empty_or_return(HashMap::new(), &res_dir_path).unwrap(),
             map
         )
     }
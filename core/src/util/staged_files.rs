@@ -0,0 +1,308 @@
+use std::ffi::OsString;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, ResultExt};
+
+const STAGED_EXTENSION: &str = "staged";
+const BACKUP_EXTENSION: &str = "backup";
+
+/// Files are written out next to the ones they replace & only renamed into place
+/// when all of them have been written out. If staging or committing fails, the
+/// files & the dirs created along the way are removed & the files that were
+/// already replaced are restored, leaving the `res` dir as it was
+#[derive(Default)]
+pub struct StagedFiles {
+    staged_files: Vec<StagedFile>,
+    created_dir_paths: Vec<PathBuf>,
+    is_committed: bool,
+}
+
+struct StagedFile {
+    staged_path: PathBuf,
+    target_path: PathBuf,
+}
+
+impl StagedFiles {
+    pub fn new() -> StagedFiles {
+        Default::default()
+    }
+
+    /// Returns the file to be written into in place of the file at the target path.
+    /// The dir of the target path is created if it doesn't exist yet
+    pub fn stage(&mut self, target_path: PathBuf) -> Result<File, Error> {
+        if let Some(dir_path) = target_path.parent() {
+            self.create_dir_all(dir_path)
+                .with_context(target_path.clone())?;
+        }
+
+        let staged_path = sibling_path(&target_path, STAGED_EXTENSION);
+        let file = File::create(&staged_path).with_context(staged_path.clone())?;
+        self.staged_files.push(StagedFile {
+            staged_path,
+            target_path,
+        });
+
+        Ok(file)
+    }
+
+    /// Renames all the staged files into place. Every staged file is flushed to
+    /// disk before it is renamed over its target (which is atomic on POSIX) &
+    /// the replaced files are backed up (as hard links or copies) till all the
+    /// renames go through so that they can be restored
+    pub fn commit(mut self) -> Result<(), Error> {
+        let mut backups = vec![];
+        let result = self.replace_target_files(&mut backups);
+        match result {
+            Ok(()) => {
+                self.is_committed = true;
+                // The new files are already in place; A stray backup isn't
+                // worth failing for
+                for (backup_path, _) in backups {
+                    if let Some(backup_path) = backup_path {
+                        let _ = fs::remove_file(backup_path);
+                    }
+                }
+            }
+
+            Err(_) => {
+                for (backup_path, target_path) in backups.into_iter().rev() {
+                    match backup_path {
+                        Some(backup_path) => {
+                            let _ = fs::rename(backup_path, target_path);
+                        }
+                        None => {
+                            let _ = fs::remove_file(target_path);
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn replace_target_files(
+        &self,
+        backups: &mut Vec<(Option<PathBuf>, PathBuf)>,
+    ) -> Result<(), Error> {
+        for staged_file in &self.staged_files {
+            let staged_path = &staged_file.staged_path;
+            OpenOptions::new()
+                .write(true)
+                .open(staged_path)
+                .and_then(|file| file.sync_all())
+                .with_context(staged_path.clone())?;
+
+            let target_path = &staged_file.target_path;
+            let backup_path = if target_path.exists() {
+                let backup_path = sibling_path(target_path, BACKUP_EXTENSION);
+                back_up(target_path, &backup_path).with_context(target_path.clone())?;
+                Some(backup_path)
+            } else {
+                None
+            };
+
+            backups.push((backup_path, target_path.clone()));
+            fs::rename(staged_path, target_path).with_context(target_path.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Only the dirs that didn't exist are remembered (outermost first)
+    fn create_dir_all(&mut self, dir_path: &Path) -> std::io::Result<()> {
+        let mut missing_dir_paths: Vec<PathBuf> = dir_path
+            .ancestors()
+            .take_while(|path| !path.as_os_str().is_empty() && !path.exists())
+            .map(Path::to_path_buf)
+            .collect();
+
+        fs::create_dir_all(dir_path)?;
+        missing_dir_paths.reverse();
+        self.created_dir_paths.extend(missing_dir_paths);
+        Ok(())
+    }
+}
+
+impl Drop for StagedFiles {
+    fn drop(&mut self) {
+        // Staged files are left over if they weren't committed or if committing failed
+        for staged_file in &self.staged_files {
+            let _ = fs::remove_file(&staged_file.staged_path);
+        }
+
+        if !self.is_committed {
+            for dir_path in self.created_dir_paths.iter().rev() {
+                let _ = fs::remove_dir(dir_path);
+            }
+        }
+    }
+}
+
+/// The target is left in place till the staged file is renamed over it. Hard
+/// links are cheaper but copying works even where they aren't supported
+fn back_up(target_path: &Path, backup_path: &Path) -> std::io::Result<()> {
+    let _ = fs::remove_file(backup_path);
+    fs::hard_link(target_path, backup_path)
+        .or_else(|_| fs::copy(target_path, backup_path).map(|_| ()))
+}
+
+/// `strings.xml` => `strings.xml.<extension>`
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".");
+    file_name.push(extension);
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+
+    use test_utilities;
+
+    use super::StagedFiles;
+
+    #[test]
+    fn commit_replaces_target_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let existing_file_path = temp_dir.path().join("values-fr/strings.xml");
+        let new_file_path = temp_dir.path().join("values-es/strings.xml");
+        fs::create_dir_all(existing_file_path.parent().unwrap()).unwrap();
+        test_utilities::file::write_content(&existing_file_path, "old french");
+
+        let mut staged_files = StagedFiles::new();
+        write!(
+            staged_files.stage(existing_file_path.clone()).unwrap(),
+            "new french"
+        )
+        .unwrap();
+        write!(
+            staged_files.stage(new_file_path.clone()).unwrap(),
+            "new spanish"
+        )
+        .unwrap();
+
+        // Nothing is replaced till the files are committed
+        assert_eq!(
+            test_utilities::file::read_content(&existing_file_path),
+            "old french"
+        );
+        assert!(!new_file_path.exists());
+
+        staged_files.commit().unwrap();
+        assert_eq!(
+            test_utilities::file::read_content(&existing_file_path),
+            "new french"
+        );
+        assert_eq!(
+            test_utilities::file::read_content(&new_file_path),
+            "new spanish"
+        );
+        assert_eq!(
+            fs::read_dir(existing_file_path.parent().unwrap())
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn commit_replaces_stale_backups() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("values-fr/strings.xml");
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        test_utilities::file::write_content(&file_path, "old french");
+
+        // Left over by a commit that was interrupted
+        test_utilities::file::write_content(
+            file_path.with_file_name("strings.xml.backup"),
+            "older french",
+        );
+
+        let mut staged_files = StagedFiles::new();
+        write!(staged_files.stage(file_path.clone()).unwrap(), "new french").unwrap();
+
+        staged_files.commit().unwrap();
+        assert_eq!(test_utilities::file::read_content(&file_path), "new french");
+        assert_eq!(
+            fs::read_dir(file_path.parent().unwrap()).unwrap().count(),
+            1
+        );
+    }
+
+    #[test]
+    fn dropping_without_commit_leaves_res_dir_as_it_was() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let existing_file_path = temp_dir.path().join("values-fr/strings.xml");
+        let new_file_path = temp_dir.path().join("values-es/strings.xml");
+        fs::create_dir_all(existing_file_path.parent().unwrap()).unwrap();
+        test_utilities::file::write_content(&existing_file_path, "old french");
+
+        {
+            let mut staged_files = StagedFiles::new();
+            write!(
+                staged_files.stage(existing_file_path.clone()).unwrap(),
+                "new french"
+            )
+            .unwrap();
+            write!(
+                staged_files.stage(new_file_path.clone()).unwrap(),
+                "new spanish"
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            test_utilities::file::read_content(&existing_file_path),
+            "old french"
+        );
+        assert!(!new_file_path.parent().unwrap().exists());
+        assert_eq!(
+            fs::read_dir(existing_file_path.parent().unwrap())
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn failed_commit_restores_replaced_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let fr_file_path = temp_dir.path().join("values-fr/strings.xml");
+        let es_file_path = temp_dir.path().join("values-es/strings.xml");
+        fs::create_dir_all(fr_file_path.parent().unwrap()).unwrap();
+        test_utilities::file::write_content(&fr_file_path, "old french");
+
+        let mut staged_files = StagedFiles::new();
+        write!(
+            staged_files.stage(fr_file_path.clone()).unwrap(),
+            "new french"
+        )
+        .unwrap();
+        write!(
+            staged_files.stage(es_file_path.clone()).unwrap(),
+            "new spanish"
+        )
+        .unwrap();
+
+        // The spanish file going missing makes its rename fail
+        fs::remove_file(es_file_path.with_file_name("strings.xml.staged")).unwrap();
+
+        assert!(staged_files.commit().is_err());
+        assert_eq!(
+            test_utilities::file::read_content(&fr_file_path),
+            "old french"
+        );
+        assert_eq!(
+            fs::read_dir(fr_file_path.parent().unwrap())
+                .unwrap()
+                .count(),
+            1
+        );
+        assert!(!es_file_path.parent().unwrap().exists());
+    }
+}
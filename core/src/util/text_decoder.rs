@@ -0,0 +1,86 @@
+use std::io::Read;
+
+use crate::error::{ErrorKind, InnerError};
+
+/// Reads out the whole source as text. UTF-8 (with or without BOM) & UTF-16
+/// (only with BOM, either endianness) are supported
+pub fn read_to_string<S: Read>(mut source: S) -> Result<String, InnerError> {
+    let mut bytes = vec![];
+    source.read_to_end(&mut bytes)?;
+    decode(bytes)
+}
+
+pub fn decode(bytes: Vec<u8>) -> Result<String, InnerError> {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        decode_utf8(bytes[3..].to_vec())
+    } else if bytes.starts_with(&[0xFF, 0xFE]) {
+        decode_utf16(&bytes[2..], u16::from_le_bytes)
+    } else if bytes.starts_with(&[0xFE, 0xFF]) {
+        decode_utf16(&bytes[2..], u16::from_be_bytes)
+    } else {
+        decode_utf8(bytes)
+    }
+}
+
+fn decode_utf8(bytes: Vec<u8>) -> Result<String, InnerError> {
+    String::from_utf8(bytes)
+        .map_err(|error| InnerError::from(ErrorKind::InvalidEncoding(error.to_string())))
+}
+
+fn decode_utf16<F>(bytes: &[u8], unit_builder: F) -> Result<String, InnerError>
+where
+    F: Fn([u8; 2]) -> u16,
+{
+    if bytes.len() % 2 == 1 {
+        return Err(ErrorKind::InvalidEncoding(String::from(
+            "UTF-16 text has an odd number of bytes",
+        ))
+        .into());
+    }
+
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|chunk| unit_builder([chunk[0], chunk[1]]))
+        .collect();
+
+    String::from_utf16(&units)
+        .map_err(|error| InnerError::from(ErrorKind::InvalidEncoding(error.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn decodes_utf8_with_and_without_bom() {
+        assert_eq!(super::decode(b"caf\xC3\xA9".to_vec()).unwrap(), "café");
+        assert_eq!(
+            super::decode(b"\xEF\xBB\xBFcaf\xC3\xA9".to_vec()).unwrap(),
+            "café"
+        );
+    }
+
+    #[test]
+    fn decodes_utf16_with_bom() {
+        let mut little_endian = vec![0xFF, 0xFE];
+        let mut big_endian = vec![0xFE, 0xFF];
+        for unit in "café".encode_utf16() {
+            little_endian.extend_from_slice(&unit.to_le_bytes());
+            big_endian.extend_from_slice(&unit.to_be_bytes());
+        }
+
+        assert_eq!(super::decode(little_endian).unwrap(), "café");
+        assert_eq!(super::decode(big_endian).unwrap(), "café");
+    }
+
+    #[test]
+    fn errors_for_invalid_utf8() {
+        let error = super::decode(vec![0xC3]).unwrap_err();
+        assert_eq!(error.kind().code(), "invalid_encoding")
+    }
+
+    #[test]
+    fn errors_for_utf16_with_odd_number_of_bytes() {
+        let error = super::decode(vec![0xFF, 0xFE, 0x61]).unwrap_err();
+        assert_eq!(error.to_string(), "UTF-16 text has an odd number of bytes");
+        assert_eq!(error.kind().code(), "invalid_encoding")
+    }
+}
@@ -0,0 +1,111 @@
+use regex::Regex;
+
+use android_localization_utilities::DevExpt;
+
+lazy_static::lazy_static! {
+    /// Format specifiers (`%1$d`, `%s`, `%.2f` etc.) & numbers. A specifier's
+    /// digits are matched as part of it
+    static ref SPECIFIER_OR_NUMBER: Regex =
+        Regex::new(r"%(\d+\$)?[-#+ 0,(]*\d*(\.\d+)?[a-zA-Z%]|\d+").expt("Invalid regex!");
+}
+
+/// Commonly used punctuation that isn't ASCII
+const UNICODE_PUNCTUATION: &str = "¡¿«»‘’“”…–—。、，！？";
+
+/// Chars of format specifiers (`%1$d`) that would otherwise be taken for punctuation
+const FORMAT_SPECIFIER_CHARS: &str = "%$";
+
+/// Drops everything but letters, digits & symbols that aren't punctuation. Format
+/// specifiers are kept as they are as they change what the text means
+pub fn strip_whitespace_and_punctuation(text: &str) -> String {
+    text.chars()
+        .filter(|c| {
+            FORMAT_SPECIFIER_CHARS.contains(*c)
+                || (!c.is_whitespace() && !c.is_ascii_punctuation() && !is_unicode_punctuation(*c))
+        })
+        .collect()
+}
+
+/// Format specifiers & numbers in the order they show up in the text. Texts that
+/// differ in these mean something else however similar they otherwise are
+pub fn find_specifiers_and_numbers(text: &str) -> Vec<&str> {
+    SPECIFIER_OR_NUMBER
+        .find_iter(text)
+        .map(|m| m.as_str())
+        .collect()
+}
+
+/// Returns a value between 0 (nothing in common) & 1 (same texts) based on the
+/// number of char edits required to turn one text into the other
+pub fn similarity(text1: &str, text2: &str) -> f32 {
+    let chars1: Vec<char> = text1.chars().collect();
+    let chars2: Vec<char> = text2.chars().collect();
+    let max_length = chars1.len().max(chars2.len());
+    if max_length == 0 {
+        return 1.0;
+    }
+
+    1.0 - (edit_distance(&chars1, &chars2) as f32 / max_length as f32)
+}
+
+/// Levenshtein distance with only a couple of rows kept around
+fn edit_distance(chars1: &[char], chars2: &[char]) -> usize {
+    let mut previous_row: Vec<usize> = (0..=chars2.len()).collect();
+    let mut current_row = vec![0; chars2.len() + 1];
+    for (i, c1) in chars1.iter().enumerate() {
+        current_row[0] = i + 1;
+        for (j, c2) in chars2.iter().enumerate() {
+            let substitution_cost = if c1 == c2 { 0 } else { 1 };
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[chars2.len()]
+}
+
+fn is_unicode_punctuation(c: char) -> bool {
+    UNICODE_PUNCTUATION.contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn strips_whitespace_and_punctuation() {
+        assert_eq!(
+            super::strip_whitespace_and_punctuation("Don't  stop… now!\n"),
+            "Dontstopnow"
+        );
+
+        assert_eq!(
+            super::strip_whitespace_and_punctuation("%1$d files."),
+            "%1$dfiles"
+        );
+        assert_ne!(
+            super::strip_whitespace_and_punctuation("%1$d files"),
+            super::strip_whitespace_and_punctuation("1d files")
+        );
+    }
+
+    #[test]
+    fn finds_specifiers_and_numbers() {
+        assert_eq!(
+            super::find_specifiers_and_numbers("%1$d of %2$s files (%.2f%%) in 10 days"),
+            vec!["%1$d", "%2$s", "%.2f", "%%", "10"]
+        );
+
+        assert!(super::find_specifiers_and_numbers("No files").is_empty());
+    }
+
+    #[test]
+    fn computes_similarity() {
+        assert_eq!(super::similarity("", ""), 1.0);
+        assert_eq!(super::similarity("same", "same"), 1.0);
+        assert_eq!(super::similarity("abcd", "wxyz"), 0.0);
+        assert_eq!(super::similarity("Recieve", "Receive"), 1.0 - 2.0 / 7.0);
+        assert_eq!(super::similarity("Save", "Saved"), 0.8);
+    }
+}
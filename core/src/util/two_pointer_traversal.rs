@@ -195,8 +195,8 @@ mod tests {
         );
 
         assert_eq!(common, vec![(1, 1.0), (2, 2.0), (3, 3.0)]);
-        assert_eq!(only_in_list1, vec![]);
-        assert_eq!(only_in_list2, vec![])
+        assert_eq!(only_in_list1, Vec::<i32>::new());
+        assert_eq!(only_in_list2, Vec::<f32>::new())
     }
 
     #[test]
@@ -217,7 +217,7 @@ mod tests {
             Some(|f: &f32| only_in_list2.push(*f)),
         );
 
-        assert_eq!(common, vec![]);
+        assert_eq!(common, Vec::<(i32, f32)>::new());
         assert_eq!(only_in_list1, vec![1, 2, 3]);
         assert_eq!(only_in_list2, vec![4.0, 5.0, 6.0]);
     }
@@ -0,0 +1,84 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Just enough of a calendar to name files by. Always in UTC
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UtcTime {
+    year: u64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+}
+
+impl UtcTime {
+    pub fn now() -> UtcTime {
+        let seconds_since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        UtcTime::from_seconds_since_epoch(seconds_since_epoch)
+    }
+
+    /// Converts days since epoch into a date as per the proleptic Gregorian
+    /// calendar. Look @ http://howardhinnant.github.io/date_algorithms.html
+    pub fn from_seconds_since_epoch(seconds: u64) -> UtcTime {
+        let seconds_of_day = seconds % 86_400;
+        let days = seconds / 86_400 + 719_468; // Shifted to start from 0000-03-01
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153; // March is 0
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        UtcTime {
+            year,
+            month,
+            day,
+            hour: seconds_of_day / 3_600,
+            minute: seconds_of_day % 3_600 / 60,
+            second: seconds_of_day % 60,
+        }
+    }
+
+    /// Eg., 2019-03-25
+    pub fn date(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Eg., 20190325T174502
+    pub fn timestamp(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UtcTime;
+
+    #[test]
+    fn formats_date_and_timestamp() {
+        let epoch = UtcTime::from_seconds_since_epoch(0);
+        assert_eq!(epoch.date(), "1970-01-01");
+        assert_eq!(epoch.timestamp(), "19700101T000000");
+
+        let leap_day = UtcTime::from_seconds_since_epoch(951_827_696);
+        assert_eq!(leap_day.date(), "2000-02-29");
+        assert_eq!(leap_day.timestamp(), "20000229T123456");
+
+        let new_years_eve = UtcTime::from_seconds_since_epoch(1_577_836_799);
+        assert_eq!(new_years_eve.timestamp(), "20191231T235959");
+    }
+}
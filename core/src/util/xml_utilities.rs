@@ -1,31 +1,50 @@
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::ops::Add;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::android_plurals::AndroidPlurals;
 use crate::android_string::AndroidString;
 use crate::constants;
-use crate::error::Error;
 use crate::error::ResultExt;
+use crate::error::{Error, ErrorKind};
 use crate::reader::xml_reader;
 
-type FileWithPath = (File, String);
+type FileWithPath = (File, PathBuf);
 
 pub fn read_default_strings(res_dir_path: &Path) -> Result<StringsWithPath, Error> {
     read_strings(open_default_strings_file(res_dir_path)?)
 }
 
+/// Errors with `ErrorKind::UnknownLocale` if there is no strings file for the locale
 pub fn read_foreign_strings(
     res_dir_path: &Path,
     locale_id: &str,
 ) -> Result<StringsWithPath, Error> {
-    read_strings(open_foreign_strings_file(res_dir_path, locale_id)?)
+    let file_with_path =
+        open_foreign_strings_file(res_dir_path, locale_id).map_err(|error| match error.kind {
+            ErrorKind::Io(ref io_error) if io_error.kind() == io::ErrorKind::NotFound => {
+                Error::new(
+                    error.context,
+                    ErrorKind::UnknownLocale(String::from(locale_id)),
+                )
+            }
+            _ => error,
+        })?;
+
+    read_strings(file_with_path)
 }
 
 fn read_strings(file_with_path: FileWithPath) -> Result<StringsWithPath, Error> {
     let (file, path) = file_with_path;
-    xml_reader::read(file)
+    xml_reader::read_with_plurals(file)
         .with_context(path.clone())
-        .map(|strings| StringsWithPath { path, strings })
+        .map(|(strings, plurals)| StringsWithPath {
+            path,
+            strings,
+            plurals,
+        })
 }
 
 fn open_default_strings_file(res_dir_path: &Path) -> Result<FileWithPath, Error> {
@@ -43,19 +62,61 @@ fn open_strings_file(res_dir_path: &Path, values_dir_name: &str) -> Result<FileW
     strings_file_path.push(values_dir_name);
     strings_file_path.push(constants::fs::STRING_FILE_NAME);
 
-    let path = String::from(strings_file_path.to_string_lossy());
-    File::open(strings_file_path)
-        .with_context(path.clone())
-        .map(|file| (file, path))
+    File::open(&strings_file_path)
+        .with_context(strings_file_path.clone())
+        .map(|file| (file, strings_file_path))
+}
+
+/// All the XML files in the values dir of the locale (of the default locale if
+/// `None`) sorted by their paths. There are none if the values dir doesn't exist
+pub fn find_values_xml_files(
+    res_dir_path: &Path,
+    locale_id: Option<&str>,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut values_dir_path = res_dir_path.to_path_buf();
+    values_dir_path.push(match locale_id {
+        None => String::from(constants::fs::BASE_VALUES_DIR_NAME),
+        Some(locale_id) => format!("{}-{}", constants::fs::BASE_VALUES_DIR_NAME, locale_id),
+    });
+
+    let mut xml_file_paths = vec![];
+    if !values_dir_path.is_dir() {
+        return Ok(xml_file_paths);
+    }
+
+    for entry in fs::read_dir(&values_dir_path).with_context(values_dir_path.clone())? {
+        let path = entry.with_context(values_dir_path.clone())?.path();
+        if path.is_file()
+            && path.extension().and_then(|extension| extension.to_str())
+                == Some(constants::extn::XML)
+        {
+            xml_file_paths.push(path)
+        }
+    }
+
+    xml_file_paths.sort();
+    Ok(xml_file_paths)
+}
+
+/// Path of the `strings.xml` file of the locale (which may not exist yet)
+pub fn foreign_strings_file_path(res_dir_path: &Path, locale_id: &str) -> PathBuf {
+    let values_dir_name = String::from(constants::fs::BASE_VALUES_DIR_NAME);
+    let values_dir_name = values_dir_name.add(&format!("-{}", locale_id));
+
+    let mut strings_file_path = res_dir_path.to_path_buf();
+    strings_file_path.push(values_dir_name);
+    strings_file_path.push(constants::fs::STRING_FILE_NAME);
+    strings_file_path
 }
 
 pub struct StringsWithPath {
-    path: String,
+    path: PathBuf,
     strings: Vec<AndroidString>,
+    plurals: Vec<AndroidPlurals>,
 }
 
 impl StringsWithPath {
-    pub fn path(&self) -> &str {
+    pub fn path(&self) -> &Path {
         &self.path
     }
 
@@ -63,9 +124,17 @@ impl StringsWithPath {
         &self.strings
     }
 
+    pub fn plurals(&self) -> &[AndroidPlurals] {
+        &self.plurals
+    }
+
     pub fn into_strings(self) -> Vec<AndroidString> {
         self.strings
     }
+
+    pub fn into_strings_and_plurals(self) -> (Vec<AndroidString>, Vec<AndroidPlurals>) {
+        (self.strings, self.plurals)
+    }
 }
 
 #[cfg(test)]
@@ -98,6 +167,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_foreign_strings_errors_if_locale_is_unknown() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let error = super::read_foreign_strings(res_dir.path(), "fr");
+        match error.err().unwrap().kind {
+            error::ErrorKind::UnknownLocale(locale_id) => assert_eq!(locale_id, "fr"),
+            error_kind => panic!("Expected unknown locale error. Received: {:?}", error_kind),
+        }
+    }
+
     #[test]
     fn open_default_strings_file_opens() {
         let res_dir = tempfile::tempdir().unwrap();
@@ -129,4 +208,20 @@ mod tests {
         assert_eq!(file_contents, "example content");
         assert_eq!(file_path, strings_file_path);
     }
+
+    #[test]
+    fn find_values_xml_files_finds_only_xml_files() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let strings_file_path =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "fr").path;
+        let values_dir_path = strings_file_path.parent().unwrap().to_path_buf();
+        let other_file_path = values_dir_path.join("donottranslate.xml");
+        test_utilities::file::write_content(other_file_path.clone(), "");
+        test_utilities::file::write_content(values_dir_path.join("notes.txt"), "");
+
+        assert_eq!(
+            super::find_values_xml_files(res_dir.path(), Some("fr")).unwrap(),
+            vec![other_file_path, strings_file_path]
+        )
+    }
 }
@@ -1,5 +1,12 @@
 pub mod apostrophe;
+pub mod duplicate_names;
 pub mod format_string;
 pub mod formatter;
+pub mod identical_texts;
+pub mod max_length;
 pub mod missing_strings;
+pub mod plurals;
+pub mod punctuation;
+pub mod references;
+pub mod untranslatable_strings;
 pub mod validator;
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, ErrorKind, ResultExt};
+use crate::reader::xml_reader;
+use crate::reader::xml_reader::DefinedName;
+use crate::util::xml_utilities;
+
+/// Strings (or plurals) with the same name can be spread across all the XML
+/// files of a values dir. Only the first definition of a name is kept around
+/// by the rest of this utility, so such names are ambiguous
+pub fn validate(
+    res_dir_path: &Path,
+    locale_id: Option<&str>,
+) -> Result<Result<(), DuplicateNames>, Error> {
+    let mut files_with_names = vec![];
+    for file_path in xml_utilities::find_values_xml_files(res_dir_path, locale_id)? {
+        let file = File::open(&file_path).with_context(file_path.clone())?;
+        let defined_names = xml_reader::read_defined_names(file).with_context(file_path.clone())?;
+        files_with_names.push((file_path, defined_names));
+    }
+
+    Ok(find_duplicates(files_with_names))
+}
+
+/// Errors with `ErrorKind::DuplicateKey` for the first values dir (default locale
+/// first) that has duplicate names
+pub fn ensure_unique_names<'a, I: IntoIterator<Item = &'a str>>(
+    res_dir_path: &Path,
+    locale_ids: I,
+) -> Result<(), Error> {
+    let locale_ids = std::iter::once(None).chain(locale_ids.into_iter().map(Some));
+    for locale_id in locale_ids {
+        if let Err(duplicate_names) = validate(res_dir_path, locale_id)? {
+            let values_dir_path = duplicate_names.duplicate_names[0]
+                .duplicate
+                .file_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+
+            return Err(Error::new(
+                values_dir_path,
+                ErrorKind::DuplicateKey(duplicate_names.to_string()),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn find_duplicates(
+    files_with_names: Vec<(PathBuf, Vec<DefinedName>)>,
+) -> Result<(), DuplicateNames> {
+    let mut first_locations: HashMap<(String, String), Location> = HashMap::new();
+    let mut duplicate_names = vec![];
+    for (file_path, defined_names) in files_with_names {
+        for defined_name in defined_names {
+            let location = Location {
+                file_path: file_path.clone(),
+                line: defined_name.line,
+            };
+
+            let key = (defined_name.tag_name, defined_name.name);
+            match first_locations.get(&key) {
+                None => {
+                    first_locations.insert(key, location);
+                }
+                Some(first) => duplicate_names.push(DuplicateName {
+                    name: key.1,
+                    first: first.clone(),
+                    duplicate: location,
+                }),
+            }
+        }
+    }
+
+    if duplicate_names.is_empty() {
+        Ok(())
+    } else {
+        Err(DuplicateNames { duplicate_names })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DuplicateNames {
+    pub duplicate_names: Vec<DuplicateName>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DuplicateName {
+    pub name: String,
+    pub first: Location,
+    pub duplicate: Location,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    pub file_path: PathBuf,
+    pub line: u64,
+}
+
+impl fmt::Display for DuplicateNames {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let duplicate_names: Vec<String> = self
+            .duplicate_names
+            .iter()
+            .map(|duplicate_name| duplicate_name.to_string())
+            .collect();
+
+        f.write_str(&duplicate_names.join(", "))
+    }
+}
+
+impl fmt::Display for DuplicateName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({} & {})", self.name, self.first, self.duplicate)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.file_path.display(), self.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use test_utilities;
+
+    use crate::error::ErrorKind;
+    use crate::reader::xml_reader::DefinedName;
+
+    use super::{DuplicateName, DuplicateNames, Location};
+
+    #[test]
+    fn finds_duplicates_across_files() {
+        let strings_path = PathBuf::from("values/strings.xml");
+        let other_path = PathBuf::from("values/donottranslate.xml");
+        let result = super::find_duplicates(vec![
+            (
+                other_path.clone(),
+                vec![
+                    defined_name("string", "s1", 2),
+                    defined_name("plurals", "s2", 3),
+                ],
+            ),
+            (
+                strings_path.clone(),
+                vec![
+                    defined_name("string", "s2", 2),
+                    defined_name("string", "s1", 3),
+                    defined_name("string", "s2", 4),
+                ],
+            ),
+        ]);
+
+        assert_eq!(
+            result,
+            Err(DuplicateNames {
+                duplicate_names: vec![
+                    DuplicateName {
+                        name: String::from("s1"),
+                        first: Location {
+                            file_path: other_path,
+                            line: 2,
+                        },
+                        duplicate: Location {
+                            file_path: strings_path.clone(),
+                            line: 3,
+                        },
+                    },
+                    DuplicateName {
+                        name: String::from("s2"),
+                        first: Location {
+                            file_path: strings_path.clone(),
+                            line: 2,
+                        },
+                        duplicate: Location {
+                            file_path: strings_path,
+                            line: 4,
+                        },
+                    },
+                ],
+            })
+        )
+    }
+
+    #[test]
+    fn passes_without_duplicates() {
+        assert_eq!(
+            super::find_duplicates(vec![(
+                PathBuf::from("values/strings.xml"),
+                vec![
+                    defined_name("string", "s1", 2),
+                    defined_name("string", "s2", 3),
+                ],
+            )]),
+            Ok(())
+        )
+    }
+
+    #[test]
+    fn ensure_unique_names_errors_for_duplicates_in_foreign_locale() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_dir.path());
+        let french_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "fr");
+        test_utilities::file::write_content(
+            default_strings.path,
+            r#"<resources><string name="s1">Save</string></resources>"#,
+        );
+        test_utilities::file::write_content(
+            french_strings.path.clone(),
+            "<resources>\n<string name=\"s1\">Enregistrer</string>\n<string name=\"s1\">Sauver</string>\n</resources>",
+        );
+
+        assert!(super::ensure_unique_names(res_dir.path(), vec![]).is_ok());
+
+        let error = super::ensure_unique_names(res_dir.path(), vec!["fr"]).unwrap_err();
+        assert_eq!(error.context, french_strings.path.parent().unwrap());
+        match error.kind {
+            ErrorKind::DuplicateKey(key) => assert_eq!(
+                key,
+                format!("s1 ({0}:2 & {0}:3)", french_strings.path.display())
+            ),
+            error_kind => panic!("Expected duplicate key error. Received: {:?}", error_kind),
+        }
+    }
+
+    fn defined_name(tag_name: &str, name: &str, line: u64) -> DefinedName {
+        DefinedName {
+            tag_name: String::from(tag_name),
+            name: String::from(name),
+            line,
+        }
+    }
+}
@@ -10,6 +10,11 @@ lazy_static::lazy_static! {
     static ref FORMAT_STRING: Regex = Regex::new(r"(%\d+\$[ds])").expt("Invalid regex!");
 }
 
+/// `%1$s` & `%2$d` are dropped from the text
+pub fn strip_format_strings(text: &str) -> String {
+    String::from(FORMAT_STRING.replace_all(text, ""))
+}
+
 pub fn validate(
     default_parsed_data: &mut [ParsedData],
     foreign_strings: &mut [AndroidString],
@@ -3,6 +3,7 @@ use std::fmt::Write;
 
 use android_localization_utilities::DevExpt;
 
+use crate::validate::plurals::PluralsQuantities;
 use crate::validate::validator::InvalidStringsFile;
 
 pub fn format_to_string(invalid_strings_files: Vec<InvalidStringsFile>) -> Result<String, Error> {
@@ -123,6 +124,161 @@ fn format_errors_from_one_file(
         }
     }
 
+    if let Some(untranslatable_strings_error) = invalid_strings_file.untranslatable_strings_error {
+        if !untranslatable_strings_error
+            .untranslatable_strings
+            .is_empty()
+        {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (translated untranslatable string(s)): {}",
+                issues_count_in_file,
+                untranslatable_strings_error
+                    .untranslatable_strings
+                    .iter()
+                    .map(|s| s.value())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )?;
+        }
+
+        if !untranslatable_strings_error
+            .untranslatable_plurals
+            .is_empty()
+        {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (translated untranslatable plurals): {}",
+                issues_count_in_file,
+                untranslatable_strings_error
+                    .untranslatable_plurals
+                    .iter()
+                    .map(|p| p.name())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )?;
+        }
+    }
+
+    if let Some(references_error) = invalid_strings_file.references_error {
+        if !references_error.dangling_references.is_empty() {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (undefined reference(s)): {}",
+                issues_count_in_file,
+                references_error
+                    .dangling_references
+                    .iter()
+                    .map(|s| s.value())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )?;
+        }
+
+        if !references_error.replaced_references.is_empty() {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (reference(s) replaced by text): {}",
+                issues_count_in_file,
+                references_error
+                    .replaced_references
+                    .iter()
+                    .map(|s| s.value())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )?;
+        }
+    }
+
+    if let Some(plurals_error) = invalid_strings_file.plurals_error {
+        for missing_quantities in plurals_error.missing_quantities {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (missing quantities): [{}] in {}",
+                issues_count_in_file,
+                join_quantities(&missing_quantities),
+                missing_quantities.name
+            )?;
+        }
+
+        for unused_quantities in plurals_error.unused_quantities {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (unused quantities): [{}] in {}",
+                issues_count_in_file,
+                join_quantities(&unused_quantities),
+                unused_quantities.name
+            )?;
+        }
+    }
+
+    if let Some(max_length_error) = invalid_strings_file.max_length_error {
+        for too_long_string in max_length_error.too_long_strings {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (too long string): Found {} chars (allowed {}) in {}",
+                issues_count_in_file,
+                too_long_string.android_string.value().chars().count(),
+                too_long_string.allowed_length,
+                too_long_string.android_string.value()
+            )?;
+        }
+    }
+
+    if let Some(identical_texts_error) = invalid_strings_file.identical_texts_error {
+        issues_count_in_file += 1;
+        writeln!(
+            &mut file_output,
+            "Error {} (same as default text): {}",
+            issues_count_in_file,
+            identical_texts_error
+                .identical_texts
+                .iter()
+                .map(|s| s.value())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )?;
+    }
+
+    if let Some(punctuation_error) = invalid_strings_file.punctuation_error {
+        for inconsistency in punctuation_error.inconsistencies {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (inconsistent punctuation): Found [{}] in {}",
+                issues_count_in_file,
+                inconsistency
+                    .rules
+                    .iter()
+                    .map(|rule| rule.name())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+                inconsistency.android_string.value()
+            )?;
+        }
+    }
+
+    if let Some(duplicate_names_error) = invalid_strings_file.duplicate_names_error {
+        for duplicate_name in duplicate_names_error.duplicate_names {
+            issues_count_in_file += 1;
+            writeln!(
+                &mut file_output,
+                "Error {} (duplicate name): {} is defined at {} & {}",
+                issues_count_in_file,
+                duplicate_name.name,
+                duplicate_name.first,
+                duplicate_name.duplicate
+            )?;
+        }
+    }
+
     let pluralized_issue = if issues_count_in_file <= 1 {
         "issue"
     } else {
@@ -132,19 +288,40 @@ fn format_errors_from_one_file(
     writeln!(
         &mut output,
         "Path: {} ({} {})",
-        invalid_strings_file.file_path, issues_count_in_file, pluralized_issue
+        invalid_strings_file.file_path.display(),
+        issues_count_in_file,
+        pluralized_issue
     )?;
     write!(&mut output, "{}", file_output)?;
 
     Ok(issues_count_in_file)
 }
 
+fn join_quantities(plurals_quantities: &PluralsQuantities) -> String {
+    plurals_quantities
+        .quantities
+        .iter()
+        .map(|quantity| quantity.name())
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use crate::android_plurals::{AndroidPlurals, Quantity};
     use crate::android_string::AndroidString;
     use crate::validate::apostrophe;
+    use crate::validate::duplicate_names;
     use crate::validate::format_string;
+    use crate::validate::identical_texts;
+    use crate::validate::max_length;
     use crate::validate::missing_strings;
+    use crate::validate::plurals;
+    use crate::validate::punctuation;
+    use crate::validate::references;
+    use crate::validate::untranslatable_strings;
     use crate::validate::validator::InvalidStringsFile;
 
     #[test]
@@ -157,17 +334,78 @@ mod tests {
 
         let invalid_strings_file = vec![
             InvalidStringsFile {
-                file_path: String::from("default"),
+                file_path: PathBuf::from("default"),
                 apostrophe_error: Some(apostrophe::InvalidStrings {
                     invalid_strings: vec![default_s1.clone()],
                 }),
                 format_string_error: None,
                 missing_strings_error: None,
+                untranslatable_strings_error: None,
+                references_error: None,
+                plurals_error: None,
+                max_length_error: None,
+                identical_texts_error: None,
+                punctuation_error: None,
+                duplicate_names_error: Some(duplicate_names::DuplicateNames {
+                    duplicate_names: vec![duplicate_names::DuplicateName {
+                        name: String::from("s2"),
+                        first: duplicate_names::Location {
+                            file_path: PathBuf::from("default"),
+                            line: 3,
+                        },
+                        duplicate: duplicate_names::Location {
+                            file_path: PathBuf::from("more"),
+                            line: 2,
+                        },
+                    }],
+                }),
             },
             InvalidStringsFile {
-                file_path: String::from("french"),
+                file_path: PathBuf::from("french"),
                 apostrophe_error: None,
                 missing_strings_error: None,
+                untranslatable_strings_error: Some(untranslatable_strings::UntranslatableStrings {
+                    untranslatable_strings: vec![AndroidString::localizable("s5", "Acmé")],
+                    untranslatable_plurals: vec![AndroidPlurals::localizable(
+                        "p3",
+                        vec![(Quantity::Other, "Acmés")],
+                    )],
+                }),
+                references_error: Some(references::InvalidReferences {
+                    dangling_references: vec![AndroidString::localizable("s7", "@string/s8")],
+                    replaced_references: vec![AndroidString::localizable("s9", "Aide")],
+                }),
+                plurals_error: Some(plurals::InvalidPlurals {
+                    missing_quantities: vec![plurals::PluralsQuantities {
+                        name: String::from("p1"),
+                        quantities: vec![Quantity::One, Quantity::Other],
+                    }],
+                    unused_quantities: vec![plurals::PluralsQuantities {
+                        name: String::from("p2"),
+                        quantities: vec![Quantity::Zero],
+                    }],
+                }),
+                max_length_error: Some(max_length::TooLongStrings {
+                    too_long_strings: vec![max_length::TooLongString {
+                        android_string: AndroidString::localizable("s2", "french_value2"),
+                        allowed_length: 10,
+                    }],
+                }),
+                identical_texts_error: Some(identical_texts::IdenticalTexts {
+                    identical_texts: vec![
+                        AndroidString::localizable("s3", "Cancel"),
+                        AndroidString::localizable("s4", "Done"),
+                    ],
+                }),
+                punctuation_error: Some(punctuation::Inconsistencies {
+                    inconsistencies: vec![punctuation::Inconsistency {
+                        android_string: AndroidString::localizable("s6", " Chargement..."),
+                        rules: vec![
+                            punctuation::PunctuationRule::Whitespace,
+                            punctuation::PunctuationRule::Ellipsis,
+                        ],
+                    }],
+                }),
                 format_string_error: Some(format_string::Mismatches {
                     mismatches: vec![format_string::Mismatch {
                         default_parsed_data: format_string::ParsedData {
@@ -180,9 +418,10 @@ mod tests {
                         },
                     }],
                 }),
+                duplicate_names_error: None,
             },
             InvalidStringsFile {
-                file_path: String::from("spanish"),
+                file_path: PathBuf::from("spanish"),
                 apostrophe_error: Some(apostrophe::InvalidStrings {
                     invalid_strings: vec![spanish_s1.clone()],
                 }),
@@ -202,18 +441,35 @@ mod tests {
                     extra_in_default_locale: vec![default_s1.clone(), default_s2.clone()],
                     extra_in_foreign_locale: vec![spanish_s1.clone(), spanish_s2.clone()],
                 }),
+                untranslatable_strings_error: None,
+                references_error: None,
+                plurals_error: None,
+                max_length_error: None,
+                identical_texts_error: None,
+                punctuation_error: None,
+                duplicate_names_error: None,
             },
         ];
 
         assert_eq!(
             super::format_to_string(invalid_strings_file).unwrap(),
             String::from(
-                r#"Path: default (1 issue)
+                r#"Path: default (2 issues)
 Error 1 (unescaped apostrophe): default_value1
+Error 2 (duplicate name): s2 is defined at default:3 & more:2
 
-Path: french (1 issue)
+Path: french (10 issues)
 Error 1 (mismatched format string(s)): Found [asdf, qwer] in french_value1
                                        Found [] in default_value1
+Error 2 (translated untranslatable string(s)): Acmé
+Error 3 (translated untranslatable plurals): p3
+Error 4 (undefined reference(s)): @string/s8
+Error 5 (reference(s) replaced by text): Aide
+Error 6 (missing quantities): [one, other] in p1
+Error 7 (unused quantities): [zero] in p2
+Error 8 (too long string): Found 13 chars (allowed 10) in french_value2
+Error 9 (same as default text): Cancel, Done
+Error 10 (inconsistent punctuation): Found [whitespace, ellipsis] in  Chargement...
 
 Path: spanish (4 issues)
 Error 1 (unescaped apostrophe): spanish_value1
@@ -222,7 +478,7 @@ Error 2 (mismatched format string(s)): Found [%1$d] in spanish_value1
 Error 3 (unlocalized string(s)): default_value1, default_value2
 Error 4 (string(s) not in defaut locale): spanish_value1, spanish_value2
 
-Found 6 issues across 3 files!"#
+Found 16 issues across 3 files!"#
             )
         );
     }
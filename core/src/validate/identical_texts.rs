@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use crate::android_string::AndroidString;
+use crate::ops::sort;
+use crate::util::two_pointer_traversal;
+use crate::validate::format_string;
+
+/// Which foreign texts are allowed to be the same as their default text
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IdenticalTextsConfig {
+    /// Texts (like brand names or "OK") that are allowed in all the locales
+    pub allowed_texts: Vec<String>,
+
+    /// Locale ID to the texts that are allowed only in that locale
+    pub allowed_texts_per_locale: HashMap<String, Vec<String>>,
+
+    /// Locales (like `en` for a default locale in US English) that aren't checked at all
+    pub skipped_locales: Vec<String>,
+}
+
+impl IdenticalTextsConfig {
+    /// `None` if the locale isn't to be checked
+    pub(crate) fn allowed_texts_for(&self, locale_id: &str) -> Option<Vec<&str>> {
+        if self
+            .skipped_locales
+            .iter()
+            .any(|skipped| skipped == locale_id)
+        {
+            return None;
+        }
+
+        let mut allowed_texts: Vec<&str> = self.allowed_texts.iter().map(String::as_str).collect();
+        if let Some(texts) = self.allowed_texts_per_locale.get(locale_id) {
+            allowed_texts.extend(texts.iter().map(String::as_str));
+        }
+
+        Some(allowed_texts)
+    }
+}
+
+/// Foreign texts that are the same as the (localizable) default text are most
+/// likely the default text pasted in instead of being localized. Texts without
+/// any letters besides format strings (`%1$d`, `—`, etc.) can't be localized &
+/// are skipped
+pub fn validate(
+    default_strings: &mut [AndroidString],
+    foreign_strings: &mut [AndroidString],
+    allowed_texts: &[&str],
+) -> Result<(), IdenticalTexts> {
+    // Sort both the lists
+    sort::sort_android_strings_by_name(default_strings);
+    sort::sort_android_strings_by_name(foreign_strings);
+
+    let mut identical_texts = vec![];
+    two_pointer_traversal::compare(
+        default_strings,
+        foreign_strings,
+        |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+        |default_string, foreign_string| {
+            let text = foreign_string.value().trim();
+            if default_string.is_localizable()
+                && !default_string.is_reference()
+                && text == default_string.value().trim()
+                && format_string::strip_format_strings(text)
+                    .chars()
+                    .any(char::is_alphabetic)
+                && !allowed_texts.contains(&text)
+            {
+                identical_texts.push(foreign_string.clone())
+            }
+        },
+    );
+
+    if identical_texts.is_empty() {
+        Ok(())
+    } else {
+        Err(IdenticalTexts { identical_texts })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct IdenticalTexts {
+    pub identical_texts: Vec<AndroidString>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::android_string::AndroidString;
+
+    use super::IdenticalTexts;
+    use super::IdenticalTextsConfig;
+
+    #[test]
+    fn validate_passes_for_localized_and_allowed_texts() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable("s2", "OK"),
+            AndroidString::localizable("s3", "%1$d"),
+            AndroidString::unlocalizable("s4", "Acme"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s1", "Enregistrer"),
+            AndroidString::localizable("s2", "OK"),
+            AndroidString::localizable("s3", "%1$d"),
+            AndroidString::localizable("s4", "Acme"),
+        ];
+
+        assert!(super::validate(&mut default_strings, &mut foreign_strings, &["OK"]).is_ok())
+    }
+
+    #[test]
+    fn validate_errors_for_identical_texts() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable("s2", "Cancel"),
+            AndroidString::localizable("s3", "OK"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s3", "OK"),
+            AndroidString::localizable("s2", "Cancel "),
+            AndroidString::localizable("s1", "Enregistrer"),
+        ];
+
+        assert_eq!(
+            super::validate(&mut default_strings, &mut foreign_strings, &[]).unwrap_err(),
+            IdenticalTexts {
+                identical_texts: vec![
+                    AndroidString::localizable("s2", "Cancel "),
+                    AndroidString::localizable("s3", "OK"),
+                ],
+            }
+        )
+    }
+
+    #[test]
+    fn finds_allowed_texts_for_locale() {
+        let mut allowed_texts_per_locale = HashMap::new();
+        allowed_texts_per_locale.insert(String::from("fr"), vec![String::from("Chat")]);
+        let config = IdenticalTextsConfig {
+            allowed_texts: vec![String::from("OK")],
+            allowed_texts_per_locale,
+            skipped_locales: vec![String::from("en")],
+        };
+
+        assert_eq!(config.allowed_texts_for("fr"), Some(vec!["OK", "Chat"]));
+        assert_eq!(config.allowed_texts_for("es"), Some(vec!["OK"]));
+        assert_eq!(config.allowed_texts_for("en"), None);
+    }
+}
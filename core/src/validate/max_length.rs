@@ -0,0 +1,137 @@
+use crate::android_string::AndroidString;
+use crate::ops::sort;
+use crate::util::two_pointer_traversal;
+
+/// Foreign strings are allowed to be only as long as the max length declared
+/// on the default string. If a ratio is passed in, they are also allowed to be
+/// only as long as the default text times that ratio. Lengths are in chars
+pub fn validate(
+    default_strings: &mut [AndroidString],
+    foreign_strings: &mut [AndroidString],
+    max_length_ratio: Option<f32>,
+) -> Result<(), TooLongStrings> {
+    // Sort both the lists
+    sort::sort_android_strings_by_name(default_strings);
+    sort::sort_android_strings_by_name(foreign_strings);
+
+    let mut too_long_strings = vec![];
+    two_pointer_traversal::compare(
+        default_strings,
+        foreign_strings,
+        |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+        |default_string, foreign_string| {
+            if let Some(allowed_length) = allowed_length(default_string, max_length_ratio) {
+                if foreign_string.value().chars().count() > allowed_length {
+                    too_long_strings.push(TooLongString {
+                        android_string: foreign_string.clone(),
+                        allowed_length,
+                    })
+                }
+            }
+        },
+    );
+
+    if too_long_strings.is_empty() {
+        Ok(())
+    } else {
+        Err(TooLongStrings { too_long_strings })
+    }
+}
+
+fn allowed_length(default_string: &AndroidString, max_length_ratio: Option<f32>) -> Option<usize> {
+    let length_by_ratio = max_length_ratio
+        .map(|ratio| (default_string.value().chars().count() as f32 * ratio).floor() as usize);
+
+    match (default_string.max_length(), length_by_ratio) {
+        (Some(max_length), Some(length_by_ratio)) => Some(max_length.min(length_by_ratio)),
+        (max_length, length_by_ratio) => max_length.or(length_by_ratio),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TooLongStrings {
+    pub too_long_strings: Vec<TooLongString>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TooLongString {
+    pub android_string: AndroidString,
+    pub allowed_length: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::android_string::AndroidString;
+
+    use super::TooLongString;
+    use super::TooLongStrings;
+
+    #[test]
+    fn validate_passes_for_strings_within_limits() {
+        let mut default_strings = vec![
+            AndroidString::localizable_with_max_length("s1", "Save", 6),
+            AndroidString::localizable("s2", "Cancel"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s2", "Annuler"),
+            AndroidString::localizable("s1", "Sauver"),
+        ];
+
+        assert!(super::validate(&mut default_strings, &mut foreign_strings, None).is_ok())
+    }
+
+    #[test]
+    fn validate_errors_for_strings_exceeding_max_length() {
+        let mut default_strings = vec![
+            AndroidString::localizable_with_max_length("s1", "Save", 6),
+            AndroidString::localizable_with_max_length("s2", "Done", 10),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s1", "Enregistrer"),
+            AndroidString::localizable("s2", "Terminé"),
+        ];
+
+        assert_eq!(
+            super::validate(&mut default_strings, &mut foreign_strings, None).unwrap_err(),
+            TooLongStrings {
+                too_long_strings: vec![TooLongString {
+                    android_string: AndroidString::localizable("s1", "Enregistrer"),
+                    allowed_length: 6,
+                }],
+            }
+        )
+    }
+
+    #[test]
+    fn validate_errors_for_strings_exceeding_ratio() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable_with_max_length("s2", "Done", 10),
+            AndroidString::localizable("s3", "Cancel"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s1", "Enregistrer"),
+            AndroidString::localizable("s2", "Terminé"),
+            AndroidString::localizable("s3", "Annuler"),
+        ];
+
+        assert_eq!(
+            super::validate(&mut default_strings, &mut foreign_strings, Some(1.5)).unwrap_err(),
+            TooLongStrings {
+                too_long_strings: vec![
+                    TooLongString {
+                        android_string: AndroidString::localizable("s1", "Enregistrer"),
+                        allowed_length: 6,
+                    },
+                    TooLongString {
+                        android_string: AndroidString::localizable("s2", "Terminé"),
+                        allowed_length: 6,
+                    },
+                ],
+            }
+        )
+    }
+}
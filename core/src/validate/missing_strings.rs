@@ -17,9 +17,9 @@ pub fn validate(
         foreign_strings,
         |default_string, foriegn_string| default_string.name().cmp(foriegn_string.name()),
         |default_string| {
-            // It is ok for non-translatable strings to be present in default locale but
-            // not the other way around
-            if default_string.is_localizable() {
+            // It is ok for non-translatable strings (& aliases of other strings) to be
+            // present in default locale but not the other way around
+            if default_string.is_localizable() && !default_string.is_reference() {
                 extra_in_default_locale.push(default_string.clone())
             }
         },
@@ -0,0 +1,264 @@
+use crate::android_plurals::{AndroidPlurals, Quantity};
+
+/// Quantities CLDR (v37) uses for the cardinal numbers of a language, keyed by
+/// the language codes Android uses (`iw` & `in` along with `he` & `id`).
+/// Languages that aren't listed here are only checked for `other`
+const QUANTITIES_BY_LANGUAGE: &[(&[&str], &[Quantity])] = &[
+    (
+        &[
+            "bo", "dz", "id", "in", "ig", "ja", "jv", "km", "ko", "lo", "ms", "my", "sah", "th",
+            "to", "vi", "wo", "yo", "zh",
+        ],
+        &[Quantity::Other],
+    ),
+    (
+        &[
+            "af", "am", "as", "az", "bg", "bn", "ca", "da", "de", "el", "en", "eo", "es", "et",
+            "eu", "fa", "fi", "fil", "fr", "fy", "gl", "gu", "ha", "hi", "hu", "hy", "is", "it",
+            "ka", "kk", "kn", "ky", "lb", "mk", "ml", "mn", "mr", "nb", "ne", "nl", "nn", "no",
+            "or", "pa", "ps", "pt", "si", "so", "sq", "sv", "sw", "ta", "te", "tk", "tl", "tr",
+            "ug", "ur", "uz", "zu",
+        ],
+        &[Quantity::One, Quantity::Other],
+    ),
+    (&["lv"], &[Quantity::Zero, Quantity::One, Quantity::Other]),
+    (
+        &["bs", "hr", "mo", "ro", "sr"],
+        &[Quantity::One, Quantity::Few, Quantity::Other],
+    ),
+    (
+        &["gd", "sl"],
+        &[Quantity::One, Quantity::Two, Quantity::Few, Quantity::Other],
+    ),
+    (
+        &["he", "iw"],
+        &[
+            Quantity::One,
+            Quantity::Two,
+            Quantity::Many,
+            Quantity::Other,
+        ],
+    ),
+    (
+        &["be", "cs", "lt", "mt", "pl", "ru", "sk", "uk"],
+        &[
+            Quantity::One,
+            Quantity::Few,
+            Quantity::Many,
+            Quantity::Other,
+        ],
+    ),
+    (
+        &["br", "ga"],
+        &[
+            Quantity::One,
+            Quantity::Two,
+            Quantity::Few,
+            Quantity::Many,
+            Quantity::Other,
+        ],
+    ),
+    (
+        &["ar", "cy"],
+        &[
+            Quantity::Zero,
+            Quantity::One,
+            Quantity::Two,
+            Quantity::Few,
+            Quantity::Many,
+            Quantity::Other,
+        ],
+    ),
+];
+
+/// Plurals have to provide all the quantities CLDR requires for the language of
+/// the locale (only `other` is required for the default locale as its language
+/// isn't known). Quantities the language never uses are harmless as Android just
+/// skips over them, so they are only reported if `fail_on_unused_quantities` is set
+pub fn validate(
+    plurals: &[AndroidPlurals],
+    locale_id: Option<&str>,
+    fail_on_unused_quantities: bool,
+) -> Result<(), InvalidPlurals> {
+    let required_quantities = locale_id.and_then(find_quantities);
+    let mut missing_quantities = vec![];
+    let mut unused_quantities = vec![];
+    for android_plurals in plurals {
+        let missing: Vec<Quantity> = required_quantities
+            .unwrap_or(&[Quantity::Other])
+            .iter()
+            .filter(|quantity| !android_plurals.items().contains_key(quantity))
+            .cloned()
+            .collect();
+
+        let unused: Vec<Quantity> = match required_quantities {
+            Some(required_quantities) if fail_on_unused_quantities => android_plurals
+                .items()
+                .keys()
+                .filter(|quantity| !required_quantities.contains(quantity))
+                .cloned()
+                .collect(),
+            _ => vec![],
+        };
+
+        if !missing.is_empty() {
+            missing_quantities.push(PluralsQuantities {
+                name: String::from(android_plurals.name()),
+                quantities: missing,
+            })
+        }
+
+        if !unused.is_empty() {
+            unused_quantities.push(PluralsQuantities {
+                name: String::from(android_plurals.name()),
+                quantities: unused,
+            })
+        }
+    }
+
+    if missing_quantities.is_empty() && unused_quantities.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidPlurals {
+            missing_quantities,
+            unused_quantities,
+        })
+    }
+}
+
+/// The language is the first part of the locale ID (`pt` in `pt-rBR`) or the
+/// one after `b+` in BCP 47 locale IDs (`sr` in `b+sr+Latn`)
+fn find_quantities(locale_id: &str) -> Option<&'static [Quantity]> {
+    let locale_id = locale_id.strip_prefix("b+").unwrap_or(locale_id);
+    let language = locale_id.split(['-', '+', '_']).next()?.to_lowercase();
+
+    QUANTITIES_BY_LANGUAGE
+        .iter()
+        .find(|(languages, _)| languages.contains(&language.as_str()))
+        .map(|(_, quantities)| *quantities)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidPlurals {
+    pub missing_quantities: Vec<PluralsQuantities>,
+    pub unused_quantities: Vec<PluralsQuantities>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PluralsQuantities {
+    pub name: String,
+    pub quantities: Vec<Quantity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::android_plurals::{AndroidPlurals, Quantity};
+
+    use super::{InvalidPlurals, PluralsQuantities};
+
+    #[test]
+    fn validate_passes_for_required_quantities() {
+        let plurals = vec![AndroidPlurals::localizable(
+            "p1",
+            vec![
+                (Quantity::One, "%d plik"),
+                (Quantity::Few, "%d pliki"),
+                (Quantity::Many, "%d plików"),
+                (Quantity::Other, "%d pliku"),
+            ],
+        )];
+
+        assert!(super::validate(&plurals, Some("pl"), true).is_ok())
+    }
+
+    #[test]
+    fn validate_errors_for_missing_quantities() {
+        let plurals = vec![
+            AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::One, "%d file"), (Quantity::Other, "%d files")],
+            ),
+            AndroidPlurals::localizable("p2", vec![(Quantity::Other, "%d 個")]),
+        ];
+
+        assert!(super::validate(&plurals, Some("ja"), false).is_ok());
+
+        assert_eq!(
+            super::validate(&plurals, Some("b+ru+RU"), false).unwrap_err(),
+            InvalidPlurals {
+                missing_quantities: vec![
+                    PluralsQuantities {
+                        name: String::from("p1"),
+                        quantities: vec![Quantity::Few, Quantity::Many],
+                    },
+                    PluralsQuantities {
+                        name: String::from("p2"),
+                        quantities: vec![Quantity::One, Quantity::Few, Quantity::Many],
+                    },
+                ],
+                unused_quantities: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn validate_errors_for_unused_quantities_if_asked_to() {
+        let plurals = vec![
+            AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::One, "%d file"), (Quantity::Other, "%d files")],
+            ),
+            AndroidPlurals::localizable("p2", vec![(Quantity::Other, "%d 個")]),
+        ];
+
+        assert_eq!(
+            super::validate(&plurals, Some("ja"), true).unwrap_err(),
+            InvalidPlurals {
+                missing_quantities: vec![],
+                unused_quantities: vec![PluralsQuantities {
+                    name: String::from("p1"),
+                    quantities: vec![Quantity::One],
+                }],
+            }
+        );
+
+        assert_eq!(
+            super::validate(&plurals, Some("b+ru+RU"), true).unwrap_err(),
+            InvalidPlurals {
+                missing_quantities: vec![
+                    PluralsQuantities {
+                        name: String::from("p1"),
+                        quantities: vec![Quantity::Few, Quantity::Many],
+                    },
+                    PluralsQuantities {
+                        name: String::from("p2"),
+                        quantities: vec![Quantity::One, Quantity::Few, Quantity::Many],
+                    },
+                ],
+                unused_quantities: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn validate_only_requires_other_for_unknown_languages() {
+        let plurals = vec![
+            AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::Zero, "No files"), (Quantity::Other, "%d files")],
+            ),
+            AndroidPlurals::localizable("p2", vec![(Quantity::One, "%d file")]),
+        ];
+
+        assert_eq!(
+            super::validate(&plurals, None, true).unwrap_err(),
+            InvalidPlurals {
+                missing_quantities: vec![PluralsQuantities {
+                    name: String::from("p2"),
+                    quantities: vec![Quantity::Other],
+                }],
+                unused_quantities: vec![],
+            }
+        );
+    }
+}
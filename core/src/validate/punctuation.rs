@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::android_string::AndroidString;
+use crate::ops::sort;
+use crate::util::two_pointer_traversal;
+
+const ELLIPSIS: char = '…';
+const THREE_DOTS: &str = "...";
+const ESCAPED_NEWLINE: &str = "\\n";
+
+/// Which rules foreign texts are linted against
+#[derive(Clone, Debug, PartialEq)]
+pub struct PunctuationConfig {
+    pub rules: Vec<PunctuationRule>,
+
+    /// Locale ID to the rules that aren't run for that locale (like
+    /// `terminal-punctuation` for Thai, which doesn't use full stops)
+    pub exempted_rules_per_locale: HashMap<String, Vec<PunctuationRule>>,
+}
+
+/// Ordered the way they are reported
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PunctuationRule {
+    /// Leading & trailing whitespace of the default text has to be kept & no
+    /// doubled spaces can be introduced
+    Whitespace,
+
+    /// A default text ending with `.`, `:`, `?` or `!` needs a foreign text ending
+    /// with the same (or its full width form, like the CJK `。`) & vice versa
+    TerminalPunctuation,
+
+    /// Both the texts need the same number of newlines (`\n`)
+    Newlines,
+
+    /// Both the texts need to use the same ellipsis (`…` or `...`)
+    Ellipsis,
+}
+
+impl Default for PunctuationConfig {
+    fn default() -> Self {
+        PunctuationConfig {
+            rules: PunctuationRule::ALL.to_vec(),
+            exempted_rules_per_locale: HashMap::new(),
+        }
+    }
+}
+
+impl PunctuationConfig {
+    pub(crate) fn rules_for(&self, locale_id: &str) -> Vec<PunctuationRule> {
+        let exempted_rules = self.exempted_rules_per_locale.get(locale_id);
+        self.rules
+            .iter()
+            .filter(|rule| !exempted_rules.is_some_and(|rules| rules.contains(rule)))
+            .cloned()
+            .collect()
+    }
+}
+
+impl PunctuationRule {
+    pub const ALL: [PunctuationRule; 4] = [
+        PunctuationRule::Whitespace,
+        PunctuationRule::TerminalPunctuation,
+        PunctuationRule::Newlines,
+        PunctuationRule::Ellipsis,
+    ];
+
+    pub fn from_name(name: &str) -> Option<PunctuationRule> {
+        match name {
+            "whitespace" => Some(PunctuationRule::Whitespace),
+            "terminal-punctuation" => Some(PunctuationRule::TerminalPunctuation),
+            "newlines" => Some(PunctuationRule::Newlines),
+            "ellipsis" => Some(PunctuationRule::Ellipsis),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PunctuationRule::Whitespace => "whitespace",
+            PunctuationRule::TerminalPunctuation => "terminal-punctuation",
+            PunctuationRule::Newlines => "newlines",
+            PunctuationRule::Ellipsis => "ellipsis",
+        }
+    }
+
+    fn is_broken_by(self, default_text: &str, foreign_text: &str) -> bool {
+        match self {
+            PunctuationRule::Whitespace => {
+                default_text.starts_with(char::is_whitespace)
+                    != foreign_text.starts_with(char::is_whitespace)
+                    || default_text.ends_with(char::is_whitespace)
+                        != foreign_text.ends_with(char::is_whitespace)
+                    || (foreign_text.contains("  ") && !default_text.contains("  "))
+            }
+
+            PunctuationRule::TerminalPunctuation => {
+                terminal_punctuation(default_text) != terminal_punctuation(foreign_text)
+            }
+
+            PunctuationRule::Newlines => {
+                newlines_count(default_text) != newlines_count(foreign_text)
+            }
+
+            PunctuationRule::Ellipsis => match (ellipsis(default_text), ellipsis(foreign_text)) {
+                (Some(default_ellipsis), Some(foreign_ellipsis)) => {
+                    default_ellipsis != foreign_ellipsis
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for PunctuationRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Foreign texts are only linted against localizable default texts. Empty
+/// foreign texts are left to the other validations
+pub fn validate(
+    default_strings: &mut [AndroidString],
+    foreign_strings: &mut [AndroidString],
+    rules: &[PunctuationRule],
+) -> Result<(), Inconsistencies> {
+    // Sort both the lists
+    sort::sort_android_strings_by_name(default_strings);
+    sort::sort_android_strings_by_name(foreign_strings);
+
+    let mut inconsistencies = vec![];
+    two_pointer_traversal::compare(
+        default_strings,
+        foreign_strings,
+        |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+        |default_string, foreign_string| {
+            if !default_string.is_localizable()
+                || default_string.is_reference()
+                || foreign_string.value().is_empty()
+            {
+                return;
+            }
+
+            let mut broken_rules: Vec<PunctuationRule> = rules
+                .iter()
+                .cloned()
+                .filter(|rule| rule.is_broken_by(default_string.value(), foreign_string.value()))
+                .collect();
+
+            if !broken_rules.is_empty() {
+                broken_rules.sort();
+                broken_rules.dedup();
+                inconsistencies.push(Inconsistency {
+                    android_string: foreign_string.clone(),
+                    rules: broken_rules,
+                })
+            }
+        },
+    );
+
+    if inconsistencies.is_empty() {
+        Ok(())
+    } else {
+        Err(Inconsistencies { inconsistencies })
+    }
+}
+
+/// Full width forms are folded into their ASCII counterparts. Ellipses aren't
+/// terminal punctuation
+fn terminal_punctuation(text: &str) -> Option<char> {
+    let text = text.trim_end();
+    if text.ends_with(ELLIPSIS) || text.ends_with(THREE_DOTS) {
+        return None;
+    }
+
+    match text.chars().last()? {
+        '.' | '。' | '．' => Some('.'),
+        ':' | '：' => Some(':'),
+        '?' | '？' | '؟' => Some('?'),
+        '!' | '！' => Some('!'),
+        _ => None,
+    }
+}
+
+/// Both literal newlines & escaped ones (`\n`) are counted
+fn newlines_count(text: &str) -> usize {
+    text.matches('\n').count() + text.matches(ESCAPED_NEWLINE).count()
+}
+
+fn ellipsis(text: &str) -> Option<&'static str> {
+    if text.contains(ELLIPSIS) {
+        Some("…")
+    } else if text.contains(THREE_DOTS) {
+        Some(THREE_DOTS)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Inconsistencies {
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Inconsistency {
+    pub android_string: AndroidString,
+    pub rules: Vec<PunctuationRule>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::android_string::AndroidString;
+
+    use super::{Inconsistencies, Inconsistency, PunctuationConfig, PunctuationRule};
+
+    #[test]
+    fn validate_passes_for_consistent_texts() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Name: "),
+            AndroidString::localizable("s2", "Why?"),
+            AndroidString::localizable("s3", "Loading…"),
+            AndroidString::localizable("s4", "Line 1\\nLine 2."),
+            AndroidString::unlocalizable("s5", "Acme"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s1", "Nom : "),
+            AndroidString::localizable("s2", "¿Por qué?"),
+            AndroidString::localizable("s3", "読み込み中…"),
+            AndroidString::localizable("s4", "一行目\\n二行目。"),
+            AndroidString::localizable("s5", "Acme."),
+        ];
+
+        assert!(super::validate(
+            &mut default_strings,
+            &mut foreign_strings,
+            &PunctuationRule::ALL
+        )
+        .is_ok())
+    }
+
+    #[test]
+    fn validate_errors_for_inconsistent_texts() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Name:"),
+            AndroidString::localizable("s2", "Loading..."),
+            AndroidString::localizable("s3", "Line 1\\nLine 2"),
+            AndroidString::localizable("s4", "Save"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s4", "Enregistrer "),
+            AndroidString::localizable("s3", "Ligne 1 Ligne 2."),
+            AndroidString::localizable("s2", "Chargement…"),
+            AndroidString::localizable("s1", "Nom  "),
+        ];
+
+        assert_eq!(
+            super::validate(
+                &mut default_strings,
+                &mut foreign_strings,
+                &PunctuationRule::ALL
+            )
+            .unwrap_err(),
+            Inconsistencies {
+                inconsistencies: vec![
+                    Inconsistency {
+                        android_string: AndroidString::localizable("s1", "Nom  "),
+                        rules: vec![
+                            PunctuationRule::Whitespace,
+                            PunctuationRule::TerminalPunctuation
+                        ],
+                    },
+                    Inconsistency {
+                        android_string: AndroidString::localizable("s2", "Chargement…"),
+                        rules: vec![PunctuationRule::Ellipsis],
+                    },
+                    Inconsistency {
+                        android_string: AndroidString::localizable("s3", "Ligne 1 Ligne 2."),
+                        rules: vec![
+                            PunctuationRule::TerminalPunctuation,
+                            PunctuationRule::Newlines
+                        ],
+                    },
+                    Inconsistency {
+                        android_string: AndroidString::localizable("s4", "Enregistrer "),
+                        rules: vec![PunctuationRule::Whitespace],
+                    },
+                ],
+            }
+        )
+    }
+
+    #[test]
+    fn validate_only_runs_passed_in_rules() {
+        let mut default_strings = vec![AndroidString::localizable("s1", "Done.")];
+        let mut foreign_strings = vec![AndroidString::localizable("s1", "เสร็จแล้ว")];
+
+        assert!(super::validate(
+            &mut default_strings,
+            &mut foreign_strings,
+            &[PunctuationRule::Whitespace]
+        )
+        .is_ok())
+    }
+
+    #[test]
+    fn finds_rules_for_locale() {
+        let mut exempted_rules_per_locale = HashMap::new();
+        exempted_rules_per_locale.insert(
+            String::from("th"),
+            vec![PunctuationRule::TerminalPunctuation],
+        );
+
+        let config = PunctuationConfig {
+            exempted_rules_per_locale,
+            ..Default::default()
+        };
+
+        assert_eq!(config.rules_for("fr"), PunctuationRule::ALL.to_vec());
+        assert_eq!(
+            config.rules_for("th"),
+            vec![
+                PunctuationRule::Whitespace,
+                PunctuationRule::Newlines,
+                PunctuationRule::Ellipsis
+            ]
+        );
+    }
+
+    #[test]
+    fn rule_round_trips_through_name() {
+        for rule in PunctuationRule::ALL.iter() {
+            assert_eq!(PunctuationRule::from_name(rule.name()), Some(*rule))
+        }
+
+        assert_eq!(PunctuationRule::from_name("tabs"), None)
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::Path;
+
+use crate::android_string::AndroidString;
+use crate::constants;
+use crate::error::{Error, ResultExt};
+use crate::ops::sort;
+use crate::reader::xml_reader;
+use crate::util::two_pointer_traversal;
+use crate::util::xml_utilities;
+
+/// Names of the strings defined across all the XML files of the default values
+/// dir. References can point to any of them
+pub fn read_defined_names(res_dir_path: &Path) -> Result<HashSet<String>, Error> {
+    let mut defined_names = HashSet::new();
+    for file_path in xml_utilities::find_values_xml_files(res_dir_path, None)? {
+        let file = File::open(&file_path).with_context(file_path.clone())?;
+        defined_names.extend(
+            xml_reader::read_defined_names(file)
+                .with_context(file_path.clone())?
+                .into_iter()
+                .filter(|defined_name| defined_name.tag_name == constants::elements::STRING)
+                .map(|defined_name| defined_name.name),
+        );
+    }
+
+    Ok(defined_names)
+}
+
+/// Default strings pointing (`@string/name`) to strings that aren't defined
+pub fn validate_default(
+    default_strings: &[AndroidString],
+    defined_names: &HashSet<String>,
+) -> Result<(), InvalidReferences> {
+    let dangling_references = find_dangling_references(default_strings, defined_names);
+    if dangling_references.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidReferences {
+            dangling_references,
+            replaced_references: vec![],
+        })
+    }
+}
+
+/// Foreign strings pointing to strings that aren't defined in the default locale
+/// & foreign strings that have a text of their own although their default string
+/// is an alias of another string. Aliases pick up the localized texts of the
+/// strings they point to & so, needn't be localized
+pub fn validate_foreign(
+    default_strings: &mut [AndroidString],
+    foreign_strings: &mut [AndroidString],
+    defined_names: &HashSet<String>,
+) -> Result<(), InvalidReferences> {
+    // Sort both the lists
+    sort::sort_android_strings_by_name(default_strings);
+    sort::sort_android_strings_by_name(foreign_strings);
+
+    let dangling_references = find_dangling_references(foreign_strings, defined_names);
+    let mut replaced_references = vec![];
+    two_pointer_traversal::compare(
+        default_strings,
+        foreign_strings,
+        |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+        |default_string, foreign_string| {
+            if default_string.is_reference() && !foreign_string.is_reference() {
+                replaced_references.push(foreign_string.clone())
+            }
+        },
+    );
+
+    if dangling_references.is_empty() && replaced_references.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidReferences {
+            dangling_references,
+            replaced_references,
+        })
+    }
+}
+
+fn find_dangling_references(
+    strings: &[AndroidString],
+    defined_names: &HashSet<String>,
+) -> Vec<AndroidString> {
+    strings
+        .iter()
+        .filter(|s| {
+            s.referenced_name()
+                .is_some_and(|name| !defined_names.contains(name))
+        })
+        .cloned()
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidReferences {
+    pub dangling_references: Vec<AndroidString>,
+    pub replaced_references: Vec<AndroidString>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use test_utilities;
+
+    use crate::android_string::AndroidString;
+
+    use super::InvalidReferences;
+
+    #[test]
+    fn reads_defined_names_across_files() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_dir.path());
+        test_utilities::file::write_content(
+            default_strings.path.clone(),
+            r#"<resources><string name="s1">Save</string><plurals name="p1"><item quantity="other">Files</item></plurals></resources>"#,
+        );
+        test_utilities::file::write_content(
+            default_strings.path.with_file_name("donottranslate.xml"),
+            r#"<resources><string name="s2" translatable="false">Acme</string></resources>"#,
+        );
+
+        assert_eq!(
+            super::read_defined_names(res_dir.path()).unwrap(),
+            defined_names(&["s1", "s2"])
+        )
+    }
+
+    #[test]
+    fn validate_default_passes_for_defined_references() {
+        let default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable("s2", "@string/s1"),
+            AndroidString::localizable("s3", "@android:string/ok"),
+        ];
+
+        assert!(
+            super::validate_default(&default_strings, &defined_names(&["s1", "s2", "s3"])).is_ok()
+        )
+    }
+
+    #[test]
+    fn validate_default_errors_for_dangling_references() {
+        let default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable("s2", "@string/s3"),
+        ];
+
+        assert_eq!(
+            super::validate_default(&default_strings, &defined_names(&["s1", "s2"])).unwrap_err(),
+            InvalidReferences {
+                dangling_references: vec![AndroidString::localizable("s2", "@string/s3")],
+                replaced_references: vec![],
+            }
+        )
+    }
+
+    #[test]
+    fn validate_foreign_passes_for_kept_references() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable("s2", "@string/s1"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s2", "@string/s1"),
+            AndroidString::localizable("s1", "Enregistrer"),
+        ];
+
+        assert!(super::validate_foreign(
+            &mut default_strings,
+            &mut foreign_strings,
+            &defined_names(&["s1", "s2"])
+        )
+        .is_ok())
+    }
+
+    #[test]
+    fn validate_foreign_errors_for_dangling_and_replaced_references() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::localizable("s2", "@string/s1"),
+            AndroidString::localizable("s3", "Cancel"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s3", "@string/s4"),
+            AndroidString::localizable("s2", "Enregistrer"),
+            AndroidString::localizable("s1", "Enregistrer"),
+        ];
+
+        assert_eq!(
+            super::validate_foreign(
+                &mut default_strings,
+                &mut foreign_strings,
+                &defined_names(&["s1", "s2", "s3"])
+            )
+            .unwrap_err(),
+            InvalidReferences {
+                dangling_references: vec![AndroidString::localizable("s3", "@string/s4")],
+                replaced_references: vec![AndroidString::localizable("s2", "Enregistrer")],
+            }
+        )
+    }
+
+    fn defined_names(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| String::from(*name)).collect()
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::android_plurals::AndroidPlurals;
+use crate::android_string::AndroidString;
+use crate::error::{Error, ResultExt};
+use crate::ops::sort;
+use crate::util::foreign_locale_ids_finder;
+use crate::util::staged_files::StagedFiles;
+use crate::util::two_pointer_traversal;
+use crate::util::xml_utilities;
+use crate::writer::xml_writer;
+
+/// Android lint treats foreign strings (& plurals) whose default is marked
+/// `translatable="false"` as errors
+pub fn validate(
+    default_strings: &mut [AndroidString],
+    foreign_strings: &mut [AndroidString],
+    default_plurals: &[AndroidPlurals],
+    foreign_plurals: &[AndroidPlurals],
+) -> Result<(), UntranslatableStrings> {
+    // Sort both the lists
+    sort::sort_android_strings_by_name(default_strings);
+    sort::sort_android_strings_by_name(foreign_strings);
+
+    let mut untranslatable_strings = vec![];
+    two_pointer_traversal::compare(
+        default_strings,
+        foreign_strings,
+        |default_string, foreign_string| default_string.name().cmp(foreign_string.name()),
+        |default_string, foreign_string| {
+            if !default_string.is_localizable() {
+                untranslatable_strings.push(foreign_string.clone())
+            }
+        },
+    );
+
+    let untranslatable_plural_names = untranslatable_plural_names(default_plurals);
+    let untranslatable_plurals: Vec<AndroidPlurals> = foreign_plurals
+        .iter()
+        .filter(|p| untranslatable_plural_names.contains(p.name()))
+        .cloned()
+        .collect();
+
+    if untranslatable_strings.is_empty() && untranslatable_plurals.is_empty() {
+        Ok(())
+    } else {
+        Err(UntranslatableStrings {
+            untranslatable_strings,
+            untranslatable_plurals,
+        })
+    }
+}
+
+/// Removes the strings & plurals marked `translatable="false"` in the default
+/// locale from the `strings.xml` files of all the foreign locales & returns the
+/// paths of the files that were rewritten
+pub fn remove_untranslatable_strings<P: AsRef<Path>>(
+    res_dir_path: P,
+) -> Result<Vec<PathBuf>, Error> {
+    let res_dir_path = res_dir_path.as_ref();
+    let (default_strings, default_plurals) =
+        xml_utilities::read_default_strings(res_dir_path)?.into_strings_and_plurals();
+    let untranslatable_names: HashSet<String> = default_strings
+        .into_iter()
+        .filter(|s| !s.is_localizable())
+        .map(|s| String::from(s.name()))
+        .collect();
+    let untranslatable_plural_names = untranslatable_plural_names(&default_plurals);
+
+    let mut updated_file_paths = vec![];
+    if untranslatable_names.is_empty() && untranslatable_plural_names.is_empty() {
+        return Ok(updated_file_paths);
+    }
+
+    let mut staged_files = StagedFiles::new();
+    for locale_id in foreign_locale_ids_finder::find(res_dir_path)? {
+        let (foreign_strings, foreign_plurals) =
+            xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?
+                .into_strings_and_plurals();
+
+        let foreign_count = foreign_strings.len() + foreign_plurals.len();
+        let foreign_strings: Vec<AndroidString> = foreign_strings
+            .into_iter()
+            .filter(|s| !untranslatable_names.contains(s.name()))
+            .collect();
+        let foreign_plurals: Vec<AndroidPlurals> = foreign_plurals
+            .into_iter()
+            .filter(|p| !untranslatable_plural_names.contains(p.name()))
+            .collect();
+
+        if foreign_strings.len() + foreign_plurals.len() == foreign_count {
+            continue;
+        }
+
+        let output_file_path = xml_utilities::foreign_strings_file_path(res_dir_path, &locale_id);
+        let mut file = staged_files.stage(output_file_path.clone())?;
+        xml_writer::write_with_plurals(&mut file, foreign_strings, foreign_plurals)
+            .with_context(output_file_path.clone())?;
+
+        updated_file_paths.push(output_file_path);
+    }
+
+    staged_files.commit()?;
+    Ok(updated_file_paths)
+}
+
+fn untranslatable_plural_names(default_plurals: &[AndroidPlurals]) -> HashSet<&str> {
+    default_plurals
+        .iter()
+        .filter(|p| !p.is_localizable())
+        .map(AndroidPlurals::name)
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UntranslatableStrings {
+    pub untranslatable_strings: Vec<AndroidString>,
+    pub untranslatable_plurals: Vec<AndroidPlurals>,
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utilities;
+
+    use crate::android_plurals::{AndroidPlurals, Quantity};
+    use crate::android_string::AndroidString;
+    use crate::util::xml_utilities;
+    use crate::writer::xml_writer;
+
+    use super::UntranslatableStrings;
+
+    #[test]
+    fn validate_passes_without_translated_untranslatable_strings() {
+        let mut default_strings = vec![
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::unlocalizable("s2", "Acme"),
+        ];
+
+        let mut foreign_strings = vec![AndroidString::localizable("s1", "Enregistrer")];
+
+        let default_plurals = vec![
+            AndroidPlurals::localizable("p1", vec![(Quantity::Other, "Files")]),
+            AndroidPlurals::unlocalizable("p2", vec![(Quantity::Other, "Acmes")]),
+        ];
+
+        let foreign_plurals = vec![AndroidPlurals::localizable(
+            "p1",
+            vec![(Quantity::Other, "Fichiers")],
+        )];
+
+        assert!(super::validate(
+            &mut default_strings,
+            &mut foreign_strings,
+            &default_plurals,
+            &foreign_plurals
+        )
+        .is_ok())
+    }
+
+    #[test]
+    fn validate_errors_for_translated_untranslatable_strings() {
+        let mut default_strings = vec![
+            AndroidString::unlocalizable("s3", "Acme"),
+            AndroidString::localizable("s1", "Save"),
+            AndroidString::unlocalizable("s2", "https://acme.com"),
+        ];
+
+        let mut foreign_strings = vec![
+            AndroidString::localizable("s3", "Acmé"),
+            AndroidString::localizable("s1", "Enregistrer"),
+            AndroidString::localizable("s2", "https://acme.fr"),
+        ];
+
+        assert_eq!(
+            super::validate(&mut default_strings, &mut foreign_strings, &[], &[]).unwrap_err(),
+            UntranslatableStrings {
+                untranslatable_strings: vec![
+                    AndroidString::localizable("s2", "https://acme.fr"),
+                    AndroidString::localizable("s3", "Acmé"),
+                ],
+                untranslatable_plurals: vec![],
+            }
+        )
+    }
+
+    #[test]
+    fn validate_errors_for_translated_untranslatable_plurals() {
+        let default_plurals = vec![
+            AndroidPlurals::localizable("p1", vec![(Quantity::Other, "Files")]),
+            AndroidPlurals::unlocalizable("p2", vec![(Quantity::Other, "Acmes")]),
+        ];
+
+        let foreign_plurals = vec![
+            AndroidPlurals::localizable("p1", vec![(Quantity::Other, "Fichiers")]),
+            AndroidPlurals::localizable("p2", vec![(Quantity::Other, "Acmés")]),
+        ];
+
+        assert_eq!(
+            super::validate(&mut [], &mut [], &default_plurals, &foreign_plurals).unwrap_err(),
+            UntranslatableStrings {
+                untranslatable_strings: vec![],
+                untranslatable_plurals: vec![AndroidPlurals::localizable(
+                    "p2",
+                    vec![(Quantity::Other, "Acmés")]
+                )],
+            }
+        )
+    }
+
+    #[test]
+    fn removes_untranslatable_strings_from_foreign_files() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_dir.path());
+        let mut french_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "fr");
+        let mut spanish_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "es");
+
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![
+                AndroidString::localizable("s1", "Save"),
+                AndroidString::unlocalizable("s2", "Acme"),
+            ],
+        )
+        .unwrap();
+
+        xml_writer::write(
+            &mut french_strings.file,
+            vec![
+                AndroidString::localizable("s1", "Enregistrer"),
+                AndroidString::localizable("s2", "Acmé"),
+            ],
+        )
+        .unwrap();
+
+        xml_writer::write(
+            &mut spanish_strings.file,
+            vec![AndroidString::localizable("s1", "Guardar")],
+        )
+        .unwrap();
+
+        assert_eq!(
+            super::remove_untranslatable_strings(res_dir.path()).unwrap(),
+            vec![french_strings.path]
+        );
+
+        assert_eq!(
+            xml_utilities::read_foreign_strings(res_dir.path(), "fr")
+                .unwrap()
+                .into_strings(),
+            vec![AndroidString::localizable("s1", "Enregistrer")]
+        );
+
+        assert_eq!(
+            xml_utilities::read_foreign_strings(res_dir.path(), "es")
+                .unwrap()
+                .into_strings(),
+            vec![AndroidString::localizable("s1", "Guardar")]
+        );
+    }
+
+    #[test]
+    fn removes_nothing_if_any_foreign_file_can_not_be_read() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_dir.path());
+        let mut french_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "fr");
+        let spanish_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "es");
+
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::unlocalizable("s1", "Acme")],
+        )
+        .unwrap();
+
+        xml_writer::write(
+            &mut french_strings.file,
+            vec![AndroidString::localizable("s1", "Acmé")],
+        )
+        .unwrap();
+
+        test_utilities::file::write_content(&spanish_strings.path, "<resources><string");
+        let french_content = test_utilities::file::read_content(&french_strings.path);
+
+        assert!(super::remove_untranslatable_strings(res_dir.path()).is_err());
+        assert_eq!(
+            test_utilities::file::read_content(&french_strings.path),
+            french_content
+        );
+        assert_eq!(
+            std::fs::read_dir(french_strings.path.parent().unwrap())
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn removes_untranslatable_plurals_from_foreign_files() {
+        let res_dir = tempfile::tempdir().unwrap();
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_dir.path());
+        let mut french_strings =
+            test_utilities::res::setup_empty_strings_for_locale(res_dir.path(), "fr");
+
+        xml_writer::write_with_plurals(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "Save")],
+            vec![
+                AndroidPlurals::localizable("p1", vec![(Quantity::Other, "Files")]),
+                AndroidPlurals::unlocalizable("p2", vec![(Quantity::Other, "Acmes")]),
+            ],
+        )
+        .unwrap();
+
+        xml_writer::write_with_plurals(
+            &mut french_strings.file,
+            vec![AndroidString::localizable("s1", "Enregistrer")],
+            vec![
+                AndroidPlurals::localizable("p1", vec![(Quantity::Other, "Fichiers")]),
+                AndroidPlurals::localizable("p2", vec![(Quantity::Other, "Acmés")]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            super::remove_untranslatable_strings(res_dir.path()).unwrap(),
+            vec![french_strings.path]
+        );
+
+        let french_strings = xml_utilities::read_foreign_strings(res_dir.path(), "fr").unwrap();
+        assert_eq!(
+            french_strings.strings(),
+            &[AndroidString::localizable("s1", "Enregistrer")][..]
+        );
+        assert_eq!(
+            french_strings.plurals(),
+            &[AndroidPlurals::localizable(
+                "p1",
+                vec![(Quantity::Other, "Fichiers")]
+            )][..]
+        );
+    }
+}
@@ -1,43 +1,114 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
+use crate::android_plurals::AndroidPlurals;
 use crate::android_string::AndroidString;
 use crate::error::Error;
 use crate::util::foreign_locale_ids_finder;
 use crate::util::xml_utilities;
 use crate::util::xml_utilities::StringsWithPath;
 use crate::validate::apostrophe;
+use crate::validate::duplicate_names;
 use crate::validate::format_string;
 use crate::validate::format_string::ParsedData;
+use crate::validate::identical_texts;
+use crate::validate::max_length;
 use crate::validate::missing_strings;
+use crate::validate::plurals;
+use crate::validate::punctuation;
+use crate::validate::references;
+use crate::validate::untranslatable_strings;
+
+pub use crate::validate::identical_texts::IdenticalTextsConfig;
+pub use crate::validate::punctuation::{PunctuationConfig, PunctuationRule};
+pub use crate::validate::untranslatable_strings::remove_untranslatable_strings;
+
+/// What `validate` checks apart from the checks that are always run. The default
+/// options are as strict as the CLI's defaults
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidateOptions {
+    /// Default strings that aren't in a foreign locale aren't reported
+    pub skip_unlocalized: bool,
+
+    /// Quantities of plurals that the language of their locale doesn't use are
+    /// reported too
+    pub fail_on_unused_quantities: bool,
+
+    /// Foreign texts can't be longer than their default text times this ratio
+    pub max_length_ratio: Option<f32>,
+
+    /// Foreign texts that are the same as their default text are reported
+    pub identical_texts: Option<IdenticalTextsConfig>,
+
+    /// Foreign texts are linted against their default text for whitespace &
+    /// punctuation
+    pub punctuation: Option<PunctuationConfig>,
+}
 
-/// Runs all validations for default & all foreign strings & returns a collection
-/// of file names on which the validations were run
-pub fn validate(
-    res_dir_path: &str,
-    fail_on_unlocalized: bool,
-) -> Result<Result<Vec<String>, Vec<InvalidStringsFile>>, Error> {
+/// Runs all validations for default & all foreign strings & returns the paths
+/// of the files that were validated. These are always checked -
+///
+/// - Apostrophes & format strings
+/// - Strings that are missing from a foreign locale
+/// - Names defined more than once across the XML files of a values dir
+///   (reported against the `strings.xml` file of that dir)
+/// - The `tools:maxLength` of the default strings
+/// - Foreign strings & plurals whose default is marked `translatable="false"`
+/// - References (`@string/name`) to strings that aren't defined & foreign
+///   texts replacing a reference
+/// - Quantities of plurals as per the language of their locale
+///
+/// Look @ `ValidateOptions` for the rest
+pub fn validate<P: AsRef<Path>>(
+    res_dir_path: P,
+    options: &ValidateOptions,
+) -> Result<Result<Vec<PathBuf>, Vec<InvalidStringsFile>>, Error> {
+    let res_dir_path = res_dir_path.as_ref();
     let mut path_of_validated_files = vec![];
     let mut invalid_strings_files = vec![];
 
-    let default_strings_with_path = xml_utilities::read_default_strings(Path::new(res_dir_path))?;
+    let default_strings_with_path = xml_utilities::read_default_strings(res_dir_path)?;
     let mut default_parsed_data =
-        format_string::parse_and_build_data(&default_strings_with_path.strings());
-
-    validate_default_strings(
-        &default_strings_with_path,
+        format_string::parse_and_build_data(default_strings_with_path.strings());
+    let defined_names = references::read_defined_names(res_dir_path)?;
+
+    collect_validated_file(
+        validate_default_strings(
+            &default_strings_with_path,
+            &defined_names,
+            duplicate_names::validate(res_dir_path, None)?,
+        ),
         &mut path_of_validated_files,
         &mut invalid_strings_files,
     );
 
-    let mut default_strings = default_strings_with_path.into_strings();
-    let res_dir_path_string = res_dir_path;
-    let locale_ids = foreign_locale_ids_finder::find(res_dir_path_string)?;
+    let (mut default_strings, default_plurals) =
+        default_strings_with_path.into_strings_and_plurals();
+    let locale_ids = foreign_locale_ids_finder::find(res_dir_path)?;
     for locale_id in locale_ids {
-        validate_foreign_strings(
-            xml_utilities::read_foreign_strings(Path::new(res_dir_path), &locale_id)?,
-            &mut default_strings,
-            &mut default_parsed_data,
-            fail_on_unlocalized,
+        collect_validated_file(
+            validate_foreign_strings(
+                xml_utilities::read_foreign_strings(res_dir_path, &locale_id)?,
+                &mut default_strings,
+                &default_plurals,
+                &mut default_parsed_data,
+                &defined_names,
+                ForeignChecks {
+                    locale_id: &locale_id,
+                    fail_on_unlocalized: !options.skip_unlocalized,
+                    fail_on_unused_quantities: options.fail_on_unused_quantities,
+                    max_length_ratio: options.max_length_ratio,
+                    identical_allowed_texts: options
+                        .identical_texts
+                        .as_ref()
+                        .and_then(|config| config.allowed_texts_for(&locale_id)),
+                    punctuation_rules: options
+                        .punctuation
+                        .as_ref()
+                        .map(|config| config.rules_for(&locale_id)),
+                },
+                duplicate_names::validate(res_dir_path, Some(&locale_id))?,
+            ),
             &mut path_of_validated_files,
             &mut invalid_strings_files,
         )
@@ -50,41 +121,99 @@ pub fn validate(
     }
 }
 
-fn validate_default_strings(
-    strings_with_path: &StringsWithPath,
-    path_of_validated_files: &mut Vec<String>,
+/// Checks that can be configured differently for every foreign locale
+struct ForeignChecks<'a> {
+    locale_id: &'a str,
+    fail_on_unlocalized: bool,
+    fail_on_unused_quantities: bool,
+    max_length_ratio: Option<f32>,
+    identical_allowed_texts: Option<Vec<&'a str>>,
+    punctuation_rules: Option<Vec<PunctuationRule>>,
+}
+
+fn collect_validated_file(
+    potential_invalid_file: InvalidStringsFile,
+    path_of_validated_files: &mut Vec<PathBuf>,
     invalid_strings_files: &mut Vec<InvalidStringsFile>,
 ) {
-    let default_strings_file_path = String::from(strings_with_path.path());
+    if potential_invalid_file.has_errors() {
+        invalid_strings_files.push(potential_invalid_file)
+    } else {
+        path_of_validated_files.push(potential_invalid_file.file_path)
+    }
+}
+
+fn validate_default_strings(
+    strings_with_path: &StringsWithPath,
+    defined_names: &HashSet<String>,
+    dn_result: Result<(), duplicate_names::DuplicateNames>,
+) -> InvalidStringsFile {
     let apos_result = apostrophe::validate(strings_with_path.strings());
+    let ref_result = references::validate_default(strings_with_path.strings(), defined_names);
+    let pl_result = plurals::validate(strings_with_path.plurals(), None, false);
+
+    let mut potential_invalid_file =
+        InvalidStringsFile::new(strings_with_path.path().to_path_buf());
     if let Err(apos_error) = apos_result {
-        invalid_strings_files.push(InvalidStringsFile {
-            file_path: default_strings_file_path,
-            apostrophe_error: Some(apos_error),
-            format_string_error: None,
-            missing_strings_error: None,
-        })
-    } else {
-        path_of_validated_files.push(default_strings_file_path)
+        potential_invalid_file.apostrophe_error = Some(apos_error);
+    }
+
+    if let Err(ref_error) = ref_result {
+        potential_invalid_file.references_error = Some(ref_error);
     }
+
+    if let Err(pl_error) = pl_result {
+        potential_invalid_file.plurals_error = Some(pl_error);
+    }
+
+    if let Err(dn_error) = dn_result {
+        potential_invalid_file.duplicate_names_error = Some(dn_error);
+    }
+
+    potential_invalid_file
 }
 
 fn validate_foreign_strings(
     strings_with_path: StringsWithPath,
-    mut default_strings: &mut [AndroidString],
-    mut default_parsed_data: &mut [ParsedData],
-    fail_on_unlocalized: bool,
-    path_of_validated_files: &mut Vec<String>,
-    invalid_strings_files: &mut Vec<InvalidStringsFile>,
-) {
-    let foreign_strings_file_path = String::from(strings_with_path.path());
-    let mut foreign_strings = strings_with_path.into_strings();
+    default_strings: &mut [AndroidString],
+    default_plurals: &[AndroidPlurals],
+    default_parsed_data: &mut [ParsedData],
+    defined_names: &HashSet<String>,
+    checks: ForeignChecks,
+    dn_result: Result<(), duplicate_names::DuplicateNames>,
+) -> InvalidStringsFile {
+    let foreign_strings_file_path = strings_with_path.path().to_path_buf();
+    let (mut foreign_strings, foreign_plurals) = strings_with_path.into_strings_and_plurals();
 
     let apos_result = apostrophe::validate(&foreign_strings);
-    let fs_result = format_string::validate(&mut default_parsed_data, &mut foreign_strings);
-    let ms_result = missing_strings::validate(&mut default_strings, &mut foreign_strings);
+    let fs_result = format_string::validate(default_parsed_data, &mut foreign_strings);
+    let ms_result = missing_strings::validate(default_strings, &mut foreign_strings);
+    let us_result = untranslatable_strings::validate(
+        default_strings,
+        &mut foreign_strings,
+        default_plurals,
+        &foreign_plurals,
+    );
+    let pl_result = plurals::validate(
+        &foreign_plurals,
+        Some(checks.locale_id),
+        checks.fail_on_unused_quantities,
+    );
+    let ref_result =
+        references::validate_foreign(default_strings, &mut foreign_strings, defined_names);
+    let ml_result = max_length::validate(
+        default_strings,
+        &mut foreign_strings,
+        checks.max_length_ratio,
+    );
+    let it_result = checks.identical_allowed_texts.map(|allowed_texts| {
+        identical_texts::validate(default_strings, &mut foreign_strings, &allowed_texts)
+    });
+    let p_result = checks
+        .punctuation_rules
+        .map(|rules| punctuation::validate(default_strings, &mut foreign_strings, &rules));
 
-    let mut potential_invalid_file = InvalidStringsFile::new(foreign_strings_file_path.clone());
+    let mut potential_invalid_file = InvalidStringsFile::new(foreign_strings_file_path);
 
     if let Err(apos_error) = apos_result {
         potential_invalid_file.apostrophe_error = Some(apos_error);
@@ -94,29 +223,60 @@ fn validate_foreign_strings(
         potential_invalid_file.format_string_error = Some(fs_error);
     }
 
-    if fail_on_unlocalized {
+    if checks.fail_on_unlocalized {
         if let Err(ms_error) = ms_result {
             potential_invalid_file.missing_strings_error = Some(ms_error);
         }
     }
 
-    if potential_invalid_file.has_errors() {
-        invalid_strings_files.push(potential_invalid_file)
-    } else {
-        path_of_validated_files.push(foreign_strings_file_path)
+    if let Err(us_error) = us_result {
+        potential_invalid_file.untranslatable_strings_error = Some(us_error);
+    }
+
+    if let Err(ref_error) = ref_result {
+        potential_invalid_file.references_error = Some(ref_error);
+    }
+
+    if let Err(pl_error) = pl_result {
+        potential_invalid_file.plurals_error = Some(pl_error);
+    }
+
+    if let Err(ml_error) = ml_result {
+        potential_invalid_file.max_length_error = Some(ml_error);
+    }
+
+    if let Some(Err(it_error)) = it_result {
+        potential_invalid_file.identical_texts_error = Some(it_error);
     }
+
+    if let Some(Err(p_error)) = p_result {
+        potential_invalid_file.punctuation_error = Some(p_error);
+    }
+
+    if let Err(dn_error) = dn_result {
+        potential_invalid_file.duplicate_names_error = Some(dn_error);
+    }
+
+    potential_invalid_file
 }
 
 #[derive(Debug, Default, PartialEq)]
 pub struct InvalidStringsFile {
-    pub file_path: String,
+    pub file_path: PathBuf,
     pub apostrophe_error: Option<apostrophe::InvalidStrings>,
     pub format_string_error: Option<format_string::Mismatches>,
     pub missing_strings_error: Option<missing_strings::MissingStrings>,
+    pub untranslatable_strings_error: Option<untranslatable_strings::UntranslatableStrings>,
+    pub references_error: Option<references::InvalidReferences>,
+    pub plurals_error: Option<plurals::InvalidPlurals>,
+    pub max_length_error: Option<max_length::TooLongStrings>,
+    pub identical_texts_error: Option<identical_texts::IdenticalTexts>,
+    pub punctuation_error: Option<punctuation::Inconsistencies>,
+    pub duplicate_names_error: Option<duplicate_names::DuplicateNames>,
 }
 
 impl InvalidStringsFile {
-    fn new(file_path: String) -> InvalidStringsFile {
+    fn new(file_path: PathBuf) -> InvalidStringsFile {
         InvalidStringsFile {
             file_path,
             ..Default::default()
@@ -127,6 +287,13 @@ impl InvalidStringsFile {
         self.apostrophe_error.is_some()
             || self.format_string_error.is_some()
             || self.missing_strings_error.is_some()
+            || self.untranslatable_strings_error.is_some()
+            || self.references_error.is_some()
+            || self.plurals_error.is_some()
+            || self.max_length_error.is_some()
+            || self.identical_texts_error.is_some()
+            || self.punctuation_error.is_some()
+            || self.duplicate_names_error.is_some()
     }
 }
 
@@ -136,9 +303,10 @@ mod tests {
 
     use crate::android_string::AndroidString;
     use crate::validate::apostrophe;
+    use crate::validate::duplicate_names;
     use crate::validate::format_string;
     use crate::validate::missing_strings;
-    use crate::validate::validator::InvalidStringsFile;
+    use crate::validate::validator::{InvalidStringsFile, ValidateOptions};
     use crate::writer::xml_writer;
 
     #[test]
@@ -172,7 +340,7 @@ mod tests {
         )
         .unwrap();
 
-        let mut actual_output = super::validate(res_path.to_str().unwrap(), true)
+        let mut actual_output = super::validate(&res_path, &ValidateOptions::default())
             .unwrap()
             .unwrap();
 
@@ -182,13 +350,61 @@ mod tests {
         test_utilities::list::assert_strict_list_eq(
             actual_output,
             vec![
+                default_strings.path,
                 spanish_strings.path,
                 french_strings.path,
-                default_strings.path,
             ],
         )
     }
 
+    #[test]
+    fn errors_for_duplicate_names_across_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut res_path = tempdir.path().to_path_buf();
+        res_path.push("res");
+
+        let mut default_strings =
+            test_utilities::res::setup_empty_strings_for_default_locale(res_path.clone());
+        xml_writer::write(
+            &mut default_strings.file,
+            vec![AndroidString::localizable("s1", "value")],
+        )
+        .unwrap();
+
+        let mut other_file_path = res_path.clone();
+        other_file_path.push("values");
+        other_file_path.push("donottranslate.xml");
+        test_utilities::file::write_content(
+            other_file_path.clone(),
+            "<resources>\n    <string name=\"s1\">other value</string>\n</resources>",
+        );
+
+        let invalid_strings_files = super::validate(&res_path, &ValidateOptions::default())
+            .unwrap()
+            .unwrap_err();
+
+        test_utilities::list::assert_strict_list_eq(
+            invalid_strings_files,
+            vec![InvalidStringsFile {
+                file_path: default_strings.path.clone(),
+                duplicate_names_error: Some(duplicate_names::DuplicateNames {
+                    duplicate_names: vec![duplicate_names::DuplicateName {
+                        name: String::from("s1"),
+                        first: duplicate_names::Location {
+                            file_path: other_file_path,
+                            line: 2,
+                        },
+                        duplicate: duplicate_names::Location {
+                            file_path: default_strings.path,
+                            line: 3,
+                        },
+                    }],
+                }),
+                ..Default::default()
+            }],
+        )
+    }
+
     #[test]
     fn errors_without_skipping_missing_errors() {
         test_errors(true)
@@ -225,10 +441,15 @@ mod tests {
         let spanish_s2 = AndroidString::localizable("s2", "v'alue %1$d");
         xml_writer::write(&mut spanish_strings.file, vec![spanish_s2.clone()]).unwrap();
 
-        let mut invalid_strings_files =
-            super::validate(res_path.to_str().unwrap(), fail_on_unlocalized)
-                .unwrap()
-                .unwrap_err();
+        let mut invalid_strings_files = super::validate(
+            &res_path,
+            &ValidateOptions {
+                skip_unlocalized: !fail_on_unlocalized,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .unwrap_err();
 
         let missing_strings_error_for_fr: Option<missing_strings::MissingStrings>;
         let missing_strings_error_for_es = if fail_on_unlocalized {
@@ -252,6 +473,21 @@ mod tests {
         test_utilities::list::assert_strict_list_eq(
             invalid_strings_files,
             vec![
+                InvalidStringsFile {
+                    file_path: default_strings.path,
+                    apostrophe_error: Some(apostrophe::InvalidStrings {
+                        invalid_strings: vec![default_s2.clone()],
+                    }),
+                    format_string_error: None,
+                    missing_strings_error: None,
+                    untranslatable_strings_error: None,
+                    references_error: None,
+                    plurals_error: None,
+                    max_length_error: None,
+                    identical_texts_error: None,
+                    punctuation_error: None,
+                    duplicate_names_error: None,
+                },
                 InvalidStringsFile {
                     file_path: spanish_strings.path,
                     apostrophe_error: Some(apostrophe::InvalidStrings {
@@ -270,6 +506,13 @@ mod tests {
                         }],
                     }),
                     missing_strings_error: missing_strings_error_for_es,
+                    untranslatable_strings_error: None,
+                    references_error: None,
+                    plurals_error: None,
+                    max_length_error: None,
+                    identical_texts_error: None,
+                    punctuation_error: None,
+                    duplicate_names_error: None,
                 },
                 InvalidStringsFile {
                     file_path: french_strings.path,
@@ -278,14 +521,13 @@ mod tests {
                     }),
                     format_string_error: None,
                     missing_strings_error: missing_strings_error_for_fr,
-                },
-                InvalidStringsFile {
-                    file_path: default_strings.path,
-                    apostrophe_error: Some(apostrophe::InvalidStrings {
-                        invalid_strings: vec![default_s2],
-                    }),
-                    format_string_error: None,
-                    missing_strings_error: None,
+                    untranslatable_strings_error: None,
+                    references_error: None,
+                    plurals_error: None,
+                    max_length_error: None,
+                    identical_texts_error: None,
+                    punctuation_error: None,
+                    duplicate_names_error: None,
                 },
             ],
         )
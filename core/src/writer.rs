@@ -1,2 +1,5 @@
+pub mod apple_strings_writer;
 pub mod csv_writer;
+pub mod json_writer;
+pub mod stringsdict_writer;
 pub mod xml_writer;
@@ -0,0 +1,79 @@
+use std::io::Write;
+
+use crate::error::InnerError;
+
+/// Key, value & an optional comment (written out right above the key-value pair)
+pub type AppleString = (String, String, Option<String>);
+
+/// Writes out key-value pairs into an Apple `.strings` file. Both keys & values
+/// are escaped as required
+pub fn write<S: Write>(sink: &mut S, strings: Vec<AppleString>) -> Result<(), InnerError> {
+    for (key, value, comment) in strings {
+        if let Some(comment) = comment {
+            // Comments can't be escaped. Just make sure that they don't end early
+            writeln!(sink, "/* {} */", comment.replace("*/", "* /"))?;
+        }
+
+        writeln!(sink, "\"{}\" = \"{}\";", escape(&key), escape(&value))?;
+    }
+
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reader::apple_strings_reader;
+
+    #[test]
+    fn writes_escaped_strings() {
+        let mut sink: Vec<u8> = vec![];
+        super::write(
+            &mut sink,
+            vec![
+                (
+                    String::from("s1"),
+                    String::from("value 1"),
+                    Some(String::from("A */ comment")),
+                ),
+                (
+                    String::from("s2"),
+                    String::from("\"quoted\"\nwith \\ slash"),
+                    None,
+                ),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(sink.clone()).unwrap(),
+            "/* A * / comment */\n\"s1\" = \"value 1\";\n\"s2\" = \"\\\"quoted\\\"\\nwith \\\\ slash\";\n"
+        );
+
+        assert_eq!(
+            apple_strings_reader::read(sink.as_slice()).unwrap(),
+            vec![
+                (String::from("s1"), String::from("value 1")),
+                (
+                    String::from("s2"),
+                    String::from("\"quoted\"\nwith \\ slash")
+                ),
+            ]
+        )
+    }
+}
@@ -1,5 +1,4 @@
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::io::Write;
@@ -8,33 +7,93 @@ use csv;
 
 use android_localization_utilities::DevExpt;
 
+use crate::android_string::AndroidString;
+use crate::constants;
 use crate::error::{Error, InnerError};
+use crate::format::CsvDialect;
 use crate::localizable_strings::LocalizableStrings;
+use crate::ops::sort;
 
 pub fn write(
     strings_list: Vec<LocalizableStrings>,
+    dialect: &CsvDialect,
     sink_provider: &mut dyn SinkProvider,
 ) -> Result<(), Error> {
-    // Split strings into groups requiring localization for the same strings
-    let mut grouped_strings_list: HashMap<u64, Vec<LocalizableStrings>> = HashMap::new();
+    // Split strings into groups requiring localization for the same strings. Groups
+    // are kept in the order they are first seen in, so that the output is stable
+    let mut grouped_strings_list: Vec<(u64, Vec<LocalizableStrings>)> = vec![];
     for strings in strings_list {
-        grouped_strings_list
-            .entry(find_grouping_hash_of(&strings))
-            .or_default()
-            .push(strings)
+        let grouping_hash = find_grouping_hash_of(&strings);
+        match grouped_strings_list
+            .iter_mut()
+            .find(|(hash, _)| *hash == grouping_hash)
+        {
+            Some((_, group)) => group.push(strings),
+            None => grouped_strings_list.push((grouping_hash, vec![strings])),
+        }
     }
 
     // We may need multiple sinks to write locale requiring
     // different strings to be localized
     for (_, some_strings_list) in grouped_strings_list.into_iter() {
+        let default_locale_strings = Vec::from(
+            some_strings_list
+                .first()
+                .expt("Empty strings list!")
+                .default_locale_strings(),
+        );
+
         sink_provider.execute_with_new_sink(Writer {
+            default_locale_strings,
             strings_list: some_strings_list,
+            dialect: dialect.clone(),
         })?;
     }
 
     Ok(())
 }
 
+/// Unlike `write`, every locale is written into a sink of its own even if
+/// other locales need the same strings to be localized
+pub fn write_per_locale(
+    strings_list: Vec<LocalizableStrings>,
+    dialect: &CsvDialect,
+    sink_provider: &mut dyn SinkProvider,
+) -> Result<(), Error> {
+    for strings in strings_list {
+        sink_provider.execute_with_new_sink(Writer {
+            default_locale_strings: Vec::from(strings.default_locale_strings()),
+            strings_list: vec![strings],
+            dialect: dialect.clone(),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Unlike `write`, all the locales are written into a single sink. Every
+/// string that needs to be localized for any of the locales gets a row &
+/// locales that don't need a particular string just have an empty cell
+pub fn write_combined(
+    strings_list: Vec<LocalizableStrings>,
+    dialect: &CsvDialect,
+    sink_provider: &mut dyn SinkProvider,
+) -> Result<(), Error> {
+    let mut default_locale_strings: Vec<AndroidString> = strings_list
+        .iter()
+        .flat_map(|strings| strings.default_locale_strings().iter().cloned())
+        .collect();
+
+    sort::sort_android_strings_by_name(&mut default_locale_strings);
+    default_locale_strings.dedup_by(|a, b| a.name() == b.name());
+
+    sink_provider.execute_with_new_sink(Writer {
+        default_locale_strings,
+        strings_list,
+        dialect: dialect.clone(),
+    })
+}
+
 fn find_grouping_hash_of(strings: &LocalizableStrings) -> u64 {
     let mut hasher = DefaultHasher::new();
     strings.default_locale_strings().hash(&mut hasher);
@@ -42,39 +101,93 @@ fn find_grouping_hash_of(strings: &LocalizableStrings) -> u64 {
 }
 
 pub struct Writer {
+    default_locale_strings: Vec<AndroidString>,
     strings_list: Vec<LocalizableStrings>,
+    dialect: CsvDialect,
 }
 
 impl Writer {
+    pub fn to_locales(&self) -> Vec<&str> {
+        self.strings_list.iter().map(|s| s.to_locale()).collect()
+    }
+
+    /// The whole CSV is built up before being encoded into the sink
     pub fn write(self, sink: &mut dyn Write) -> Result<(), InnerError> {
-        // Sink is automatically buffered
-        let mut csv_writer = csv::Writer::from_writer(sink);
-        let locale_count = self.strings_list.len();
-        let localizable_strings = self.strings_list.first().expt("Empty strings list!");
-        let value_count = localizable_strings.default_locale_strings().len();
+        let mut csv_writer = csv::WriterBuilder::new()
+            .delimiter(self.dialect.delimiter)
+            .quote(self.dialect.quote)
+            .from_writer(vec![]);
+
+        // Metadata columns are only written if there is some metadata to be shared
+        let default_locale_strings = &self.default_locale_strings;
+        let has_context = default_locale_strings.iter().any(|s| s.context().is_some());
+        let has_max_length = default_locale_strings
+            .iter()
+            .any(|s| s.max_length().is_some());
 
         // Write header record
-        let mut header = Vec::with_capacity(locale_count + 2);
-        header.push("string_name");
-        header.push("default_locale");
-        for i in 0..locale_count {
-            header.push(self.strings_list[i].to_locale());
+        let mut header = vec![
+            self.dialect.key_header.as_str(),
+            self.dialect.source_header.as_str(),
+        ];
+
+        if has_context {
+            header.push(constants::csv::CONTEXT_HEADER);
+        }
+
+        if has_max_length {
+            header.push(constants::csv::MAX_LENGTH_HEADER);
+        }
+
+        // Declared metadata columns are always written for translators to fill in
+        for metadata_header in &self.dialect.metadata_headers {
+            header.push(metadata_header);
+        }
+
+        for strings in &self.strings_list {
+            header.push(strings.to_locale());
         }
         csv_writer.write_record(header)?;
 
         // Write values
-        let mut record = vec![""; locale_count + 2];
-        for i in 0..value_count {
-            let localizable_string = localizable_strings
-                .default_locale_strings()
-                .get(i)
-                .expt("Already checked the size but it still fails!");
-            record[0] = localizable_string.name();
-            record[1] = localizable_string.value();
+        for localizable_string in default_locale_strings {
+            let mut record = vec![
+                String::from(localizable_string.name()),
+                String::from(localizable_string.value()),
+            ];
+
+            if has_context {
+                record.push(String::from(localizable_string.context().unwrap_or("")));
+            }
+
+            if has_max_length {
+                record.push(
+                    localizable_string
+                        .max_length()
+                        .map(|length| length.to_string())
+                        .unwrap_or_default(),
+                );
+            }
+
+            record.extend(self.dialect.metadata_headers.iter().map(|_| String::new()));
+
+            // Locales are empty unless there is something pre-filled for them
+            for strings in &self.strings_list {
+                record.push(String::from(
+                    strings
+                        .prefilled_value(localizable_string.name())
+                        .unwrap_or(""),
+                ));
+            }
+
             csv_writer.write_record(&record)?;
         }
 
-        csv_writer.flush()?;
+        let contents = csv_writer
+            .into_inner()
+            .map_err(|error| InnerError::from(error.into_error()))?;
+        let text = String::from_utf8_lossy(&contents);
+        sink.write_all(&self.dialect.encoding.encode(&text, true))?;
         Ok(())
     }
 }
@@ -85,10 +198,13 @@ pub trait SinkProvider {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use test_utilities;
 
     use crate::android_string::AndroidString;
     use crate::error::ResultExt;
+    use crate::format::{CsvDialect, CsvEncoding};
     use crate::localizable_strings::LocalizableStrings;
 
     use super::Error;
@@ -138,12 +254,9 @@ mod tests {
         // Convert all the written bytes into strings
         let mut sink_provider = ByteSinkProvider { data: vec![] };
 
-        super::write(strings_list, &mut sink_provider).unwrap();
+        super::write(strings_list, &CsvDialect::default(), &mut sink_provider).unwrap();
 
-        // Since a map is used, sort the contents to be sure of the order
-        sink_provider.data.sort();
-
-        // Time to assert
+        // Time to assert. Groups must be in the order they are first seen in
         test_utilities::list::assert_strict_list_eq(
             sink_provider.data,
             vec![
@@ -153,4 +266,151 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn writes_strings_to_a_file_per_locale() {
+        let default_strings = vec![AndroidString::localizable("string_1", "english 1")];
+        let strings_list = vec![
+            LocalizableStrings::new(String::from("french"), default_strings.clone()),
+            LocalizableStrings::new(String::from("spanish"), default_strings),
+        ];
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write_per_locale(strings_list, &CsvDialect::default(), &mut sink_provider).unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            sink_provider.data,
+            vec![
+                String::from("string_name,default_locale,french\nstring_1,english 1,\n"),
+                String::from("string_name,default_locale,spanish\nstring_1,english 1,\n"),
+            ],
+        );
+    }
+
+    #[test]
+    fn writes_combined_strings_to_a_single_file() {
+        let strings_list = vec![
+            LocalizableStrings::new(
+                String::from("french"),
+                vec![AndroidString::localizable("string_1", "english 1")],
+            ),
+            LocalizableStrings::new(
+                String::from("spanish"),
+                vec![
+                    AndroidString::localizable("string_2", "english 2"),
+                    AndroidString::localizable("string_1", "english 1"),
+                ],
+            ),
+        ];
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write_combined(strings_list, &CsvDialect::default(), &mut sink_provider).unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            sink_provider.data,
+            vec![String::from(
+                "string_name,default_locale,french,spanish\nstring_1,english 1,,\nstring_2,english 2,,\n",
+            )],
+        );
+    }
+
+    #[test]
+    fn writes_prefilled_values() {
+        let default_strings = vec![
+            AndroidString::localizable("string_1", "english 1"),
+            AndroidString::localizable("string_2", "english 2"),
+        ];
+
+        let mut prefilled_values = HashMap::new();
+        prefilled_values.insert(String::from("string_2"), String::from("french 2"));
+
+        let strings_list = vec![
+            LocalizableStrings::with_prefilled_values(
+                String::from("french"),
+                default_strings.clone(),
+                prefilled_values,
+            ),
+            LocalizableStrings::new(String::from("spanish"), default_strings),
+        ];
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write(strings_list, &CsvDialect::default(), &mut sink_provider).unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            sink_provider.data,
+            vec![String::from(
+                "string_name,default_locale,french,spanish\nstring_1,english 1,,\nstring_2,english 2,french 2,\n",
+            )],
+        );
+    }
+
+    #[test]
+    fn writes_metadata_columns_only_if_required() {
+        let strings_list = vec![LocalizableStrings::new(
+            String::from("french"),
+            vec![
+                AndroidString::localizable_with_context("string_1", "english 1", "Title"),
+                AndroidString::localizable_with_max_length("string_2", "english 2", 12),
+            ],
+        )];
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write(strings_list, &CsvDialect::default(), &mut sink_provider).unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            sink_provider.data,
+            vec![String::from(
+                "string_name,default_locale,context,max_length,french\nstring_1,english 1,Title,,\nstring_2,english 2,,12,\n",
+            )],
+        );
+    }
+
+    #[test]
+    fn writes_strings_as_per_dialect() {
+        let strings_list = vec![LocalizableStrings::new(
+            String::from("french"),
+            vec![AndroidString::localizable("string_1", "english; 1")],
+        )];
+
+        let dialect = CsvDialect {
+            delimiter: b';',
+            quote: b'\'',
+            encoding: CsvEncoding::Utf8Bom,
+            key_header: String::from("key"),
+            source_header: String::from("source"),
+            ..Default::default()
+        };
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write(strings_list, &dialect, &mut sink_provider).unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            sink_provider.data,
+            vec![String::from(
+                "\u{FEFF}key;source;french\nstring_1;'english; 1';\n",
+            )],
+        );
+    }
+    #[test]
+    fn writes_declared_metadata_columns() {
+        let strings_list = vec![LocalizableStrings::new(
+            String::from("french"),
+            vec![AndroidString::localizable("string_1", "english 1")],
+        )];
+
+        let dialect = CsvDialect {
+            metadata_headers: vec![String::from("notes"), String::from("status")],
+            ..Default::default()
+        };
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write(strings_list, &dialect, &mut sink_provider).unwrap();
+
+        test_utilities::list::assert_strict_list_eq(
+            sink_provider.data,
+            vec![String::from(
+                "string_name,default_locale,notes,status,french\nstring_1,english 1,,,\n",
+            )],
+        );
+    }
 }
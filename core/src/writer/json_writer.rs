@@ -0,0 +1,230 @@
+use std::io::Write;
+
+use serde_json::{Map, Value};
+
+use crate::constants;
+use crate::error::{Error, InnerError};
+use crate::format::{Format, JsonFlavor};
+use crate::localizable_strings::LocalizableStrings;
+
+/// Unlike CSVs which can carry multiple locales in the same file, a JSON/ARB
+/// document is written for each locale. Plain JSON documents are flat (string
+/// name to text) & so, only ARB documents carry the metadata of the strings
+pub fn write(
+    strings_list: Vec<LocalizableStrings>,
+    flavor: JsonFlavor,
+    sink_provider: &mut dyn SinkProvider,
+) -> Result<(), Error> {
+    for strings in strings_list {
+        sink_provider.execute_with_new_sink(Writer { strings, flavor })?;
+    }
+
+    Ok(())
+}
+
+pub struct Writer {
+    strings: LocalizableStrings,
+    flavor: JsonFlavor,
+}
+
+impl Writer {
+    pub fn to_locale(&self) -> &str {
+        self.strings.to_locale()
+    }
+
+    pub fn format(&self) -> Format {
+        Format::Json(self.flavor)
+    }
+
+    pub fn write(self, sink: &mut dyn Write) -> Result<(), InnerError> {
+        let mut document = Map::new();
+        if self.flavor == JsonFlavor::Arb {
+            document.insert(
+                String::from(constants::json::LOCALE_KEY),
+                Value::from(self.strings.to_locale()),
+            );
+        }
+
+        for string in self.strings.default_locale_strings() {
+            if self.flavor == JsonFlavor::Plain {
+                document.insert(String::from(string.name()), Value::from(string.value()));
+                continue;
+            }
+
+            // ARB carries the default text in the metadata & so the pre-filled
+            // text can take its place
+            let value = self
+                .strings
+                .prefilled_value(string.name())
+                .unwrap_or_else(|| string.value());
+
+            document.insert(String::from(string.name()), Value::from(value));
+
+            // The default text is carried along so that when the localized texts
+            // come back, they can be checked against the current default text
+            let mut metadata = Map::new();
+            metadata.insert(
+                String::from(constants::json::SOURCE_METADATA_KEY),
+                Value::from(string.value()),
+            );
+
+            // ARB already has a standard place for notes to translators
+            if let Some(context) = string.context() {
+                metadata.insert(
+                    String::from(constants::json::DESCRIPTION_METADATA_KEY),
+                    Value::from(context),
+                );
+            }
+
+            // ARB only allows custom metadata if it is prefixed with `x-`
+            if let Some(max_length) = string.max_length() {
+                metadata.insert(
+                    String::from(constants::json::ARB_MAX_LENGTH_METADATA_KEY),
+                    Value::from(max_length),
+                );
+            }
+
+            document.insert(
+                format!("{}{}", constants::json::METADATA_KEY_PREFIX, string.name()),
+                Value::Object(metadata),
+            );
+        }
+
+        serde_json::to_writer_pretty(&mut *sink, &Value::Object(document))?;
+        writeln!(sink)?;
+        sink.flush()?;
+        Ok(())
+    }
+}
+
+pub trait SinkProvider {
+    fn execute_with_new_sink(&mut self, writer: Writer) -> Result<(), Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_utilities;
+
+    use crate::android_string::AndroidString;
+    use crate::error::ResultExt;
+    use crate::format::JsonFlavor;
+    use crate::localizable_strings::LocalizableStrings;
+
+    use super::Error;
+    use super::SinkProvider;
+    use super::Writer;
+
+    struct ByteSinkProvider {
+        data: Vec<(String, String)>,
+    }
+
+    impl SinkProvider for ByteSinkProvider {
+        fn execute_with_new_sink(&mut self, writer: Writer) -> Result<(), Error> {
+            let locale = String::from(writer.to_locale());
+            let mut contents = vec![];
+            let result = writer.write(&mut contents);
+            self.data
+                .push((locale, String::from_utf8(contents).unwrap()));
+            result.with_context("added context for tests")
+        }
+    }
+
+    #[test]
+    fn writes_plain_json() {
+        test_write(
+            JsonFlavor::Plain,
+            vec![
+                (
+                    String::from("french"),
+                    String::from(
+                        r#"{
+  "string_2": "english 2",
+  "string_1": "english 1"
+}
+"#,
+                    ),
+                ),
+                (
+                    String::from("spanish"),
+                    String::from(
+                        r#"{
+  "string_2": "english 2"
+}
+"#,
+                    ),
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn writes_arb() {
+        test_write(
+            JsonFlavor::Arb,
+            vec![
+                (
+                    String::from("french"),
+                    String::from(
+                        r#"{
+  "@@locale": "french",
+  "string_2": "french 2",
+  "@string_2": {
+    "x-source": "english 2"
+  },
+  "string_1": "english 1",
+  "@string_1": {
+    "x-source": "english 1",
+    "description": "Title"
+  }
+}
+"#,
+                    ),
+                ),
+                (
+                    String::from("spanish"),
+                    String::from(
+                        r#"{
+  "@@locale": "spanish",
+  "string_2": "english 2",
+  "@string_2": {
+    "x-source": "english 2",
+    "x-max-length": 12
+  }
+}
+"#,
+                    ),
+                ),
+            ],
+        )
+    }
+
+    fn test_write(flavor: JsonFlavor, expected: Vec<(String, String)>) {
+        let mut prefilled_values = HashMap::new();
+        prefilled_values.insert(String::from("string_2"), String::from("french 2"));
+
+        let strings_list = vec![
+            LocalizableStrings::with_prefilled_values(
+                String::from("french"),
+                vec![
+                    AndroidString::localizable("string_2", "english 2"),
+                    AndroidString::localizable_with_context("string_1", "english 1", "Title"),
+                ],
+                prefilled_values,
+            ),
+            LocalizableStrings::new(
+                String::from("spanish"),
+                vec![AndroidString::localizable_with_max_length(
+                    "string_2",
+                    "english 2",
+                    12,
+                )],
+            ),
+        ];
+
+        let mut sink_provider = ByteSinkProvider { data: vec![] };
+        super::write(strings_list, flavor, &mut sink_provider).unwrap();
+        test_utilities::list::assert_strict_list_eq(sink_provider.data, expected);
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::Write;
+
+use xml::escape::escape_str_pcdata;
+
+use crate::constants::stringsdict;
+use crate::error::InnerError;
+use crate::reader::stringsdict_reader::ApplePlurals;
+
+const HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>"#;
+
+const FOOTER: &str = "</dict>\n</plist>";
+
+/// Writes out plurals into an Apple `.stringsdict` file. Every plural gets
+/// a single integer variable which spans the whole of the localized format
+pub fn write<S: Write>(sink: &mut S, plurals: Vec<ApplePlurals>) -> Result<(), InnerError> {
+    writeln!(sink, "{}", HEADER)?;
+    for (name, items) in plurals {
+        write_entry(sink, 1, stringsdict::KEY, &name)?;
+        write_line(sink, 1, "<dict>")?;
+        write_entry(sink, 2, stringsdict::KEY, stringsdict::LOCALIZED_FORMAT_KEY)?;
+        write_entry(
+            sink,
+            2,
+            stringsdict::STRING,
+            &format!("%#@{}@", stringsdict::VARIABLE_NAME),
+        )?;
+
+        write_entry(sink, 2, stringsdict::KEY, stringsdict::VARIABLE_NAME)?;
+        write_line(sink, 2, "<dict>")?;
+        write_entry(sink, 3, stringsdict::KEY, stringsdict::SPEC_TYPE_KEY)?;
+        write_entry(sink, 3, stringsdict::STRING, stringsdict::PLURAL_RULE_TYPE)?;
+        write_entry(sink, 3, stringsdict::KEY, stringsdict::VALUE_TYPE_KEY)?;
+        write_entry(sink, 3, stringsdict::STRING, "d")?;
+        for (quantity, value) in items {
+            write_entry(sink, 3, stringsdict::KEY, quantity.name())?;
+            write_entry(sink, 3, stringsdict::STRING, &value)?;
+        }
+
+        write_line(sink, 2, "</dict>")?;
+        write_line(sink, 1, "</dict>")?;
+    }
+
+    writeln!(sink, "{}", FOOTER)?;
+    Ok(())
+}
+
+fn write_entry<S: Write>(
+    sink: &mut S,
+    depth: usize,
+    element_name: &str,
+    text: &str,
+) -> Result<(), InnerError> {
+    write_line(
+        sink,
+        depth,
+        &format!(
+            "<{0}>{1}</{0}>",
+            element_name,
+            escape_str_pcdata(text).replace('>', "&gt;")
+        ),
+    )
+}
+
+fn write_line<S: Write>(sink: &mut S, depth: usize, line: &str) -> Result<(), InnerError> {
+    writeln!(sink, "{}{}", "    ".repeat(depth), line).map_err(InnerError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::android_plurals::Quantity;
+    use crate::reader::stringsdict_reader;
+
+    #[test]
+    fn writes_plurals_which_could_be_read_back() {
+        let mut items = BTreeMap::new();
+        items.insert(Quantity::Other, String::from("%d apples & <pears>"));
+        items.insert(Quantity::One, String::from("%d apple"));
+        let plurals = vec![(String::from("apples"), items)];
+
+        let mut sink: Vec<u8> = vec![];
+        super::write(&mut sink, plurals.clone()).unwrap();
+
+        let written = String::from_utf8(sink.clone()).unwrap();
+        assert!(written.contains("        <key>NSStringLocalizedFormatKey</key>\n"));
+        assert!(written.contains("            <string>%d apples &amp; &lt;pears&gt;</string>\n"));
+        assert_eq!(stringsdict_reader::read(sink.as_slice()).unwrap(), plurals)
+    }
+}
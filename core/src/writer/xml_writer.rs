@@ -1,19 +1,50 @@
 use std::io::BufWriter;
 use std::io::Write;
 
+use regex::Regex;
 use xml::reader::XmlEvent as ReadXmlEvent;
 use xml::writer;
 use xml::writer::XmlEvent as WriteXmlEvent;
 use xml::EmitterConfig;
 use xml::ParserConfig;
 
+use android_localization_utilities::DevExpt;
+
+use crate::android_plurals::AndroidPlurals;
 use crate::android_string::AndroidString;
 use crate::constants;
-use crate::error::InnerError;
+use crate::error::{ErrorKind, InnerError};
+
+lazy_static::lazy_static! {
+    static ref ENTITY_REGEX: Regex =
+        Regex::new("^&(amp|lt|gt|quot|apos|#[0-9]+|#x[0-9a-fA-F]+);").expt("Invalid regex!");
+}
 
+/// Only tests write out strings without plurals
+#[cfg(test)]
 pub fn write<S: Write>(
     sink: &mut S,
     android_strings: Vec<AndroidString>,
+) -> Result<(), InnerError> {
+    write_with_plurals(sink, android_strings, vec![])
+}
+
+/// Returns what `write_with_plurals` would write out. Handy to check whether a
+/// file would change before writing it out
+pub fn serialize(
+    android_strings: Vec<AndroidString>,
+    android_plurals: Vec<AndroidPlurals>,
+) -> Result<Vec<u8>, InnerError> {
+    let mut content = vec![];
+    write_with_plurals(&mut content, android_strings, android_plurals)?;
+    Ok(content)
+}
+
+/// Plurals are written out after all the strings
+pub fn write_with_plurals<S: Write>(
+    sink: &mut S,
+    android_strings: Vec<AndroidString>,
+    android_plurals: Vec<AndroidPlurals>,
 ) -> Result<(), InnerError> {
     let mut writer = EmitterConfig::new()
         .perform_indent(true)
@@ -41,12 +72,79 @@ pub fn write<S: Write>(
         writer.write(WriteXmlEvent::end_element())?;
     }
 
+    // Write all plurals elements
+    for android_plurals in android_plurals {
+        let mut plurals_element = WriteXmlEvent::start_element(constants::elements::PLURALS)
+            .attr(constants::attributes::NAME, android_plurals.name());
+
+        if !android_plurals.is_localizable() {
+            plurals_element =
+                plurals_element.attr(constants::attributes::LOCALIZABLE, constants::flags::FALSE);
+        }
+
+        writer.write(plurals_element)?;
+        for (quantity, value) in android_plurals.items() {
+            writer.write(
+                WriteXmlEvent::start_element(constants::elements::ITEM)
+                    .attr(constants::attributes::QUANTITY, quantity.name()),
+            )?;
+            write_string(&mut writer, value)?;
+            writer.write(WriteXmlEvent::end_element())?;
+        }
+
+        writer.write(WriteXmlEvent::end_element())?;
+    }
+
     // Ending resources
     writer.write(WriteXmlEvent::end_element())?;
 
     Ok(())
 }
 
+/// Values are written out as the content of a `string` (or `item`) element.
+/// Errors with what is wrong if the value isn't well formed as such (like when
+/// it has a stray `<` or `&`) or if it has markup (like `<b>`) as only text &
+/// CDATA sections are written out
+pub fn check_value(value: &str) -> Result<(), String> {
+    let value = format!("<a>{}</a>", value);
+    let mut is_wrapper_element = true;
+    for element_or_error in ParserConfig::new().create_reader(value.as_bytes()) {
+        match element_or_error {
+            Err(error) => return Err(String::from(error.msg())),
+            Ok(ReadXmlEvent::StartElement { name, .. }) => {
+                if !is_wrapper_element {
+                    return Err(format!(
+                        "Tag <{}> would be dropped. Wrap it in a CDATA section to keep it",
+                        name
+                    ));
+                }
+
+                is_wrapper_element = false
+            }
+
+            Ok(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Escapes `<`, `>` & the `&`s that don't start a predefined or a numeric entity
+/// (`&amp;`, `&#8230;` etc.) so that the whole value is read back as plain text
+pub fn escape_value(value: &str) -> String {
+    let mut escaped_value = String::with_capacity(value.len());
+    for (index, c) in value.char_indices() {
+        match c {
+            '<' => escaped_value.push_str("&lt;"),
+            '>' => escaped_value.push_str("&gt;"),
+            '&' if !ENTITY_REGEX.is_match(&value[index..]) => escaped_value.push_str("&amp;"),
+            _ => escaped_value.push(c),
+        }
+    }
+
+    escaped_value
+}
+
 fn write_string<W: Write>(
     writer: &mut writer::EventWriter<W>,
     value: &str,
@@ -65,13 +163,19 @@ fn write_string<W: Write>(
             Ok(ref element) => match element {
                 ReadXmlEvent::Characters(_) => {
                     writer.write(element.as_writer_event().ok_or_else(|| {
-                        InnerError::from(format!("Can't build writer event from {}", &value))
+                        InnerError::from(ErrorKind::InvalidStringsXml(format!(
+                            "Can't build writer event from {}",
+                            &value
+                        )))
                     })?)
                 }
 
                 ReadXmlEvent::CData(_) => {
                     writer.write(element.as_writer_event().ok_or_else(|| {
-                        InnerError::from(format!("Can't build writer event from {}", &value))
+                        InnerError::from(ErrorKind::InvalidStringsXml(format!(
+                            "Can't build writer event from {}",
+                            &value
+                        )))
                     })?)
                 }
 
@@ -87,8 +191,28 @@ fn write_string<W: Write>(
 mod tests {
     use test_utilities;
 
+    use crate::android_plurals::{AndroidPlurals, Quantity};
     use crate::android_string::AndroidString;
 
+    #[test]
+    fn checks_values() {
+        assert!(super::check_value("Tom &amp; Jerry <![CDATA[<b>]]>").is_ok());
+        assert!(super::check_value("Tom &amp; <b>Jerry</b>").is_err());
+        assert!(super::check_value("Tom & Jerry").is_err());
+        assert!(super::check_value("1 < 2").is_err());
+        assert!(super::check_value("<b>Tom").is_err());
+    }
+
+    #[test]
+    fn escapes_values() {
+        let escaped_value = super::escape_value("Tom & Jerry &amp; <b>1 > 0 &#8230;");
+        assert_eq!(
+            escaped_value,
+            "Tom &amp; Jerry &amp; &lt;b&gt;1 &gt; 0 &#8230;"
+        );
+        assert!(super::check_value(&escaped_value).is_ok());
+    }
+
     #[test]
     fn writes_strings_to_file() {
         let android_strings = vec![
@@ -114,6 +238,41 @@ mod tests {
         )
     }
 
+    #[test]
+    fn writes_plurals_after_strings() {
+        let mut sink: Vec<u8> = vec![];
+        super::write_with_plurals(
+            &mut sink,
+            vec![AndroidString::localizable("s1", "value")],
+            vec![
+                AndroidPlurals::localizable(
+                    "p1",
+                    vec![(Quantity::Other, "%d items"), (Quantity::One, "%d item")],
+                ),
+                AndroidPlurals::unlocalizable("p2", vec![(Quantity::Other, "%d")]),
+            ],
+        )
+        .unwrap();
+
+        let written_content = String::from_utf8(sink).unwrap();
+        test_utilities::list::assert_strict_list_eq(
+            written_content.lines().collect::<Vec<&str>>(),
+            vec![
+                r##"<?xml version="1.0" encoding="utf-8"?>"##,
+                r##"<resources>"##,
+                r##"    <string name="s1">value</string>"##,
+                r##"    <plurals name="p1">"##,
+                r##"        <item quantity="one">%d item</item>"##,
+                r##"        <item quantity="other">%d items</item>"##,
+                r##"    </plurals>"##,
+                r##"    <plurals name="p2" translatable="false">"##,
+                r##"        <item quantity="other">%d</item>"##,
+                r##"    </plurals>"##,
+                r##"</resources>"##,
+            ],
+        )
+    }
+
     #[test]
     fn writes_string_with_one_cdata_event() {
         test_cdata_handling("<![CDATA[this is a test]]>")
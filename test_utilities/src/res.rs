@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 pub struct FileWithPath {
     pub file: File,
-    pub path: String,
+    pub path: PathBuf,
 }
 
 pub fn setup_values_dir_for_default_locale<P: AsRef<Path>>(res_path: P) -> String {
@@ -52,6 +52,6 @@ fn setup_strings_file<P: AsRef<Path>, L: AsRef<str>>(
 
     FileWithPath {
         file,
-        path: String::from(strings_file_path.to_str().unwrap()),
+        path: strings_file_path,
     }
 }